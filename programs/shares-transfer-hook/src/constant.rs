@@ -0,0 +1,13 @@
+use anchor_lang::prelude::*;
+
+/// Vault program managing the share mints and their address lists.
+pub const VAULT_PROGRAM_ID: Pubkey = pubkey!("8wjJau9UuUBHBWiafvh2svxp4rCqkDpcUa1j13EdYh5C");
+
+// Seeds mirrored from the vault program
+pub const VAULT_AUTHORITY_SEED: &str = "vault_authority";
+pub const SHARES_MINT_SEED: &str = "shares_mint";
+pub const BLOCKED_ADDRESS_SEED: &str = "blocked_address";
+pub const ALLOWED_ADDRESS_SEED: &str = "allowed_address";
+
+pub const EXTRA_ACCOUNT_METAS_SEED: &str = "extra-account-metas";
+pub const HOOK_CONFIG_SEED: &str = "hook_config";
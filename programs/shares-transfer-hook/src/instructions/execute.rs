@@ -0,0 +1,126 @@
+use crate::constant::{EXTRA_ACCOUNT_METAS_SEED, HOOK_CONFIG_SEED, VAULT_PROGRAM_ID};
+use crate::state::HookConfig;
+use crate::util::Errors;
+use anchor_lang::prelude::*;
+use anchor_spl::token_2022::spl_token_2022::extension::transfer_hook::TransferHookAccount;
+use anchor_spl::token_2022::spl_token_2022::extension::{
+    BaseStateWithExtensions, StateWithExtensions,
+};
+use anchor_spl::token_2022::spl_token_2022::state::Account as TokenAccountState;
+use anchor_spl::token_interface::{Mint, TokenAccount};
+
+/// Execute accounts, in the order Token-2022 passes them:
+/// - source_token: sender's shares account
+/// - shares_mint: vault's shares mint
+/// - destination_token: recipient's shares account
+/// - owner: sender or its delegate
+/// - extra_account_meta_list: extra accounts of the shares mint
+/// - hook_config: address list mode of the shares mint
+/// - vault_program
+/// - vault: vault managing the address list
+/// - source_entry: vault list PDA of the source owner, may not exist
+/// - destination_entry: vault list PDA of the destination owner, may not exist
+#[derive(Accounts)]
+pub struct Execute<'info> {
+    #[account(token::mint = shares_mint)]
+    source_token: Box<InterfaceAccount<'info, TokenAccount>>,
+    shares_mint: Box<InterfaceAccount<'info, Mint>>,
+    #[account(token::mint = shares_mint)]
+    destination_token: Box<InterfaceAccount<'info, TokenAccount>>,
+    /// CHECK: authority of the transfer, checked by Token-2022
+    owner: UncheckedAccount<'info>,
+    /// CHECK: checked by seeds, read by Token-2022
+    #[account(
+        seeds = [EXTRA_ACCOUNT_METAS_SEED.as_bytes(), shares_mint.key().as_ref()],
+        bump
+    )]
+    extra_account_meta_list: UncheckedAccount<'info>,
+    #[account(
+        seeds = [HOOK_CONFIG_SEED.as_bytes(), shares_mint.key().as_ref()],
+        bump = hook_config.bump
+    )]
+    hook_config: Box<Account<'info, HookConfig>>,
+    /// CHECK: vault program id
+    #[account(address = VAULT_PROGRAM_ID)]
+    vault_program: UncheckedAccount<'info>,
+    /// CHECK: vault of the hook config
+    #[account(address = hook_config.vault)]
+    vault: UncheckedAccount<'info>,
+    /// CHECK: address checked by seeds, only its existence is read
+    #[account(
+        seeds = [
+            HookConfig::list_seed(hook_config.mode),
+            vault.key().as_ref(),
+            source_token.owner.as_ref()
+        ],
+        bump,
+        seeds::program = VAULT_PROGRAM_ID
+    )]
+    source_entry: UncheckedAccount<'info>,
+    /// CHECK: address checked by seeds, only its existence is read
+    #[account(
+        seeds = [
+            HookConfig::list_seed(hook_config.mode),
+            vault.key().as_ref(),
+            destination_token.owner.as_ref()
+        ],
+        bump,
+        seeds::program = VAULT_PROGRAM_ID
+    )]
+    destination_entry: UncheckedAccount<'info>,
+}
+
+/// Called by Token-2022 on every shares transfer. In blocklist mode neither owner may be
+/// blocked, in allowlist mode both must be allowed. The vault authority is exempt so the
+/// vault's own escrow flows keep working.
+pub fn handle(ctx: Context<Execute>, amount: u64) -> Result<()> {
+    check_is_transferring(&ctx.accounts.source_token.to_account_info())?;
+
+    let config = &ctx.accounts.hook_config;
+    let source_owner = ctx.accounts.source_token.owner;
+    let destination_owner = ctx.accounts.destination_token.owner;
+
+    msg!(
+        "checking transfer of {} shares from {} to {}",
+        amount,
+        source_owner,
+        destination_owner
+    );
+
+    let source_listed = is_listed(&ctx.accounts.source_entry);
+    let destination_listed = is_listed(&ctx.accounts.destination_entry);
+    let source_exempt = source_owner == config.vault_authority;
+    let destination_exempt = destination_owner == config.vault_authority;
+
+    if config.mode == HookConfig::ALLOWLIST {
+        require!(source_exempt || source_listed, Errors::SourceNotAllowed);
+        require!(
+            destination_exempt || destination_listed,
+            Errors::DestinationNotAllowed
+        );
+    } else {
+        require!(source_exempt || !source_listed, Errors::SourceBlocked);
+        require!(
+            destination_exempt || !destination_listed,
+            Errors::DestinationBlocked
+        );
+    }
+
+    Ok(())
+}
+
+/// Only the vault program can create data at a list PDA, its existence is the flag.
+fn is_listed(entry: &AccountInfo) -> bool {
+    *entry.owner == VAULT_PROGRAM_ID && !entry.data_is_empty()
+}
+
+/// Rejects direct calls: Token-2022 flags the source account while the hook runs.
+fn check_is_transferring(source_token: &AccountInfo) -> Result<()> {
+    let data = source_token.try_borrow_data()?;
+    let account = StateWithExtensions::<TokenAccountState>::unpack(&data)?;
+    let extension = account
+        .get_extension::<TransferHookAccount>()
+        .map_err(|_| Errors::NotTransferring)?;
+    require!(bool::from(extension.transferring), Errors::NotTransferring);
+    Ok(())
+}
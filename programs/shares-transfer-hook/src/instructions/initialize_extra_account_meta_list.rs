@@ -0,0 +1,154 @@
+use crate::constant::{
+    EXTRA_ACCOUNT_METAS_SEED, HOOK_CONFIG_SEED, SHARES_MINT_SEED, VAULT_AUTHORITY_SEED,
+    VAULT_PROGRAM_ID,
+};
+use crate::state::HookConfig;
+use crate::util::Errors;
+use anchor_lang::prelude::*;
+use anchor_spl::token_2022::spl_token_2022::extension::transfer_hook::TransferHook;
+use anchor_spl::token_interface::{get_mint_extension_data, Mint};
+use spl_tlv_account_resolution::account::ExtraAccountMeta;
+use spl_tlv_account_resolution::seeds::Seed;
+use spl_tlv_account_resolution::state::ExtraAccountMetaList;
+use spl_transfer_hook_interface::instruction::ExecuteInstruction;
+
+/// Initialize extra account meta list accounts:
+/// - payer: pays for the accounts (vault admin)
+/// - vault_authority: vault authority PDA of `vault`, mint authority of the shares mint
+/// - shares_mint: vault's shares mint, must point its transfer hook to this program
+/// - extra_account_meta_list: extra accounts resolved by Token-2022 on every transfer, created
+/// - hook_config: address list mode of the shares mint, created
+/// - system_program
+#[derive(Accounts)]
+#[instruction(vault: Pubkey)]
+pub struct InitializeExtraAccountMetaList<'info> {
+    #[account(mut)]
+    payer: Signer<'info>,
+    vault_authority: Signer<'info>,
+    #[account(mint::authority = vault_authority)]
+    shares_mint: Box<InterfaceAccount<'info, Mint>>,
+    /// CHECK: TLV data written by the handler, checked by seeds
+    #[account(
+        init,
+        payer = payer,
+        space = ExtraAccountMetaList::size_of(HOOK_EXTRA_ACCOUNTS)?,
+        seeds = [EXTRA_ACCOUNT_METAS_SEED.as_bytes(), shares_mint.key().as_ref()],
+        bump
+    )]
+    extra_account_meta_list: UncheckedAccount<'info>,
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + HookConfig::MAX_SIZE,
+        seeds = [HOOK_CONFIG_SEED.as_bytes(), shares_mint.key().as_ref()],
+        bump
+    )]
+    hook_config: Box<Account<'info, HookConfig>>,
+    system_program: Program<'info, System>,
+}
+
+/// Number of extra accounts appended to `execute`.
+const HOOK_EXTRA_ACCOUNTS: usize = 5;
+
+/// Binds the shares mint of `vault` to an address list and stores the extra accounts
+/// Token-2022 passes to `execute`: the hook config, the vault program, the vault and
+/// the list entries of the source and destination owners.
+/// Called by the vault program during `initialize`, signed by the vault authority.
+/// - vault: vault owning the shares mint
+/// - mode: `HookConfig::BLOCKLIST` or `HookConfig::ALLOWLIST`
+pub fn handle(ctx: Context<InitializeExtraAccountMetaList>, vault: Pubkey, mode: u8) -> Result<()> {
+    require!(
+        mode == HookConfig::BLOCKLIST || mode == HookConfig::ALLOWLIST,
+        Errors::InvalidHookMode
+    );
+
+    // The signer must be the vault authority PDA of `vault`, owning the shares mint PDA
+    let vault_authority = ctx.accounts.vault_authority.key();
+    let (expected_authority, _) = Pubkey::find_program_address(
+        &[VAULT_AUTHORITY_SEED.as_bytes(), vault.as_ref()],
+        &VAULT_PROGRAM_ID,
+    );
+    let (expected_mint, _) = Pubkey::find_program_address(
+        &[SHARES_MINT_SEED.as_bytes(), vault_authority.as_ref()],
+        &VAULT_PROGRAM_ID,
+    );
+    require_keys_eq!(
+        vault_authority,
+        expected_authority,
+        Errors::InvalidVaultAuthority
+    );
+    require_keys_eq!(
+        ctx.accounts.shares_mint.key(),
+        expected_mint,
+        Errors::InvalidVaultAuthority
+    );
+
+    let hook = get_mint_extension_data::<TransferHook>(&ctx.accounts.shares_mint.to_account_info())
+        .map_err(|_| Errors::HookNotConfigured)?;
+    require!(
+        Option::<Pubkey>::from(hook.program_id) == Some(crate::ID),
+        Errors::HookNotConfigured
+    );
+
+    msg!(
+        "initializing transfer hook of shares mint {} for vault {} (mode {})",
+        ctx.accounts.shares_mint.key(),
+        vault,
+        mode
+    );
+
+    let hook_config = &mut ctx.accounts.hook_config;
+    hook_config.vault = vault;
+    hook_config.vault_authority = vault_authority;
+    hook_config.shares_mint = ctx.accounts.shares_mint.key();
+    hook_config.mode = mode;
+    hook_config.bump = ctx.bumps.hook_config;
+
+    // Execute accounts: 0 source, 1 mint, 2 destination, 3 owner, 4 meta list, then these
+    let list_seed = HookConfig::list_seed(mode).to_vec();
+    let owner_entry = |token_account_index: u8| {
+        ExtraAccountMeta::new_external_pda_with_seeds(
+            6,
+            &[
+                Seed::Literal {
+                    bytes: list_seed.clone(),
+                },
+                Seed::AccountKey { index: 7 },
+                // token account owner
+                Seed::AccountData {
+                    account_index: token_account_index,
+                    data_index: 32,
+                    length: 32,
+                },
+            ],
+            false,
+            false,
+        )
+    };
+    let metas = [
+        ExtraAccountMeta::new_with_pubkey(&ctx.accounts.hook_config.key(), false, false)?,
+        ExtraAccountMeta::new_with_pubkey(&VAULT_PROGRAM_ID, false, false)?,
+        ExtraAccountMeta::new_with_pubkey(&vault, false, false)?,
+        owner_entry(0)?,
+        owner_entry(2)?,
+    ];
+    ExtraAccountMetaList::init::<ExecuteInstruction>(
+        &mut ctx.accounts.extra_account_meta_list.try_borrow_mut_data()?,
+        &metas,
+    )?;
+
+    emit!(InitializeHookEvent {
+        vault,
+        shares_mint: ctx.accounts.shares_mint.key(),
+        mode,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct InitializeHookEvent {
+    pub vault: Pubkey,
+    pub shares_mint: Pubkey,
+    pub mode: u8,
+}
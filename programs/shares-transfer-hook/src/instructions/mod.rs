@@ -0,0 +1,5 @@
+pub mod initialize_extra_account_meta_list;
+pub use initialize_extra_account_meta_list::*;
+
+pub mod execute;
+pub use execute::*;
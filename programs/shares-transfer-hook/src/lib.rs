@@ -0,0 +1,30 @@
+use anchor_lang::prelude::*;
+use spl_discriminator::SplDiscriminate;
+use spl_transfer_hook_interface::instruction::ExecuteInstruction;
+
+pub mod constant;
+mod instructions;
+pub mod state;
+pub mod util;
+
+use instructions::*;
+
+declare_id!("CoQGVKx2pRj7kaiP3omK7ZsGHhG8v2LUKzNd8BuRYQjz");
+
+#[program]
+pub mod shares_transfer_hook {
+    use super::*;
+
+    pub fn initialize_extra_account_meta_list(
+        ctx: Context<InitializeExtraAccountMetaList>,
+        vault: Pubkey,
+        mode: u8,
+    ) -> Result<()> {
+        initialize_extra_account_meta_list::handle(ctx, vault, mode)
+    }
+
+    #[instruction(discriminator = ExecuteInstruction::SPL_DISCRIMINATOR_SLICE)]
+    pub fn execute(ctx: Context<Execute>, amount: u64) -> Result<()> {
+        execute::handle(ctx, amount)
+    }
+}
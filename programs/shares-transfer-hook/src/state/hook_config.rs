@@ -0,0 +1,33 @@
+use crate::constant::{ALLOWED_ADDRESS_SEED, BLOCKED_ADDRESS_SEED};
+use anchor_lang::prelude::*;
+
+/// Address list enforced on the transfers of one vault's shares mint.
+#[account]
+pub struct HookConfig {
+    pub vault: Pubkey,           // Vault managing the address list
+    pub vault_authority: Pubkey, // Vault authority, exempt from the list (escrow flows)
+    pub shares_mint: Pubkey,     // Shares mint pointing to this hook
+    pub mode: u8,                // BLOCKLIST or ALLOWLIST
+    pub bump: u8,                // hook config bump
+}
+
+impl HookConfig {
+    /// Owners with a vault `BlockedAddress` PDA can't send nor receive shares
+    pub const BLOCKLIST: u8 = 1;
+    /// Only owners with a vault `AllowedAddress` PDA can send and receive shares
+    pub const ALLOWLIST: u8 = 2;
+
+    pub const MAX_SIZE: usize = 32 + // Pubkey: vault
+        32 + // Pubkey: vault_authority
+        32 + // Pubkey: shares_mint
+        1 + // u8: mode
+        1; // u8: bump
+
+    /// Seed prefix of the vault PDAs listing wallets in `mode`.
+    pub fn list_seed(mode: u8) -> &'static [u8] {
+        match mode {
+            Self::ALLOWLIST => ALLOWED_ADDRESS_SEED.as_bytes(),
+            _ => BLOCKED_ADDRESS_SEED.as_bytes(),
+        }
+    }
+}
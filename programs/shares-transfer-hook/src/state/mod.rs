@@ -0,0 +1,2 @@
+pub mod hook_config;
+pub use hook_config::*;
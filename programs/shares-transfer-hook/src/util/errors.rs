@@ -0,0 +1,21 @@
+use anchor_lang::prelude::*;
+
+#[error_code]
+pub enum Errors {
+    #[msg("Invalid hook mode")]
+    InvalidHookMode,
+    #[msg("Signer is not the vault authority of the shares mint")]
+    InvalidVaultAuthority,
+    #[msg("Shares mint does not point to this transfer hook")]
+    HookNotConfigured,
+    #[msg("Hook invoked outside of a token transfer")]
+    NotTransferring,
+    #[msg("Source wallet is blocked by the vault")]
+    SourceBlocked,
+    #[msg("Destination wallet is blocked by the vault")]
+    DestinationBlocked,
+    #[msg("Source wallet is not allowed by the vault")]
+    SourceNotAllowed,
+    #[msg("Destination wallet is not allowed by the vault")]
+    DestinationNotAllowed,
+}
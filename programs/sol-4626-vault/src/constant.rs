@@ -1,3 +1,27 @@
+use anchor_lang::prelude::*;
+
 pub const VAULT_SEED: &str = "vault";
 pub const SHARES_MINT_SEED: &str = "shares_mint";
 pub const VAULT_AUTHORITY_SEED: &str = "vault_authority";
+pub const USER_POSITION_SEED: &str = "user_position";
+pub const BLOCKED_ADDRESS_SEED: &str = "blocked_address";
+pub const REDEEM_REQUEST_SEED: &str = "redeem_request";
+pub const EPOCH_SEED: &str = "epoch";
+pub const EPOCH_RECEIPT_SEED: &str = "epoch_receipt";
+pub const ALLOCATION_TARGET_SEED: &str = "allocation_target";
+pub const MINT_VAULTS_SEED: &str = "mint_vaults";
+pub const GLOBAL_CONFIG_SEED: &str = "global_config";
+pub const TOKEN_METADATA_SEED: &str = "metadata";
+pub const ALLOWED_ADDRESS_SEED: &str = "allowed_address";
+pub const BASKET_SEED: &str = "basket";
+pub const BASKET_AUTHORITY_SEED: &str = "basket_authority";
+pub const PRICE_FEED_SEED: &str = "price_feed";
+pub const NAV_UPDATE_SEED: &str = "nav_update";
+pub const PRICE_HISTORY_SEED: &str = "price_history";
+
+/// Owner of the Pyth-style price accounts read by the oracle adapter
+pub const PYTH_ORACLE_PROGRAM_ID: Pubkey = pubkey!("FsJ3A3u2vn5cTVofAjvy6y5kwABJAqYWpe4975bi2epH");
+
+/// Metaplex Token Metadata program, holding the metadata of classic SPL Token shares mints
+pub const TOKEN_METADATA_PROGRAM_ID: Pubkey =
+    pubkey!("metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s");
@@ -0,0 +1,94 @@
+use crate::state::{Basket, BasketAsset};
+use crate::util::{load_oracle_price, Errors, MintExtensions};
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+/// Add basket asset accounts:
+/// - admin: basket admin, pays for the asset ATA
+/// - basket: basket PDA
+/// - basket_authority: basket authority PDA
+/// - asset_mint: whitelisted asset mint
+/// - price_feed: price oracle of the asset, a `PriceFeed` of the asset mint or a Pyth-style
+///   price account
+/// - basket_asset_ata: basket authority's ATA for the asset, created if needed
+/// - token_program
+/// - associated_token_program
+/// - system_program
+#[derive(Accounts)]
+pub struct AddBasketAsset<'info> {
+    #[account(mut)]
+    admin: Signer<'info>,
+    #[account(mut,
+        has_one = admin,
+        has_one = basket_authority,
+        has_one = token_program
+    )]
+    basket: Box<Account<'info, Basket>>,
+    /// CHECK: basket authority checked (has_one)
+    basket_authority: AccountInfo<'info>,
+    #[account(mint::token_program = token_program)]
+    asset_mint: Box<InterfaceAccount<'info, Mint>>,
+    /// CHECK: parsed by the oracle adapter of its owner
+    price_feed: UncheckedAccount<'info>,
+    #[account(
+        init_if_needed,
+        payer = admin,
+        associated_token::mint = asset_mint,
+        associated_token::authority = basket_authority,
+        associated_token::token_program = token_program
+    )]
+    basket_asset_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+    token_program: Interface<'info, TokenInterface>,
+    associated_token_program: Program<'info, AssociatedToken>,
+    system_program: Program<'info, System>,
+}
+
+/// Whitelists an asset valued through `price_feed`. Mints able to seize or freeze the
+/// basket's assets are rejected unless each such feature is opted into.
+/// - allowed_mint_extensions: `MintExtensions` flags accepted on the asset mint
+pub fn handle(ctx: Context<AddBasketAsset>, allowed_mint_extensions: u16) -> Result<()> {
+    let mint = ctx.accounts.asset_mint.key();
+    msg!(
+        "adding asset {} to basket {}",
+        mint,
+        ctx.accounts.basket.key()
+    );
+
+    let mint_extensions = MintExtensions::of(&ctx.accounts.asset_mint.to_account_info())?;
+    MintExtensions::check(mint_extensions, allowed_mint_extensions)?;
+    let (_, priced_mint) = load_oracle_price(&ctx.accounts.price_feed)?;
+    require!(
+        priced_mint.is_none_or(|priced| priced == mint),
+        Errors::InvalidPriceFeed
+    );
+
+    let basket = &mut ctx.accounts.basket;
+    require!(basket.assets.len() < Basket::MAX_ASSETS, Errors::BasketFull);
+    require!(
+        basket.asset_index(&mint).is_err(),
+        Errors::AssetAlreadyInBasket
+    );
+    basket.assets.push(BasketAsset {
+        mint,
+        ata: ctx.accounts.basket_asset_ata.key(),
+        price_feed: ctx.accounts.price_feed.key(),
+        decimals: ctx.accounts.asset_mint.decimals,
+        balance: 0,
+    });
+
+    emit!(AddBasketAssetEvent {
+        basket: basket.key(),
+        mint,
+        price_feed: ctx.accounts.price_feed.key(),
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct AddBasketAssetEvent {
+    pub basket: Pubkey,
+    pub mint: Pubkey,
+    pub price_feed: Pubkey,
+}
@@ -0,0 +1,376 @@
+use crate::state::Vault;
+use crate::util::{BoolU8, Errors, BPS_DENOMINATOR};
+use anchor_lang::prelude::*;
+
+/// Vault admin accounts, shared by the configuration instructions:
+/// - admin: vault admin
+/// - vault: vault PDA
+#[derive(Accounts)]
+pub struct VaultAdmin<'info> {
+    admin: Signer<'info>,
+    #[account(mut, has_one = admin)]
+    vault: AccountLoader<'info, Vault>,
+}
+
+/// Sets the guardian allowed to trigger an emergency shutdown.
+pub fn set_guardian(ctx: Context<VaultAdmin>, guardian: Pubkey) -> Result<()> {
+    let mut vlt = ctx.accounts.vault.load_mut()?;
+
+    msg!(
+        "setting guardian of vault {} to {}",
+        ctx.accounts.vault.key(),
+        guardian
+    );
+
+    vlt.guardian = guardian;
+
+    emit!(SetGuardianEvent {
+        vault: ctx.accounts.vault.key(),
+        guardian,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct SetGuardianEvent {
+    pub vault: Pubkey,
+    pub guardian: Pubkey,
+}
+
+/// Sets the vault-wide and per-user deposit caps, 0 disables a cap.
+/// Lowering a cap below current deposits only blocks new deposits.
+pub fn set_deposit_caps(
+    ctx: Context<VaultAdmin>,
+    deposit_cap: u64,
+    user_deposit_cap: u64,
+) -> Result<()> {
+    let mut vlt = ctx.accounts.vault.load_mut()?;
+
+    msg!(
+        "setting deposit caps of vault {} to total {} per user {}",
+        ctx.accounts.vault.key(),
+        deposit_cap,
+        user_deposit_cap
+    );
+
+    vlt.deposit_cap = deposit_cap;
+    vlt.user_deposit_cap = user_deposit_cap;
+
+    emit!(SetDepositCapsEvent {
+        vault: ctx.accounts.vault.key(),
+        deposit_cap,
+        user_deposit_cap,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct SetDepositCapsEvent {
+    pub vault: Pubkey,
+    pub deposit_cap: u64,
+    pub user_deposit_cap: u64,
+}
+
+/// Sets the merkle root of wallets allowed to deposit, zeroed root opens the vault.
+/// One transaction updates the whole set, the tree itself lives off chain.
+pub fn set_allowlist_root(ctx: Context<VaultAdmin>, allowlist_root: [u8; 32]) -> Result<()> {
+    let mut vlt = ctx.accounts.vault.load_mut()?;
+
+    msg!(
+        "setting allowlist root of vault {}",
+        ctx.accounts.vault.key()
+    );
+
+    vlt.allowlist_root = allowlist_root;
+
+    emit!(SetAllowlistRootEvent {
+        vault: ctx.accounts.vault.key(),
+        allowlist_root,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct SetAllowlistRootEvent {
+    pub vault: Pubkey,
+    pub allowlist_root: [u8; 32],
+}
+
+/// Sets the off-chain compliance signer whose vouchers deposits require,
+/// zeroed key disables vouchers.
+pub fn set_compliance_signer(ctx: Context<VaultAdmin>, compliance_signer: Pubkey) -> Result<()> {
+    let mut vlt = ctx.accounts.vault.load_mut()?;
+
+    msg!(
+        "setting compliance signer of vault {} to {}",
+        ctx.accounts.vault.key(),
+        compliance_signer
+    );
+
+    vlt.compliance_signer = compliance_signer;
+
+    emit!(SetComplianceSignerEvent {
+        vault: ctx.accounts.vault.key(),
+        compliance_signer,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct SetComplianceSignerEvent {
+    pub vault: Pubkey,
+    pub compliance_signer: Pubkey,
+}
+
+/// Sets the off-chain valuation signer whose NAV reports `update_nav` applies, zeroed key
+/// disables reports, the max change of total assets per report (0 = unlimited) and the
+/// max age in seconds of a report when relayed (required with a signer).
+pub fn set_valuation_signer(
+    ctx: Context<VaultAdmin>,
+    valuation_signer: Pubkey,
+    max_nav_change_bps: u16,
+    max_nav_age: i64,
+) -> Result<()> {
+    require!(
+        max_nav_age >= 0 && (max_nav_age > 0 || valuation_signer == Pubkey::default()),
+        Errors::InvalidNavMaxAge
+    );
+    let mut vlt = ctx.accounts.vault.load_mut()?;
+
+    msg!(
+        "setting valuation signer of vault {} to {} with {} bps max NAV change, {}s max age",
+        ctx.accounts.vault.key(),
+        valuation_signer,
+        max_nav_change_bps,
+        max_nav_age
+    );
+
+    vlt.valuation_signer = valuation_signer;
+    vlt.max_nav_change_bps = max_nav_change_bps;
+    vlt.nav_max_age = max_nav_age;
+
+    emit!(SetValuationSignerEvent {
+        vault: ctx.accounts.vault.key(),
+        valuation_signer,
+        max_nav_change_bps,
+        max_nav_age,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct SetValuationSignerEvent {
+    pub vault: Pubkey,
+    pub valuation_signer: Pubkey,
+    pub max_nav_change_bps: u16,
+    pub max_nav_age: i64,
+}
+
+/// Configures the share price circuit breaker applied to NAV reports, 0 disables a check.
+/// Restarts the breaker window at the next report.
+/// - report_bps: max share price change per report
+/// - window_bps: max share price change within `window` seconds
+/// - pause: breaches pause deposits and withdrawals until the guardian resets the breaker,
+///   instead of reverting the report
+pub fn set_circuit_breaker(
+    ctx: Context<VaultAdmin>,
+    report_bps: u16,
+    window_bps: u16,
+    window: i64,
+    pause: bool,
+) -> Result<()> {
+    require!(
+        window >= 0 && (window_bps == 0 || window > 0),
+        Errors::InvalidCircuitBreaker
+    );
+
+    let mut vlt = ctx.accounts.vault.load_mut()?;
+
+    msg!(
+        "setting circuit breaker of vault {} to {} bps per report, {} bps per {}s (pause: {})",
+        ctx.accounts.vault.key(),
+        report_bps,
+        window_bps,
+        window,
+        pause
+    );
+
+    vlt.breaker_report_bps = report_bps;
+    vlt.breaker_window_bps = window_bps;
+    vlt.breaker_window = window;
+    vlt.breaker_pause = BoolU8::new(pause);
+    vlt.breaker_ref_at = 0;
+
+    emit!(SetCircuitBreakerEvent {
+        vault: ctx.accounts.vault.key(),
+        report_bps,
+        window_bps,
+        window,
+        pause,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct SetCircuitBreakerEvent {
+    pub vault: Pubkey,
+    pub report_bps: u16,
+    pub window_bps: u16,
+    pub window: i64,
+    pub pause: bool,
+}
+
+/// Sets the compliance role allowed to block wallets and freeze their shares.
+pub fn set_compliance_authority(
+    ctx: Context<VaultAdmin>,
+    compliance_authority: Pubkey,
+) -> Result<()> {
+    let mut vlt = ctx.accounts.vault.load_mut()?;
+
+    msg!(
+        "setting compliance authority of vault {} to {}",
+        ctx.accounts.vault.key(),
+        compliance_authority
+    );
+
+    vlt.compliance_authority = compliance_authority;
+
+    emit!(SetComplianceAuthorityEvent {
+        vault: ctx.accounts.vault.key(),
+        compliance_authority,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct SetComplianceAuthorityEvent {
+    pub vault: Pubkey,
+    pub compliance_authority: Pubkey,
+}
+
+/// Sets the minimum epoch length in seconds, 0 disables queued deposits and redeems.
+/// Enabling epochs starts the current epoch now.
+pub fn set_epoch_duration(ctx: Context<VaultAdmin>, epoch_duration: i64) -> Result<()> {
+    let mut vlt = ctx.accounts.vault.load_mut()?;
+
+    require!(epoch_duration >= 0, Errors::InvalidEpochDuration);
+
+    msg!(
+        "setting epoch duration of vault {} to {}s",
+        ctx.accounts.vault.key(),
+        epoch_duration
+    );
+
+    if !vlt.epochs_enabled() {
+        vlt.epoch_started_at = Clock::get()?.unix_timestamp;
+    }
+    vlt.epoch_duration = epoch_duration;
+
+    emit!(SetEpochDurationEvent {
+        vault: ctx.accounts.vault.key(),
+        epoch_duration,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct SetEpochDurationEvent {
+    pub vault: Pubkey,
+    pub epoch_duration: i64,
+}
+
+/// Sets the minimum holding period after a deposit and the early-exit penalty.
+/// The penalty decays linearly to 0 over the lockup, 0 rejects exits while locked.
+/// Each deposit restarts the lockup of the whole position.
+/// The lockup is tracked per wallet, so only soulbound shares can be locked: shares moved to
+/// a fresh wallet would otherwise exit freely.
+pub fn set_lockup(
+    ctx: Context<VaultAdmin>,
+    lockup_seconds: i64,
+    early_exit_penalty_bps: u16,
+) -> Result<()> {
+    let mut vlt = ctx.accounts.vault.load_mut()?;
+
+    require!(
+        lockup_seconds >= 0 && (early_exit_penalty_bps as u64) < BPS_DENOMINATOR,
+        Errors::InvalidLockup
+    );
+    require!(
+        lockup_seconds == 0 || vlt.soulbound_shares.is_true(),
+        Errors::LockupRequiresSoulboundShares
+    );
+
+    msg!(
+        "setting lockup of vault {} to {}s with {} bps early-exit penalty",
+        ctx.accounts.vault.key(),
+        lockup_seconds,
+        early_exit_penalty_bps
+    );
+
+    vlt.lockup_seconds = lockup_seconds;
+    vlt.early_exit_penalty_bps = early_exit_penalty_bps;
+
+    emit!(SetLockupEvent {
+        vault: ctx.accounts.vault.key(),
+        lockup_seconds,
+        early_exit_penalty_bps,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct SetLockupEvent {
+    pub vault: Pubkey,
+    pub lockup_seconds: i64,
+    pub early_exit_penalty_bps: u16,
+}
+
+/// Sets the withdrawal rate limit: at most `outflow_limit_bps` of total assets can leave
+/// through withdraw/redeem per rolling `outflow_window` seconds, 0 bps disables it.
+pub fn set_outflow_limit(
+    ctx: Context<VaultAdmin>,
+    outflow_limit_bps: u16,
+    outflow_window: i64,
+) -> Result<()> {
+    let mut vlt = ctx.accounts.vault.load_mut()?;
+
+    require!(
+        (outflow_limit_bps as u64) <= BPS_DENOMINATOR
+            && (outflow_limit_bps == 0 || outflow_window > 0),
+        Errors::InvalidOutflowLimit
+    );
+
+    msg!(
+        "setting outflow limit of vault {} to {} bps per {}s",
+        ctx.accounts.vault.key(),
+        outflow_limit_bps,
+        outflow_window
+    );
+
+    vlt.outflow_limit_bps = outflow_limit_bps;
+    vlt.outflow_window = outflow_window;
+
+    emit!(SetOutflowLimitEvent {
+        vault: ctx.accounts.vault.key(),
+        outflow_limit_bps,
+        outflow_window,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct SetOutflowLimitEvent {
+    pub vault: Pubkey,
+    pub outflow_limit_bps: u16,
+    pub outflow_window: i64,
+}
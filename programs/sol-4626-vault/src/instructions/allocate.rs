@@ -1,5 +1,5 @@
-use crate::constant::{VAULT_AUTHORITY_SEED};
-use crate::state::Vault;
+use crate::constant::{ALLOCATION_TARGET_SEED, GLOBAL_CONFIG_SEED, VAULT_AUTHORITY_SEED};
+use crate::state::{AllocationTarget, GlobalConfig, PriceHistory, Vault};
 use crate::util::Errors;
 use anchor_lang::prelude::*;
 use anchor_lang::Accounts;
@@ -12,11 +12,14 @@ use anchor_spl::token_interface::{
 /// - base_asset_mint: vault's base asset mint
 /// - vault_base_asset_ata: vault's ATA for base assets
 /// - vault: vault PDA
-/// - target_ata: external target ATA to allocate assets to
+/// - price_history: share price history PDA of the vault
+/// - shares_mint: vault's shares mint
+/// - global_config: protocol config PDA (global pause)
+/// - target_ata: strategy escrow token account to allocate assets to, owned by the vault authority
+/// - allocation_target: allocation target PDA of the target ATA, must be approved
 /// - token_program
 /// - system_program
 #[derive(Accounts)]
-#[instruction(amount: u64, ticker: [u8; 16])]
 pub struct Allocate<'info> {
     #[account(mut)]
     admin: Signer<'info>,
@@ -25,12 +28,21 @@ pub struct Allocate<'info> {
         has_one = vault_authority,
         has_one = vault_base_asset_ata,
         has_one = token_program,
+        has_one = shares_mint,
         has_one = admin
     )]
     vault: AccountLoader<'info, Vault>,
+    #[account(mut, has_one = vault)]
+    price_history: AccountLoader<'info, PriceHistory>,
+    shares_mint: Box<InterfaceAccount<'info, Mint>>,
     /// CHECK: vault authority checked (has_one)
     #[account(mut)]
     vault_authority: AccountInfo<'info>,
+    #[account(
+        seeds = [GLOBAL_CONFIG_SEED.as_bytes()],
+        bump = global_config.bump
+    )]
+    global_config: Box<Account<'info, GlobalConfig>>,
     #[account(
         mint::token_program = token_program,
     )]
@@ -43,23 +55,75 @@ pub struct Allocate<'info> {
         token::token_program = token_program,
     )]
     target_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+    /// CHECK: allocation target PDA of the target ATA (checked by seeds), may not exist
+    #[account(
+        mut,
+        seeds = [ALLOCATION_TARGET_SEED.as_bytes(), vault.key().as_ref(), target_ata.key().as_ref()],
+        bump
+    )]
+    allocation_target: UncheckedAccount<'info>,
     token_program: Interface<'info, TokenInterface>,
     system_program: Program<'info, System>,
 }
 
-/// Moves base assets from the vault's ATA to an external target ATA.
+/// Moves base assets from the vault's ATA to a strategy escrow.
 /// The vault's accounting keeps total assets unchanged because funds
 /// are only being relocated (e.g., allocated to an external yield strategy),
 /// This is just a poc, better way of doing that is to manage idle and in_use assets
+///
+/// Only destinations approved by the guardian are accepted, within their per-period
+/// limit. A breach emits `RateLimitHit` and fails, reverting everything but the logs, so
+/// no breach is recorded on-chain (see `RateLimitHit`).
+/// A Token-2022 transfer fee withheld on the way out is a loss to the vault's assets.
+///
+/// The target must be a token account owned by the vault authority (not the vault's own ATA),
+/// so the admin can always deallocate without the strategy's signature. A delegation would
+/// not do, the strategy could revoke it.
 pub fn handle(ctx: Context<Allocate>, amount: u64) -> Result<()> {
-    let vlt = ctx.accounts.vault.load()?;
+    let mut vlt = ctx.accounts.vault.load_mut()?;
 
     require!(amount > 0, Errors::InvalidAmount);
-    require!(vlt.deposit_paused == 0, Errors::AllocatePaused);
+    require!(!ctx.accounts.global_config.paused, Errors::ProtocolPaused);
+    require!(!vlt.shutdown.is_true(), Errors::VaultShutdown);
+    require!(vlt.allocate_paused == 0, Errors::AllocatePaused);
     require!(
-        amount <= ctx.accounts.vault_base_asset_ata.amount,
+        amount <= vlt.idle_assets(ctx.accounts.vault_base_asset_ata.amount),
         Errors::InsufficientBaseAssetBalance
     );
+    require!(
+        ctx.accounts.target_ata.owner == ctx.accounts.vault_authority.key()
+            && ctx.accounts.target_ata.key() != ctx.accounts.vault_base_asset_ata.key(),
+        Errors::TargetNotVaultOwned
+    );
+
+    let now = Clock::get()?.unix_timestamp;
+    // Only this program can create data at the PDA, an empty account is an unknown target
+    let target_info = &ctx.accounts.allocation_target;
+    let mut target = if target_info.data_is_empty() {
+        None
+    } else {
+        Some(AllocationTarget::try_deserialize(
+            &mut &target_info.try_borrow_data()?[..],
+        )?)
+    }
+    .filter(|target| target.active);
+
+    let remaining = target
+        .as_ref()
+        .map_or(0, |target| target.period_remaining(now));
+    if target.is_none() || amount > remaining {
+        emit!(RateLimitHit {
+            vault: ctx.accounts.vault.key(),
+            target_ata: ctx.accounts.target_ata.key(),
+            amount,
+            approved: target.is_some(),
+            remaining,
+        });
+    }
+    let Some(target) = target.as_mut() else {
+        return err!(Errors::DestinationNotApproved);
+    };
+    require!(amount <= remaining, Errors::AllocationLimitExceeded);
 
     msg!(
         "allocating {} base assets from vault {} to target ATA {}",
@@ -72,7 +136,7 @@ pub fn handle(ctx: Context<Allocate>, amount: u64) -> Result<()> {
         from: ctx.accounts.vault_base_asset_ata.to_account_info(),
         mint: ctx.accounts.base_asset_mint.to_account_info(),
         to: ctx.accounts.target_ata.to_account_info(),
-        authority: ctx.accounts.vault.to_account_info(),
+        authority: ctx.accounts.vault_authority.to_account_info(),
     };
 
     let vlt_address = ctx.accounts.vault.key();
@@ -87,12 +151,32 @@ pub fn handle(ctx: Context<Allocate>, amount: u64) -> Result<()> {
         vlt_auth_seeds,
     );
 
+    let balance_before = ctx.accounts.target_ata.amount;
     transfer_checked(transfer_ctx, amount, ctx.accounts.base_asset_mint.decimals)?;
 
+    ctx.accounts.target_ata.reload()?;
+    let received = ctx
+        .accounts
+        .target_ata
+        .amount
+        .checked_sub(balance_before)
+        .ok_or(Errors::MathOverflow)?;
+    let fee = amount.saturating_sub(received);
+    vlt.total_base_assets = vlt.total_base_assets.saturating_sub(fee);
+    ctx.accounts.price_history.load_mut()?.record(
+        now,
+        vlt.total_base_assets,
+        ctx.accounts.shares_mint.supply,
+    );
+
+    target.record_allocation(amount, received, now)?;
+    target.try_serialize(&mut &mut target_info.try_borrow_mut_data()?[..])?;
+
     emit!(AllocateEvent {
         vault: ctx.accounts.vault.key(),
         target_ata: ctx.accounts.target_ata.key(),
         amount,
+        transfer_fee: fee,
     });
 
     Ok(())
@@ -103,4 +187,19 @@ pub struct AllocateEvent {
     pub vault: Pubkey,
     pub target_ata: Pubkey,
     pub amount: u64,
+    pub transfer_fee: u64,
+}
+
+/// Emitted by an `allocate` breaching the destination allowlist or a period limit, right
+/// before the instruction fails. It only exists in the logs of the failed transaction:
+/// indexers must fetch failed transactions to see it, and should rely on the
+/// `DestinationNotApproved` / `AllocationLimitExceeded` error code, with this event as
+/// its details. Successful allocations are tracked through `AllocateEvent`.
+#[event]
+pub struct RateLimitHit {
+    pub vault: Pubkey,
+    pub target_ata: Pubkey,
+    pub amount: u64,
+    pub approved: bool,
+    pub remaining: u64,
 }
@@ -0,0 +1,95 @@
+use crate::constant::ALLOCATION_TARGET_SEED;
+use crate::state::{AllocationTarget, Vault};
+use crate::util::Errors;
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+/// Set allocation target accounts:
+/// - guardian: vault guardian, approves destinations independently of the admin
+/// - vault: vault PDA
+/// - base_asset_mint: vault's base asset mint
+/// - target_ata: destination token account being approved
+/// - allocation_target: allocation target PDA of the destination (created on first approval)
+/// - token_program
+/// - system_program
+#[derive(Accounts)]
+pub struct SetAllocationTarget<'info> {
+    #[account(mut)]
+    guardian: Signer<'info>,
+    #[account(
+        has_one = guardian,
+        has_one = base_asset_mint,
+        has_one = token_program
+    )]
+    vault: AccountLoader<'info, Vault>,
+    #[account()]
+    base_asset_mint: Box<InterfaceAccount<'info, Mint>>,
+    #[account(
+        token::mint = base_asset_mint,
+        token::token_program = token_program,
+    )]
+    target_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        init_if_needed,
+        payer = guardian,
+        space = 8 + AllocationTarget::MAX_SIZE,
+        seeds = [ALLOCATION_TARGET_SEED.as_bytes(), vault.key().as_ref(), target_ata.key().as_ref()],
+        bump
+    )]
+    allocation_target: Box<Account<'info, AllocationTarget>>,
+    token_program: Interface<'info, TokenInterface>,
+    system_program: Program<'info, System>,
+}
+
+/// Approves (or revokes with `active = false`) a destination for `allocate` and sets
+/// how much can be allocated to it per period. Revoked targets keep their accounting
+/// so deployed assets can still be deallocated.
+/// - period_limit: max base assets allocated per period
+/// - period_seconds: length of an allocation period
+/// - active: whether allocations to the destination are allowed
+pub fn set_allocation_target(
+    ctx: Context<SetAllocationTarget>,
+    period_limit: u64,
+    period_seconds: i64,
+    active: bool,
+) -> Result<()> {
+    require!(period_seconds > 0, Errors::InvalidAllocationPeriod);
+
+    msg!(
+        "setting allocation target {} of vault {}: active {}, {} per {}s",
+        ctx.accounts.target_ata.key(),
+        ctx.accounts.vault.key(),
+        active,
+        period_limit,
+        period_seconds
+    );
+
+    let target = &mut ctx.accounts.allocation_target;
+    if target.vault == Pubkey::default() {
+        target.vault = ctx.accounts.vault.key();
+        target.target_ata = ctx.accounts.target_ata.key();
+        target.bump = ctx.bumps.allocation_target;
+    }
+    target.active = active;
+    target.period_limit = period_limit;
+    target.period_seconds = period_seconds;
+
+    emit!(SetAllocationTargetEvent {
+        vault: ctx.accounts.vault.key(),
+        target_ata: ctx.accounts.target_ata.key(),
+        active,
+        period_limit,
+        period_seconds,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct SetAllocationTargetEvent {
+    pub vault: Pubkey,
+    pub target_ata: Pubkey,
+    pub active: bool,
+    pub period_limit: u64,
+    pub period_seconds: i64,
+}
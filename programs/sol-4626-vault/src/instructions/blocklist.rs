@@ -0,0 +1,226 @@
+use crate::constant::{BLOCKED_ADDRESS_SEED, VAULT_AUTHORITY_SEED};
+use crate::state::{BlockedAddress, Vault};
+use crate::util::Errors;
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{
+    freeze_account, thaw_account, FreezeAccount, Mint, ThawAccount, TokenAccount, TokenInterface,
+};
+
+/// Block address accounts:
+/// - compliance_authority: vault compliance role
+/// - vault: vault PDA
+/// - blocked_address: blocked address PDA for (vault, wallet), created
+/// - system_program
+#[derive(Accounts)]
+#[instruction(wallet: Pubkey)]
+pub struct BlockAddress<'info> {
+    #[account(mut)]
+    compliance_authority: Signer<'info>,
+    #[account(has_one = compliance_authority)]
+    vault: AccountLoader<'info, Vault>,
+    #[account(
+        init,
+        payer = compliance_authority,
+        space = 8 + BlockedAddress::MAX_SIZE,
+        seeds = [BLOCKED_ADDRESS_SEED.as_bytes(), vault.key().as_ref(), wallet.as_ref()],
+        bump
+    )]
+    blocked_address: Account<'info, BlockedAddress>,
+    system_program: Program<'info, System>,
+}
+
+/// Unblock address accounts:
+/// - compliance_authority: vault compliance role, receives the rent back
+/// - vault: vault PDA
+/// - blocked_address: blocked address PDA, closed
+#[derive(Accounts)]
+pub struct UnblockAddress<'info> {
+    #[account(mut)]
+    compliance_authority: Signer<'info>,
+    #[account(has_one = compliance_authority)]
+    vault: AccountLoader<'info, Vault>,
+    #[account(
+        mut,
+        close = compliance_authority,
+        has_one = vault
+    )]
+    blocked_address: Account<'info, BlockedAddress>,
+}
+
+/// Freeze/thaw holder accounts:
+/// - compliance_authority: vault compliance role
+/// - vault: vault PDA
+/// - vault_authority: vault authority PDA, freeze authority of the shares mint
+/// - shares_mint: vault's shares mint
+/// - holder_shares_ata: holder's shares account
+/// - blocked_address: blocked address PDA of the holder (checked by seeds)
+/// - shares_token_program
+#[derive(Accounts)]
+pub struct SetHolderFrozen<'info> {
+    compliance_authority: Signer<'info>,
+    #[account(
+        has_one = compliance_authority,
+        has_one = vault_authority,
+        has_one = shares_mint,
+        has_one = shares_token_program
+    )]
+    vault: AccountLoader<'info, Vault>,
+    /// CHECK: vault authority checked (has_one)
+    vault_authority: AccountInfo<'info>,
+    shares_mint: Box<InterfaceAccount<'info, Mint>>,
+    #[account(
+        mut,
+        token::mint = shares_mint,
+        token::token_program = shares_token_program,
+    )]
+    holder_shares_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+    /// CHECK: address checked by seeds, only its existence is read
+    #[account(
+        seeds = [
+            BLOCKED_ADDRESS_SEED.as_bytes(),
+            vault.key().as_ref(),
+            holder_shares_ata.owner.as_ref()
+        ],
+        bump
+    )]
+    blocked_address: UncheckedAccount<'info>,
+    shares_token_program: Interface<'info, TokenInterface>,
+}
+
+/// Refuses service to `wallet`: deposit, mint, withdraw and redeem reject it from now on.
+pub fn block(ctx: Context<BlockAddress>, wallet: Pubkey) -> Result<()> {
+    msg!("blocking {} on vault {}", wallet, ctx.accounts.vault.key());
+
+    let blocked = &mut ctx.accounts.blocked_address;
+    blocked.vault = ctx.accounts.vault.key();
+    blocked.wallet = wallet;
+    blocked.blocked_at = Clock::get()?.unix_timestamp;
+    blocked.bump = ctx.bumps.blocked_address;
+
+    emit!(BlockAddressEvent {
+        vault: ctx.accounts.vault.key(),
+        wallet,
+    });
+
+    Ok(())
+}
+
+/// Lifts the block on a wallet by closing its blocked address PDA.
+pub fn unblock(ctx: Context<UnblockAddress>) -> Result<()> {
+    let wallet = ctx.accounts.blocked_address.wallet;
+
+    msg!(
+        "unblocking {} on vault {}",
+        wallet,
+        ctx.accounts.vault.key()
+    );
+
+    emit!(UnblockAddressEvent {
+        vault: ctx.accounts.vault.key(),
+        wallet,
+    });
+
+    Ok(())
+}
+
+/// Freezes the shares account of a blocked holder, the vault authority is the
+/// freeze authority of the shares mint.
+pub fn freeze_holder(ctx: Context<SetHolderFrozen>) -> Result<()> {
+    require!(
+        !ctx.accounts.blocked_address.data_is_empty(),
+        Errors::AddressNotBlocked
+    );
+
+    msg!(
+        "freezing shares account {} of vault {}",
+        ctx.accounts.holder_shares_ata.key(),
+        ctx.accounts.vault.key()
+    );
+
+    let vlt = ctx.accounts.vault.load()?;
+    let vlt_address = ctx.accounts.vault.key();
+    let vlt_auth_seeds: &[&[&[u8]]] = &[&[
+        VAULT_AUTHORITY_SEED.as_bytes(),
+        vlt_address.as_ref(),
+        &[vlt.vault_authority_bump],
+    ]];
+    let freeze_accounts = FreezeAccount {
+        account: ctx.accounts.holder_shares_ata.to_account_info(),
+        mint: ctx.accounts.shares_mint.to_account_info(),
+        authority: ctx.accounts.vault_authority.to_account_info(),
+    };
+    let freeze_ctx = CpiContext::new_with_signer(
+        ctx.accounts.shares_token_program.to_account_info(),
+        freeze_accounts,
+        vlt_auth_seeds,
+    );
+    freeze_account(freeze_ctx)?;
+
+    emit!(FreezeHolderEvent {
+        vault: ctx.accounts.vault.key(),
+        holder_shares_ata: ctx.accounts.holder_shares_ata.key(),
+        frozen: true,
+    });
+
+    Ok(())
+}
+
+/// Thaws the shares account of a holder that is no longer blocked.
+pub fn thaw_holder(ctx: Context<SetHolderFrozen>) -> Result<()> {
+    require!(
+        ctx.accounts.blocked_address.data_is_empty(),
+        Errors::AddressBlocked
+    );
+
+    msg!(
+        "thawing shares account {} of vault {}",
+        ctx.accounts.holder_shares_ata.key(),
+        ctx.accounts.vault.key()
+    );
+
+    let vlt = ctx.accounts.vault.load()?;
+    let vlt_address = ctx.accounts.vault.key();
+    let vlt_auth_seeds: &[&[&[u8]]] = &[&[
+        VAULT_AUTHORITY_SEED.as_bytes(),
+        vlt_address.as_ref(),
+        &[vlt.vault_authority_bump],
+    ]];
+    let thaw_accounts = ThawAccount {
+        account: ctx.accounts.holder_shares_ata.to_account_info(),
+        mint: ctx.accounts.shares_mint.to_account_info(),
+        authority: ctx.accounts.vault_authority.to_account_info(),
+    };
+    let thaw_ctx = CpiContext::new_with_signer(
+        ctx.accounts.shares_token_program.to_account_info(),
+        thaw_accounts,
+        vlt_auth_seeds,
+    );
+    thaw_account(thaw_ctx)?;
+
+    emit!(FreezeHolderEvent {
+        vault: ctx.accounts.vault.key(),
+        holder_shares_ata: ctx.accounts.holder_shares_ata.key(),
+        frozen: false,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct BlockAddressEvent {
+    pub vault: Pubkey,
+    pub wallet: Pubkey,
+}
+
+#[event]
+pub struct UnblockAddressEvent {
+    pub vault: Pubkey,
+    pub wallet: Pubkey,
+}
+
+#[event]
+pub struct FreezeHolderEvent {
+    pub vault: Pubkey,
+    pub holder_shares_ata: Pubkey,
+    pub frozen: bool,
+}
@@ -0,0 +1,218 @@
+use crate::constant::{
+    BLOCKED_ADDRESS_SEED, EPOCH_RECEIPT_SEED, USER_POSITION_SEED, VAULT_AUTHORITY_SEED,
+};
+use crate::state::{Epoch, EpochReceipt, UserPosition, Vault};
+use crate::util::{transfer_shares, Errors};
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token_interface::{
+    transfer_checked, Mint, TokenAccount, TokenInterface, TransferChecked,
+};
+
+/// Claim epoch accounts:
+/// - signer: receipt owner
+/// - vault: vault PDA
+/// - vault_authority: vault authority PDA
+/// - shares_mint: vault's shares mint
+/// - shares_escrow: vault authority's ATA for shares
+/// - shares_ata: owner's ATA receiving shares
+/// - base_asset_mint: base token asset mint
+/// - base_asset_ata: owner's ATA receiving base assets
+/// - vault_base_asset_ata: vault's ATA for base assets
+/// - epoch: settled epoch PDA
+/// - epoch_receipt: owner's receipt for the epoch, closed to the owner
+/// - user_position: owner's position PDA
+/// - blocked_address: blocked address PDA of the owner, must be empty
+/// - token_program
+/// - shares_token_program
+/// - associated_token_program
+/// - system_program
+#[derive(Accounts)]
+pub struct ClaimEpoch<'info> {
+    #[account(mut)]
+    signer: Signer<'info>,
+    #[account(mut,
+        has_one = vault_authority,
+        has_one = shares_mint,
+        has_one = base_asset_mint,
+        has_one = vault_base_asset_ata,
+        has_one = token_program,
+        has_one = shares_token_program
+    )]
+    vault: AccountLoader<'info, Vault>,
+    /// CHECK: vault authority checked (has_one)
+    vault_authority: AccountInfo<'info>,
+    #[account()]
+    shares_mint: Box<InterfaceAccount<'info, Mint>>,
+    #[account(
+        mut,
+        associated_token::mint = shares_mint,
+        associated_token::authority = vault_authority,
+        associated_token::token_program = shares_token_program
+    )]
+    shares_escrow: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        init_if_needed,
+        payer = signer,
+        associated_token::mint = shares_mint,
+        associated_token::authority = signer,
+        associated_token::token_program = shares_token_program
+    )]
+    shares_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account()]
+    base_asset_mint: Box<InterfaceAccount<'info, Mint>>,
+    #[account(
+        init_if_needed,
+        payer = signer,
+        associated_token::mint = base_asset_mint,
+        associated_token::authority = signer,
+        associated_token::token_program = token_program
+    )]
+    base_asset_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(mut)]
+    vault_base_asset_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(has_one = vault)]
+    epoch: Box<Account<'info, Epoch>>,
+    #[account(
+        mut,
+        close = signer,
+        seeds = [EPOCH_RECEIPT_SEED.as_bytes(), epoch.key().as_ref(), signer.key().as_ref()],
+        bump = epoch_receipt.bump
+    )]
+    epoch_receipt: Box<Account<'info, EpochReceipt>>,
+    #[account(
+        init_if_needed,
+        payer = signer,
+        space = 8 + UserPosition::MAX_SIZE,
+        seeds = [USER_POSITION_SEED.as_bytes(), vault.key().as_ref(), signer.key().as_ref()],
+        bump
+    )]
+    user_position: Box<Account<'info, UserPosition>>,
+    /// CHECK: blocked address PDA of the signer (checked by seeds), must not exist
+    #[account(
+        seeds = [BLOCKED_ADDRESS_SEED.as_bytes(), vault.key().as_ref(), signer.key().as_ref()],
+        bump
+    )]
+    blocked_address: UncheckedAccount<'info>,
+    token_program: Interface<'info, TokenInterface>,
+    shares_token_program: Interface<'info, TokenInterface>,
+    associated_token_program: Program<'info, AssociatedToken>,
+    system_program: Program<'info, System>,
+}
+
+/// Pays out the owner's pro-rata part of a settled epoch: shares for the queued
+/// deposit, base assets for the queued redeem. Rounding dust stays in the vault.
+/// Both sides update the owner's cost basis at the epoch price. Deposits refunded at the
+/// close are paid back in base assets. Owners blocked after queueing can't claim until
+/// unblocked.
+/// With a shares transfer hook, the remaining accounts carry the hook's extra accounts.
+pub fn handle<'info>(ctx: Context<'_, '_, '_, 'info, ClaimEpoch<'info>>) -> Result<()> {
+    let mut vlt = ctx.accounts.vault.load_mut()?;
+    let epoch = &ctx.accounts.epoch;
+    let receipt = &ctx.accounts.epoch_receipt;
+
+    require!(epoch.is_settled(), Errors::EpochNotSettled);
+    require!(
+        ctx.accounts.blocked_address.data_is_empty(),
+        Errors::AddressBlocked
+    );
+
+    let shares = epoch.shares_for(receipt.queued_assets)?;
+    let refunded = if epoch.deposits_refunded() {
+        receipt.queued_assets
+    } else {
+        0
+    };
+    let assets = epoch
+        .assets_for(receipt.queued_shares)?
+        .checked_add(refunded)
+        .ok_or(Errors::MathOverflow)?;
+
+    msg!(
+        "claiming {} shares and {} base assets from epoch {} of vault {}",
+        shares,
+        assets,
+        epoch.id,
+        ctx.accounts.vault.key()
+    );
+
+    let vlt_address = ctx.accounts.vault.key();
+    let vlt_auth_seeds: &[&[&[u8]]] = &[&[
+        VAULT_AUTHORITY_SEED.as_bytes(),
+        vlt_address.as_ref(),
+        &[vlt.vault_authority_bump],
+    ]];
+
+    if shares > 0 {
+        let transfer_accounts = TransferChecked {
+            mint: ctx.accounts.shares_mint.to_account_info(),
+            from: ctx.accounts.shares_escrow.to_account_info(),
+            to: ctx.accounts.shares_ata.to_account_info(),
+            authority: ctx.accounts.vault_authority.to_account_info(),
+        };
+        let transfer_ctx = CpiContext::new_with_signer(
+            ctx.accounts.shares_token_program.to_account_info(),
+            transfer_accounts,
+            vlt_auth_seeds,
+        )
+        .with_remaining_accounts(ctx.remaining_accounts.to_vec());
+        transfer_shares(transfer_ctx, shares, ctx.accounts.shares_mint.decimals)?;
+    }
+
+    if assets > 0 {
+        let transfer_accounts = TransferChecked {
+            mint: ctx.accounts.base_asset_mint.to_account_info(),
+            from: ctx.accounts.vault_base_asset_ata.to_account_info(),
+            to: ctx.accounts.base_asset_ata.to_account_info(),
+            authority: ctx.accounts.vault_authority.to_account_info(),
+        };
+        let transfer_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            transfer_accounts,
+            vlt_auth_seeds,
+        );
+        transfer_checked(transfer_ctx, assets, ctx.accounts.base_asset_mint.decimals)?;
+
+        vlt.reserved_assets = vlt
+            .reserved_assets
+            .checked_sub(assets)
+            .ok_or(Errors::MathOverflow)?;
+    }
+
+    let position = &mut ctx.accounts.user_position;
+    position.init_if_needed(
+        ctx.accounts.vault.key(),
+        ctx.accounts.signer.key(),
+        ctx.bumps.user_position,
+    );
+    position.deposited_assets = position.deposited_assets.saturating_sub(assets);
+    if receipt.queued_assets > 0 && refunded == 0 {
+        position.record_entry(receipt.queued_assets, shares)?;
+    }
+    let realised_pnl = if receipt.queued_shares > 0 {
+        position.record_exit(receipt.queued_shares, assets - refunded)?
+    } else {
+        0
+    };
+
+    emit!(ClaimEpochEvent {
+        vault: ctx.accounts.vault.key(),
+        epoch: epoch.id,
+        owner: ctx.accounts.signer.key(),
+        shares,
+        assets,
+        realised_pnl,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct ClaimEpochEvent {
+    pub vault: Pubkey,
+    pub epoch: u64,
+    pub owner: Pubkey,
+    pub shares: u64,
+    pub assets: u64,
+    pub realised_pnl: i64,
+}
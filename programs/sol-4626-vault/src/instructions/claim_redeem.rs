@@ -0,0 +1,121 @@
+use crate::constant::{BLOCKED_ADDRESS_SEED, REDEEM_REQUEST_SEED, VAULT_AUTHORITY_SEED};
+use crate::state::{RedeemRequest, Vault};
+use crate::util::Errors;
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token_interface::{
+    transfer_checked, Mint, TokenAccount, TokenInterface, TransferChecked,
+};
+
+/// Claim redeem accounts:
+/// - signer: request owner
+/// - vault: vault PDA
+/// - vault_authority: vault authority PDA
+/// - base_asset_mint: base token asset mint
+/// - base_asset_ata: owner's ATA receiving base assets
+/// - vault_base_asset_ata: vault's ATA for base assets
+/// - redeem_request: owner's redeem request PDA
+/// - blocked_address: blocked address PDA of the owner, must be empty
+/// - token_program
+/// - associated_token_program
+/// - system_program
+#[derive(Accounts)]
+pub struct ClaimRedeem<'info> {
+    #[account(mut)]
+    signer: Signer<'info>,
+    #[account(mut,
+        has_one = base_asset_mint,
+        has_one = vault_authority,
+        has_one = vault_base_asset_ata,
+        has_one = token_program
+    )]
+    vault: AccountLoader<'info, Vault>,
+    /// CHECK: vault authority checked (has_one)
+    vault_authority: AccountInfo<'info>,
+    #[account()]
+    base_asset_mint: Box<InterfaceAccount<'info, Mint>>,
+    #[account(
+        init_if_needed,
+        payer = signer,
+        associated_token::mint = base_asset_mint,
+        associated_token::authority = signer,
+        associated_token::token_program = token_program
+    )]
+    base_asset_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(mut)]
+    vault_base_asset_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        seeds = [REDEEM_REQUEST_SEED.as_bytes(), vault.key().as_ref(), signer.key().as_ref()],
+        bump = redeem_request.bump
+    )]
+    redeem_request: Box<Account<'info, RedeemRequest>>,
+    /// CHECK: blocked address PDA of the signer (checked by seeds), must not exist
+    #[account(
+        seeds = [BLOCKED_ADDRESS_SEED.as_bytes(), vault.key().as_ref(), signer.key().as_ref()],
+        bump
+    )]
+    blocked_address: UncheckedAccount<'info>,
+    token_program: Interface<'info, TokenInterface>,
+    associated_token_program: Program<'info, AssociatedToken>,
+    system_program: Program<'info, System>,
+}
+
+/// Pays out the base assets locked in by a fulfilled redeem request.
+/// Owners blocked after their request can't claim until unblocked.
+pub fn handle(ctx: Context<ClaimRedeem>) -> Result<()> {
+    let mut vlt = ctx.accounts.vault.load_mut()?;
+
+    let assets = ctx.accounts.redeem_request.claimable_assets;
+    require!(assets > 0, Errors::NoClaimableRedeemRequest);
+    require!(
+        ctx.accounts.blocked_address.data_is_empty(),
+        Errors::AddressBlocked
+    );
+
+    msg!(
+        "claiming {} base assets from vault {}",
+        assets,
+        ctx.accounts.vault.key()
+    );
+
+    let vlt_address = ctx.accounts.vault.key();
+    let transfer_accounts = TransferChecked {
+        mint: ctx.accounts.base_asset_mint.to_account_info(),
+        from: ctx.accounts.vault_base_asset_ata.to_account_info(),
+        to: ctx.accounts.base_asset_ata.to_account_info(),
+        authority: ctx.accounts.vault_authority.to_account_info(),
+    };
+    let vlt_auth_seeds: &[&[&[u8]]] = &[&[
+        VAULT_AUTHORITY_SEED.as_bytes(),
+        vlt_address.as_ref(),
+        &[vlt.vault_authority_bump],
+    ]];
+    let transfer_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        transfer_accounts,
+        vlt_auth_seeds,
+    );
+    transfer_checked(transfer_ctx, assets, ctx.accounts.base_asset_mint.decimals)?;
+
+    vlt.reserved_assets = vlt
+        .reserved_assets
+        .checked_sub(assets)
+        .ok_or(Errors::MathOverflow)?;
+    ctx.accounts.redeem_request.claimable_assets = 0;
+
+    emit!(RedeemClaimedEvent {
+        vault: ctx.accounts.vault.key(),
+        owner: ctx.accounts.signer.key(),
+        assets,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct RedeemClaimedEvent {
+    pub vault: Pubkey,
+    pub owner: Pubkey,
+    pub assets: u64,
+}
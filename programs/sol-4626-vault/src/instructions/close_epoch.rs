@@ -0,0 +1,211 @@
+use crate::constant::{EPOCH_SEED, VAULT_AUTHORITY_SEED};
+use crate::state::{Epoch, PriceHistory, Vault};
+use crate::util::{convert_to_assets, convert_to_shares, Errors};
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token_interface::{
+    burn, mint_to, Burn, Mint, MintTo, TokenAccount, TokenInterface,
+};
+
+/// Close epoch accounts:
+/// - cranker: anyone, pays for the epoch and escrow accounts if nobody queued
+/// - vault: vault PDA
+/// - price_history: share price history PDA of the vault
+/// - vault_authority: vault authority PDA, owner of the shares escrow
+/// - shares_mint: vault's shares mint
+/// - shares_escrow: vault authority's ATA for shares
+/// - vault_base_asset_ata: vault's ATA for base assets
+/// - epoch: current epoch PDA
+/// - shares_token_program
+/// - associated_token_program
+/// - system_program
+#[derive(Accounts)]
+pub struct CloseEpoch<'info> {
+    #[account(mut)]
+    cranker: Signer<'info>,
+    #[account(mut,
+        has_one = vault_authority,
+        has_one = shares_mint,
+        has_one = vault_base_asset_ata,
+        has_one = shares_token_program
+    )]
+    vault: AccountLoader<'info, Vault>,
+    #[account(mut, has_one = vault)]
+    price_history: AccountLoader<'info, PriceHistory>,
+    /// CHECK: vault authority checked (has_one)
+    vault_authority: AccountInfo<'info>,
+    #[account(mut)]
+    shares_mint: Box<InterfaceAccount<'info, Mint>>,
+    #[account(
+        init_if_needed,
+        payer = cranker,
+        associated_token::mint = shares_mint,
+        associated_token::authority = vault_authority,
+        associated_token::token_program = shares_token_program
+    )]
+    shares_escrow: Box<InterfaceAccount<'info, TokenAccount>>,
+    vault_base_asset_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        init_if_needed,
+        payer = cranker,
+        space = 8 + Epoch::MAX_SIZE,
+        seeds = [EPOCH_SEED.as_bytes(), vault.key().as_ref(), &vault.load()?.current_epoch.to_le_bytes()],
+        bump
+    )]
+    epoch: Box<Account<'info, Epoch>>,
+    shares_token_program: Interface<'info, TokenInterface>,
+    associated_token_program: Program<'info, AssociatedToken>,
+    system_program: Program<'info, System>,
+}
+
+/// Permissionless crank settling the current epoch once it has run for `epoch_duration`.
+/// Snapshots a single share price from total assets and supply, then settles every
+/// queued entry at it: mints the deposit shares into escrow, burns the escrowed redeem
+/// shares and reserves their base assets. Users claim with `claim_epoch`.
+///
+/// A vault left without assets for its outstanding shares (zeroed by a NAV report or a
+/// write-down) can't price deposits: its redeems settle at 0, and queued deposits, like
+/// deposits too small to mint a share, are refunded on claim instead of joining the vault.
+pub fn handle(ctx: Context<CloseEpoch>) -> Result<()> {
+    let mut vlt = ctx.accounts.vault.load_mut()?;
+    let now = Clock::get()?.unix_timestamp;
+
+    require!(vlt.epoch_ended(now), Errors::EpochNotEnded);
+    require!(
+        !vlt.breaker_tripped.is_true(),
+        Errors::CircuitBreakerTripped
+    );
+
+    let epoch = &mut ctx.accounts.epoch;
+    if epoch.vault == Pubkey::default() {
+        epoch.vault = ctx.accounts.vault.key();
+        epoch.id = vlt.current_epoch;
+        epoch.bump = ctx.bumps.epoch;
+    }
+
+    // Single price for the whole epoch
+    let total_assets = vlt.total_base_assets;
+    let total_shares = ctx.accounts.shares_mint.supply;
+    let shares_out = if total_assets == 0 && total_shares > 0 {
+        0
+    } else {
+        convert_to_shares(epoch.queued_assets, total_assets, total_shares)?
+    };
+    let assets_out = convert_to_assets(epoch.queued_shares, total_assets, total_shares)?;
+    let deposited_assets = if shares_out > 0 {
+        epoch.queued_assets
+    } else {
+        0
+    };
+
+    msg!(
+        "closing epoch {} of vault {}: {} assets in for {} shares, {} shares out for {} assets",
+        epoch.id,
+        ctx.accounts.vault.key(),
+        epoch.queued_assets,
+        shares_out,
+        epoch.queued_shares,
+        assets_out
+    );
+
+    let vlt_address = ctx.accounts.vault.key();
+    let vlt_auth_seeds: &[&[&[u8]]] = &[&[
+        VAULT_AUTHORITY_SEED.as_bytes(),
+        vlt_address.as_ref(),
+        &[vlt.vault_authority_bump],
+    ]];
+
+    // Burn escrowed redeem shares
+    if epoch.queued_shares > 0 {
+        let burn_accounts = Burn {
+            mint: ctx.accounts.shares_mint.to_account_info(),
+            from: ctx.accounts.shares_escrow.to_account_info(),
+            authority: ctx.accounts.vault_authority.to_account_info(),
+        };
+        let burn_ctx = CpiContext::new_with_signer(
+            ctx.accounts.shares_token_program.to_account_info(),
+            burn_accounts,
+            vlt_auth_seeds,
+        );
+        burn(burn_ctx, epoch.queued_shares)?;
+    }
+
+    // Mint deposit shares into escrow, claimed pro-rata
+    if shares_out > 0 {
+        let mint_accounts = MintTo {
+            mint: ctx.accounts.shares_mint.to_account_info(),
+            to: ctx.accounts.shares_escrow.to_account_info(),
+            authority: ctx.accounts.vault_authority.to_account_info(),
+        };
+        let mint_ctx = CpiContext::new_with_signer(
+            ctx.accounts.shares_token_program.to_account_info(),
+            mint_accounts,
+            vlt_auth_seeds,
+        );
+        mint_to(mint_ctx, shares_out)?;
+    }
+
+    // Queued deposits join the share price, redeemed assets leave it for the reserve,
+    // refunded deposits stay reserved until claimed
+    vlt.total_base_assets = total_assets
+        .checked_add(deposited_assets)
+        .ok_or(Errors::MathOverflow)?
+        .checked_sub(assets_out)
+        .ok_or(Errors::MathOverflow)?;
+    vlt.reserved_assets = vlt
+        .reserved_assets
+        .checked_sub(deposited_assets)
+        .ok_or(Errors::MathOverflow)?
+        .checked_add(assets_out)
+        .ok_or(Errors::MathOverflow)?;
+    require!(
+        ctx.accounts.vault_base_asset_ata.amount >= vlt.reserved_assets,
+        Errors::InsufficientVaultLiquidity
+    );
+
+    ctx.accounts.shares_mint.reload()?;
+    ctx.accounts.price_history.load_mut()?.record(
+        now,
+        vlt.total_base_assets,
+        ctx.accounts.shares_mint.supply,
+    );
+
+    epoch.total_assets = total_assets;
+    epoch.total_shares = total_shares;
+    epoch.settled_shares = shares_out;
+    epoch.settled_assets = assets_out;
+    epoch.closed_at = now;
+
+    vlt.current_epoch = vlt
+        .current_epoch
+        .checked_add(1)
+        .ok_or(Errors::MathOverflow)?;
+    vlt.epoch_started_at = now;
+
+    emit!(EpochClosedEvent {
+        vault: ctx.accounts.vault.key(),
+        epoch: epoch.id,
+        total_assets,
+        total_shares,
+        deposited_assets,
+        refunded_assets: epoch.queued_assets - deposited_assets,
+        minted_shares: shares_out,
+        redeemed_shares: epoch.queued_shares,
+        redeemed_assets: assets_out,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct EpochClosedEvent {
+    pub vault: Pubkey,
+    pub epoch: u64,
+    pub total_assets: u64,
+    pub total_shares: u64,
+    pub deposited_assets: u64,
+    pub refunded_assets: u64,
+    pub minted_shares: u64,
+    pub redeemed_shares: u64,
+    pub redeemed_assets: u64,
+}
@@ -0,0 +1,111 @@
+use crate::constant::{GLOBAL_CONFIG_SEED, VAULT_AUTHORITY_SEED};
+use crate::state::{GlobalConfig, Vault};
+use crate::util::Errors;
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token_interface::{
+    transfer_checked, Mint, TokenAccount, TokenInterface, TransferChecked,
+};
+
+/// Collect protocol fees accounts:
+/// - cranker: anyone, pays for the recipient ATA if needed
+/// - global_config: protocol config PDA
+/// - vault: vault PDA
+/// - vault_authority: vault authority PDA
+/// - base_asset_mint: base token asset mint
+/// - vault_base_asset_ata: vault's ATA for base assets
+/// - fee_recipient: protocol fee recipient of the global config
+/// - fee_recipient_ata: recipient's ATA receiving base assets
+/// - token_program
+/// - associated_token_program
+/// - system_program
+#[derive(Accounts)]
+pub struct CollectProtocolFees<'info> {
+    #[account(mut)]
+    cranker: Signer<'info>,
+    #[account(
+        seeds = [GLOBAL_CONFIG_SEED.as_bytes()],
+        bump = global_config.bump,
+        has_one = fee_recipient
+    )]
+    global_config: Box<Account<'info, GlobalConfig>>,
+    #[account(mut,
+        has_one = base_asset_mint,
+        has_one = vault_authority,
+        has_one = vault_base_asset_ata,
+        has_one = token_program
+    )]
+    vault: AccountLoader<'info, Vault>,
+    /// CHECK: vault authority checked (has_one)
+    vault_authority: AccountInfo<'info>,
+    #[account()]
+    base_asset_mint: Box<InterfaceAccount<'info, Mint>>,
+    #[account(mut)]
+    vault_base_asset_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+    /// CHECK: fee recipient checked (has_one)
+    fee_recipient: AccountInfo<'info>,
+    #[account(
+        init_if_needed,
+        payer = cranker,
+        associated_token::mint = base_asset_mint,
+        associated_token::authority = fee_recipient,
+        associated_token::token_program = token_program
+    )]
+    fee_recipient_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+    token_program: Interface<'info, TokenInterface>,
+    associated_token_program: Program<'info, AssociatedToken>,
+    system_program: Program<'info, System>,
+}
+
+/// Pays the protocol cut accrued by the vault to the protocol fee recipient.
+/// Early-exit penalties are not cut, only vault fees accrue here.
+/// Permissionless, funds can only go to the recipient set in the global config.
+pub fn handle(ctx: Context<CollectProtocolFees>) -> Result<()> {
+    let mut vlt = ctx.accounts.vault.load_mut()?;
+
+    let fees = vlt.protocol_fees;
+    require!(fees > 0, Errors::NoProtocolFees);
+
+    msg!(
+        "collecting {} base assets of protocol fees from vault {} to {}",
+        fees,
+        ctx.accounts.vault.key(),
+        ctx.accounts.fee_recipient.key()
+    );
+
+    let vlt_address = ctx.accounts.vault.key();
+    let transfer_accounts = TransferChecked {
+        mint: ctx.accounts.base_asset_mint.to_account_info(),
+        from: ctx.accounts.vault_base_asset_ata.to_account_info(),
+        to: ctx.accounts.fee_recipient_ata.to_account_info(),
+        authority: ctx.accounts.vault_authority.to_account_info(),
+    };
+    let vlt_auth_seeds: &[&[&[u8]]] = &[&[
+        VAULT_AUTHORITY_SEED.as_bytes(),
+        vlt_address.as_ref(),
+        &[vlt.vault_authority_bump],
+    ]];
+    let transfer_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        transfer_accounts,
+        vlt_auth_seeds,
+    );
+    transfer_checked(transfer_ctx, fees, ctx.accounts.base_asset_mint.decimals)?;
+
+    vlt.protocol_fees = 0;
+
+    emit!(ProtocolFeesCollectedEvent {
+        vault: ctx.accounts.vault.key(),
+        fee_recipient: ctx.accounts.fee_recipient.key(),
+        fees,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct ProtocolFeesCollectedEvent {
+    pub vault: Pubkey,
+    pub fee_recipient: Pubkey,
+    pub fees: u64,
+}
@@ -0,0 +1,146 @@
+use crate::constant::{ALLOCATION_TARGET_SEED, VAULT_AUTHORITY_SEED};
+use crate::state::{AllocationTarget, PriceHistory, Vault};
+use crate::util::Errors;
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{
+    transfer_checked, Mint, TokenAccount, TokenInterface, TransferChecked,
+};
+
+/// Deallocate accounts:
+/// - admin: vault admin
+/// - vault: vault PDA
+/// - vault_authority: vault authority PDA, owner of the source escrow
+/// - price_history: share price history PDA of the vault
+/// - shares_mint: vault's shares mint
+/// - base_asset_mint: vault's base asset mint
+/// - vault_base_asset_ata: vault's ATA for base assets
+/// - source_ata: strategy escrow the assets were allocated to
+/// - allocation_target: allocation target PDA of the source escrow
+/// - token_program
+#[derive(Accounts)]
+pub struct Deallocate<'info> {
+    admin: Signer<'info>,
+    #[account(
+        mut,
+        has_one = base_asset_mint,
+        has_one = vault_authority,
+        has_one = vault_base_asset_ata,
+        has_one = token_program,
+        has_one = shares_mint,
+        has_one = admin
+    )]
+    vault: AccountLoader<'info, Vault>,
+    /// CHECK: vault authority checked (has_one)
+    vault_authority: AccountInfo<'info>,
+    #[account(mut, has_one = vault)]
+    price_history: AccountLoader<'info, PriceHistory>,
+    shares_mint: Box<InterfaceAccount<'info, Mint>>,
+    #[account(
+        mint::token_program = token_program,
+    )]
+    base_asset_mint: Box<InterfaceAccount<'info, Mint>>,
+    #[account(mut)]
+    vault_base_asset_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        token::mint = base_asset_mint,
+        token::token_program = token_program,
+    )]
+    source_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        seeds = [ALLOCATION_TARGET_SEED.as_bytes(), vault.key().as_ref(), source_ata.key().as_ref()],
+        bump = allocation_target.bump
+    )]
+    allocation_target: Box<Account<'info, AllocationTarget>>,
+    token_program: Interface<'info, TokenInterface>,
+}
+
+/// Moves base assets back from a strategy escrow into the vault's ATA.
+/// Never blocked by pauses or shutdown: in an emergency the admin pulls funds
+/// back from every strategy (one instruction per escrow) so depositors can exit.
+/// The vault authority signs the transfer as owner of the escrow, so the strategy doesn't
+/// have to co-sign.
+/// Returns first repay the strategy's deployed assets, anything above them is profit
+/// credited to the vault's total assets.
+/// A Token-2022 transfer fee withheld on the way back is a loss to the vault's assets.
+pub fn handle(ctx: Context<Deallocate>, amount: u64) -> Result<()> {
+    require!(amount > 0, Errors::InvalidAmount);
+    require!(
+        amount <= ctx.accounts.source_ata.amount,
+        Errors::InsufficientBaseAssetBalance
+    );
+
+    msg!(
+        "deallocating {} base assets from {} back to vault {}",
+        amount,
+        ctx.accounts.source_ata.key(),
+        ctx.accounts.vault.key()
+    );
+
+    let transfer_accounts = TransferChecked {
+        from: ctx.accounts.source_ata.to_account_info(),
+        mint: ctx.accounts.base_asset_mint.to_account_info(),
+        to: ctx.accounts.vault_base_asset_ata.to_account_info(),
+        authority: ctx.accounts.vault_authority.to_account_info(),
+    };
+    let vlt_address = ctx.accounts.vault.key();
+    let vlt_auth_bump = ctx.accounts.vault.load()?.vault_authority_bump;
+    let vlt_auth_seeds: &[&[&[u8]]] = &[&[
+        VAULT_AUTHORITY_SEED.as_bytes(),
+        vlt_address.as_ref(),
+        &[vlt_auth_bump],
+    ]];
+    let transfer_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        transfer_accounts,
+        vlt_auth_seeds,
+    );
+    let balance_before = ctx.accounts.vault_base_asset_ata.amount;
+    transfer_checked(transfer_ctx, amount, ctx.accounts.base_asset_mint.decimals)?;
+
+    ctx.accounts.vault_base_asset_ata.reload()?;
+    let received = ctx
+        .accounts
+        .vault_base_asset_ata
+        .amount
+        .checked_sub(balance_before)
+        .ok_or(Errors::MathOverflow)?;
+    let fee = amount.saturating_sub(received);
+
+    // Returned yield can exceed what was deployed
+    let target = &mut ctx.accounts.allocation_target;
+    let profit = amount.saturating_sub(target.allocated_assets);
+    target.allocated_assets -= amount - profit;
+
+    let mut vlt = ctx.accounts.vault.load_mut()?;
+    vlt.total_base_assets = vlt
+        .total_base_assets
+        .checked_add(profit)
+        .ok_or(Errors::MathOverflow)?
+        .saturating_sub(fee);
+    ctx.accounts.price_history.load_mut()?.record(
+        Clock::get()?.unix_timestamp,
+        vlt.total_base_assets,
+        ctx.accounts.shares_mint.supply,
+    );
+
+    emit!(DeallocateEvent {
+        vault: ctx.accounts.vault.key(),
+        source_ata: ctx.accounts.source_ata.key(),
+        amount,
+        profit,
+        transfer_fee: fee,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct DeallocateEvent {
+    pub vault: Pubkey,
+    pub source_ata: Pubkey,
+    pub amount: u64,
+    pub profit: u64,
+    pub transfer_fee: u64,
+}
@@ -1,6 +1,12 @@
-use crate::constant::{VAULT_AUTHORITY_SEED};
-use crate::state::Vault;
-use crate::util::{convert_to_shares, Errors};
+use crate::constant::{
+    BLOCKED_ADDRESS_SEED, GLOBAL_CONFIG_SEED, USER_POSITION_SEED, VAULT_AUTHORITY_SEED,
+};
+use crate::state::{GlobalConfig, PriceHistory, UserPosition, Vault};
+use crate::util::{
+    allowlist_leaf, convert_to_assets_rounded_up, convert_to_shares, gross_up_for_transfer_fee,
+    is_native_mint, load_preceding_ed25519_message, transfer_fee, unwrap_sol, verify_proof,
+    wrap_sol, DepositVoucher, Errors,
+};
 use anchor_lang::prelude::*;
 use anchor_spl::associated_token::AssociatedToken;
 use anchor_spl::token_interface::{
@@ -12,14 +18,19 @@ use anchor_spl::token_interface::{
 /// - shares_mint: vault's shares mint
 /// - shares_ata: depositor's ATA for shares
 /// - base_asset_mint: base token asset mint
-/// - base_asset_ata: depositor's ATA holding base assets
+/// - base_asset_ata: depositor's ATA holding base assets (wSOL ATA created and closed by `deposit_sol`)
 /// - vault_base_asset_ata: vault's ATA for base assets
 /// - vault: vault PDA
+/// - price_history: share price history PDA of the vault
+/// - global_config: protocol config PDA (global pause)
+/// - user_position: depositor's position PDA (created on first deposit)
+/// - blocked_address: blocked address PDA of the signer, must be empty
+/// - instructions_sysvar: used to read the deposit voucher when the vault requires one
 /// - token_program
+/// - shares_token_program
 /// - associated_token_program
 /// - system_program
 #[derive(Accounts)]
-#[instruction(amount: u64, ticker: [u8; 16])]
 pub struct Deposit<'info> {
     #[account(mut)]
     signer: Signer<'info>,
@@ -30,24 +41,33 @@ pub struct Deposit<'info> {
         has_one = base_asset_mint,
         has_one = vault_authority,
         has_one = vault_base_asset_ata,
-        has_one = token_program
+        has_one = token_program,
+        has_one = shares_token_program
     )]
     vault: AccountLoader<'info, Vault>,
+    #[account(mut, has_one = vault)]
+    price_history: AccountLoader<'info, PriceHistory>,
     /// CHECK: vault authority checked (has_one)
     #[account(mut)]
     vault_authority: AccountInfo<'info>,
+    #[account(
+        seeds = [GLOBAL_CONFIG_SEED.as_bytes()],
+        bump = global_config.bump
+    )]
+    global_config: Box<Account<'info, GlobalConfig>>,
     #[account(
         init_if_needed,
         payer = signer,
         associated_token::mint = shares_mint,
         associated_token::authority = signer,
-        associated_token::token_program = token_program
+        associated_token::token_program = shares_token_program
     )]
     shares_ata: Box<InterfaceAccount<'info, TokenAccount>>,
     #[account()]
     base_asset_mint: Box<InterfaceAccount<'info, Mint>>,
     #[account(
-        mut,
+        init_if_needed,
+        payer = signer,
         associated_token::mint = base_asset_mint,
         associated_token::authority = signer,
         associated_token::token_program = token_program
@@ -55,35 +75,131 @@ pub struct Deposit<'info> {
     base_asset_ata: Box<InterfaceAccount<'info, TokenAccount>>,
     #[account(mut)]
     vault_base_asset_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        init_if_needed,
+        payer = signer,
+        space = 8 + UserPosition::MAX_SIZE,
+        seeds = [USER_POSITION_SEED.as_bytes(), vault.key().as_ref(), signer.key().as_ref()],
+        bump
+    )]
+    user_position: Box<Account<'info, UserPosition>>,
+    /// CHECK: blocked address PDA of the signer (checked by seeds), must not exist
+    #[account(
+        seeds = [BLOCKED_ADDRESS_SEED.as_bytes(), vault.key().as_ref(), signer.key().as_ref()],
+        bump
+    )]
+    blocked_address: UncheckedAccount<'info>,
+    /// CHECK: instructions sysvar (address checked)
+    #[account(address = solana_sdk_ids::sysvar::instructions::ID)]
+    instructions_sysvar: AccountInfo<'info>,
     token_program: Interface<'info, TokenInterface>,
+    shares_token_program: Interface<'info, TokenInterface>,
     associated_token_program: Program<'info, AssociatedToken>,
     system_program: Program<'info, System>,
 }
 
 /// Process a deposit: validate amount, transfer base asset to vault, mint shares.
 /// - amount: amount of base asset to deposit
-pub fn handle(ctx: Context<Deposit>, amount: u64) -> Result<()> {
+/// - proof: merkle proof of the signer in the vault allowlist (empty for open vaults)
+///
+/// Vaults with a compliance signer also require the preceding instruction to be an Ed25519
+/// signature of that signer over a `DepositVoucher` for this deposit.
+pub fn handle(ctx: Context<Deposit>, amount: u64, proof: Vec<[u8; 32]>) -> Result<()> {
+    enter(ctx, EnterAmount::Assets(amount), &proof, false)
+}
+
+/// Exact side of an entry requested by the depositor.
+#[derive(Clone, Copy)]
+pub(crate) enum EnterAmount {
+    /// `deposit`: exact base assets in, shares rounded down
+    Assets(u64),
+    /// `mint`: exact shares out, base assets rounded up
+    Shares(u64),
+}
+
+/// Shared entry path of `deposit` and `mint`: enforce caps, pull base assets, mint shares.
+/// Rejected while the vault settles in epochs, entries then go through `queue_deposit`.
+/// With a transfer-fee base asset, shares are minted on the net amount the vault receives:
+/// `deposit` pulls the exact amount, `mint` pulls the gross amount netting the shares' worth.
+/// With `native`, the pulled amount is first wrapped from the depositor's lamports into its
+/// wSOL ATA, which is closed back to the depositor once the shares are minted.
+pub(crate) fn enter(
+    ctx: Context<Deposit>,
+    entry: EnterAmount,
+    proof: &[[u8; 32]],
+    native: bool,
+) -> Result<()> {
     let mut vlt = ctx.accounts.vault.load_mut()?;
+    require!(!vlt.epochs_enabled(), Errors::EpochsEnabled);
+
+    let total_shares = ctx.accounts.shares_mint.supply;
+    let total_assets = vlt.total_base_assets;
+    let base_asset_mint = ctx.accounts.base_asset_mint.to_account_info();
+
+    // (amount pulled from the depositor, net amount expected in the vault)
+    let (pulled, amount) = match entry {
+        EnterAmount::Assets(assets) => (
+            assets,
+            assets
+                .checked_sub(transfer_fee(&base_asset_mint, assets)?)
+                .ok_or(Errors::MathOverflow)?,
+        ),
+        EnterAmount::Shares(shares) => {
+            let assets = convert_to_assets_rounded_up(shares, total_assets, total_shares)?;
+            (gross_up_for_transfer_fee(&base_asset_mint, assets)?, assets)
+        }
+    };
+
+    if native {
+        require!(
+            is_native_mint(&ctx.accounts.base_asset_mint.key()),
+            Errors::NotNativeMint
+        );
+        wrap_sol(
+            &ctx.accounts.system_program.to_account_info(),
+            &ctx.accounts.token_program.to_account_info(),
+            &ctx.accounts.signer.to_account_info(),
+            &ctx.accounts.base_asset_ata.to_account_info(),
+            pulled,
+        )?;
+        ctx.accounts.base_asset_ata.reload()?;
+    }
 
     require!(
-        ctx.accounts.base_asset_ata.amount >= amount,
+        ctx.accounts.base_asset_ata.amount >= pulled,
         Errors::InsufficientBaseAssetBalance
     );
     require!(amount > 0, Errors::ZeroDeposit);
-    require!(vlt.deposit_paused == 0, Errors::DepositPaused);
+    require!(
+        ctx.accounts.blocked_address.data_is_empty(),
+        Errors::AddressBlocked
+    );
+
+    let position = &mut ctx.accounts.user_position;
+    position.init_if_needed(
+        ctx.accounts.vault.key(),
+        ctx.accounts.signer.key(),
+        ctx.bumps.user_position,
+    );
+    admit(
+        &ctx.accounts.global_config,
+        &vlt,
+        &ctx.accounts.vault.key(),
+        &ctx.accounts.signer.key(),
+        position,
+        total_assets,
+        amount,
+        proof,
+        &ctx.accounts.instructions_sysvar,
+    )?;
 
     msg!(
-        "depositing {} base assets into vault {}",
+        "depositing {} base assets ({} before transfer fee) into vault {}",
         amount,
+        pulled,
         ctx.accounts.vault.key()
     );
 
-    let total_shares = ctx.accounts.shares_mint.supply;
-    let total_assets = vlt.total_base_assets;
-
-    //to be minted
-    let to_mint = convert_to_shares(amount, total_assets, total_shares)?;
-
     // Transfer base assets from user to vault
     let transfer_accounts = TransferChecked {
         mint: ctx.accounts.base_asset_mint.to_account_info(),
@@ -95,7 +211,22 @@ pub fn handle(ctx: Context<Deposit>, amount: u64) -> Result<()> {
         ctx.accounts.token_program.to_account_info(),
         transfer_accounts,
     );
-    transfer_checked(transfer_ctx, amount, ctx.accounts.base_asset_mint.decimals)?;
+    let balance_before = ctx.accounts.vault_base_asset_ata.amount;
+    transfer_checked(transfer_ctx, pulled, ctx.accounts.base_asset_mint.decimals)?;
+
+    // Credit what actually arrived, the transfer fee may differ from the prediction
+    ctx.accounts.vault_base_asset_ata.reload()?;
+    let received = ctx
+        .accounts
+        .vault_base_asset_ata
+        .amount
+        .checked_sub(balance_before)
+        .ok_or(Errors::MathOverflow)?;
+    require!(received >= amount, Errors::InsufficientBaseAssetBalance);
+    let to_mint = match entry {
+        EnterAmount::Assets(_) => convert_to_shares(received, total_assets, total_shares)?,
+        EnterAmount::Shares(shares) => shares,
+    };
 
     // Mint shares to user
     let vlt_address = ctx.accounts.vault.key();
@@ -110,7 +241,7 @@ pub fn handle(ctx: Context<Deposit>, amount: u64) -> Result<()> {
         &[vlt.vault_authority_bump],
     ]];
     let mint_ctx = CpiContext::new_with_signer(
-        ctx.accounts.token_program.to_account_info(),
+        ctx.accounts.shares_token_program.to_account_info(),
         mint_accounts,
         vlt_auth_seeds,
     );
@@ -119,15 +250,95 @@ pub fn handle(ctx: Context<Deposit>, amount: u64) -> Result<()> {
     // Update vault state
     vlt.total_base_assets = vlt
         .total_base_assets
-        .checked_add(amount)
+        .checked_add(received)
         .ok_or(Errors::MathOverflow)?;
 
+    ctx.accounts.shares_mint.reload()?;
+    ctx.accounts.price_history.load_mut()?.record(
+        Clock::get()?.unix_timestamp,
+        vlt.total_base_assets,
+        ctx.accounts.shares_mint.supply,
+    );
+
+    ctx.accounts.user_position.record_entry(received, to_mint)?;
+
     emit!(DepositEvent {
         depositor: ctx.accounts.signer.key(),
-        base_asset_amount: amount,
+        base_asset_amount: received,
         shares_minted: to_mint,
     });
 
+    // Leave no wSOL behind, whatever the ATA held is unwrapped to the depositor
+    if native {
+        unwrap_sol(
+            &ctx.accounts.token_program.to_account_info(),
+            &ctx.accounts.signer.to_account_info(),
+            &ctx.accounts.base_asset_ata.to_account_info(),
+        )?;
+    }
+
+    Ok(())
+}
+
+/// Admission checks shared by every entry path (direct deposit/mint and queued
+/// epoch deposits): shutdown and global/vault pause, allowlist proof, deposit caps and the
+/// compliance voucher. Records the amount and timestamp on the depositor's position.
+/// - total_assets: vault assets the deposit adds to, for the vault-wide cap
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn admit(
+    config: &GlobalConfig,
+    vlt: &Vault,
+    vault: &Pubkey,
+    depositor: &Pubkey,
+    position: &mut UserPosition,
+    total_assets: u64,
+    amount: u64,
+    proof: &[[u8; 32]],
+    instructions_sysvar: &AccountInfo,
+) -> Result<()> {
+    require!(!config.paused, Errors::ProtocolPaused);
+    require!(!vlt.shutdown.is_true(), Errors::VaultShutdown);
+    require!(vlt.deposit_paused == 0, Errors::DepositPaused);
+    require!(
+        !vlt.breaker_tripped.is_true(),
+        Errors::CircuitBreakerTripped
+    );
+    if vlt.has_allowlist() {
+        require!(
+            verify_proof(proof, vlt.allowlist_root, allowlist_leaf(depositor)),
+            Errors::NotAllowlisted
+        );
+    }
+
+    let deposited_by_user = position
+        .deposited_assets
+        .checked_add(amount)
+        .ok_or(Errors::MathOverflow)?;
+    if vlt.deposit_cap > 0 {
+        let total_after = total_assets
+            .checked_add(amount)
+            .ok_or(Errors::MathOverflow)?;
+        require!(total_after <= vlt.deposit_cap, Errors::DepositCapExceeded);
+    }
+    if vlt.user_deposit_cap > 0 {
+        require!(
+            deposited_by_user <= vlt.user_deposit_cap,
+            Errors::UserDepositCapExceeded
+        );
+    }
+    position.deposited_assets = deposited_by_user;
+
+    let now = Clock::get()?.unix_timestamp;
+    position.record_deposit(now);
+
+    if vlt.requires_voucher() {
+        let message = load_preceding_ed25519_message(instructions_sysvar, &vlt.compliance_signer)?;
+        let voucher =
+            DepositVoucher::try_from_slice(&message).map_err(|_| Errors::InvalidVoucher)?;
+        voucher.verify(vault, depositor, amount, now, position.last_voucher_nonce)?;
+        position.last_voucher_nonce = voucher.nonce;
+    }
+
     Ok(())
 }
 
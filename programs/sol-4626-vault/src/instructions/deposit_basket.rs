@@ -0,0 +1,173 @@
+use crate::constant::{BASKET_AUTHORITY_SEED, GLOBAL_CONFIG_SEED};
+use crate::state::{Basket, GlobalConfig};
+use crate::util::{convert_to_shares, Errors};
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token_interface::{
+    mint_to, transfer_checked, Mint, MintTo, TokenAccount, TokenInterface, TransferChecked,
+};
+
+/// Deposit basket accounts:
+/// - signer: depositor
+/// - basket: basket PDA
+/// - basket_authority: basket authority PDA
+/// - global_config: protocol config PDA (global pause)
+/// - shares_mint: basket shares mint
+/// - shares_ata: depositor's ATA for shares
+/// - asset_mint: deposited asset mint
+/// - asset_ata: depositor's ATA holding the asset
+/// - basket_asset_ata: basket's ATA for the asset
+/// - token_program
+/// - associated_token_program
+/// - system_program
+///
+/// Remaining accounts, per basket asset in order: its price oracle.
+#[derive(Accounts)]
+pub struct DepositBasket<'info> {
+    #[account(mut)]
+    signer: Signer<'info>,
+    #[account(
+        mut,
+        has_one = basket_authority,
+        has_one = shares_mint,
+        has_one = token_program
+    )]
+    basket: Box<Account<'info, Basket>>,
+    /// CHECK: basket authority checked (has_one)
+    basket_authority: AccountInfo<'info>,
+    #[account(
+        seeds = [GLOBAL_CONFIG_SEED.as_bytes()],
+        bump = global_config.bump
+    )]
+    global_config: Box<Account<'info, GlobalConfig>>,
+    #[account(mut)]
+    shares_mint: Box<InterfaceAccount<'info, Mint>>,
+    #[account(
+        init_if_needed,
+        payer = signer,
+        associated_token::mint = shares_mint,
+        associated_token::authority = signer,
+        associated_token::token_program = token_program
+    )]
+    shares_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+    asset_mint: Box<InterfaceAccount<'info, Mint>>,
+    #[account(
+        mut,
+        associated_token::mint = asset_mint,
+        associated_token::authority = signer,
+        associated_token::token_program = token_program
+    )]
+    asset_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(mut)]
+    basket_asset_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+    token_program: Interface<'info, TokenInterface>,
+    associated_token_program: Program<'info, AssociatedToken>,
+    system_program: Program<'info, System>,
+}
+
+/// Deposit one whitelisted asset, minting shares on the value received (net of any
+/// transfer fee) against the whole basket value.
+/// - amount: amount of the asset to deposit
+pub fn handle(ctx: Context<DepositBasket>, amount: u64) -> Result<()> {
+    require!(!ctx.accounts.global_config.paused, Errors::ProtocolPaused);
+    require!(
+        ctx.accounts.asset_ata.amount >= amount,
+        Errors::InsufficientBaseAssetBalance
+    );
+
+    let basket = &ctx.accounts.basket;
+    let index = basket.asset_index(&ctx.accounts.asset_mint.key())?;
+    let asset = basket.assets[index];
+    require_keys_eq!(
+        ctx.accounts.basket_asset_ata.key(),
+        asset.ata,
+        Errors::InvalidBasketAccounts
+    );
+
+    // Value before the deposit lands
+    let valuation = basket.valuation(ctx.remaining_accounts, Clock::get()?.unix_timestamp)?;
+
+    let transfer_accounts = TransferChecked {
+        mint: ctx.accounts.asset_mint.to_account_info(),
+        from: ctx.accounts.asset_ata.to_account_info(),
+        to: ctx.accounts.basket_asset_ata.to_account_info(),
+        authority: ctx.accounts.signer.to_account_info(),
+    };
+    let transfer_ctx = CpiContext::new(
+        ctx.accounts.token_program.to_account_info(),
+        transfer_accounts,
+    );
+    let balance_before = ctx.accounts.basket_asset_ata.amount;
+    transfer_checked(transfer_ctx, amount, asset.decimals)?;
+
+    // Price what actually arrived, net of any transfer fee
+    ctx.accounts.basket_asset_ata.reload()?;
+    let received = ctx
+        .accounts
+        .basket_asset_ata
+        .amount
+        .checked_sub(balance_before)
+        .ok_or(Errors::MathOverflow)?;
+    let value =
+        valuation.prices[index].value_of(received, asset.decimals, Basket::VALUE_DECIMALS)?;
+    let shares = convert_to_shares(
+        value,
+        valuation.total_value,
+        ctx.accounts.shares_mint.supply,
+    )?;
+    require!(shares > 0, Errors::ZeroDeposit);
+
+    let basket = &mut ctx.accounts.basket;
+    basket.assets[index].balance = asset
+        .balance
+        .checked_add(received)
+        .ok_or(Errors::MathOverflow)?;
+
+    msg!(
+        "depositing {} of {} ({} before transfer fee) worth {} into basket {}",
+        received,
+        asset.mint,
+        amount,
+        value,
+        basket.key()
+    );
+
+    let basket_address = basket.key();
+    let basket_auth_seeds: &[&[&[u8]]] = &[&[
+        BASKET_AUTHORITY_SEED.as_bytes(),
+        basket_address.as_ref(),
+        &[basket.basket_authority_bump],
+    ]];
+    let mint_accounts = MintTo {
+        mint: ctx.accounts.shares_mint.to_account_info(),
+        to: ctx.accounts.shares_ata.to_account_info(),
+        authority: ctx.accounts.basket_authority.to_account_info(),
+    };
+    let mint_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        mint_accounts,
+        basket_auth_seeds,
+    );
+    mint_to(mint_ctx, shares)?;
+
+    emit!(BasketDepositEvent {
+        depositor: ctx.accounts.signer.key(),
+        basket: basket_address,
+        mint: asset.mint,
+        amount: received,
+        value,
+        shares_minted: shares,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct BasketDepositEvent {
+    pub depositor: Pubkey,
+    pub basket: Pubkey,
+    pub mint: Pubkey,
+    pub amount: u64,
+    pub value: u64,
+    pub shares_minted: u64,
+}
@@ -0,0 +1,11 @@
+use crate::instructions::deposit::{self, Deposit, EnterAmount};
+use anchor_lang::prelude::*;
+
+/// Deposit native SOL into a wSOL vault. The lamports are wrapped into the depositor's wSOL
+/// ATA, deposited like `deposit`, and the ATA is closed back to the depositor.
+/// Uses the `Deposit` accounts and is subject to the same pauses and caps.
+/// - lamports: amount of SOL to deposit
+/// - proof: merkle proof of the signer in the vault allowlist (empty for open vaults)
+pub fn handle(ctx: Context<Deposit>, lamports: u64, proof: Vec<[u8; 32]>) -> Result<()> {
+    deposit::enter(ctx, EnterAmount::Assets(lamports), &proof, true)
+}
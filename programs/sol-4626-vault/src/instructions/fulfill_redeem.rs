@@ -0,0 +1,151 @@
+use crate::constant::{USER_POSITION_SEED, VAULT_AUTHORITY_SEED};
+use crate::state::{PriceHistory, RedeemRequest, UserPosition, Vault};
+use crate::util::{convert_to_assets, Errors};
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{burn, Burn, Mint, TokenAccount, TokenInterface};
+
+/// Fulfill redeem accounts:
+/// - admin: vault admin, acting as allocator
+/// - vault: vault PDA
+/// - price_history: share price history PDA of the vault
+/// - vault_authority: vault authority PDA, owner of the shares escrow
+/// - shares_mint: vault's shares mint
+/// - shares_escrow: vault authority's ATA for shares
+/// - vault_base_asset_ata: vault's ATA for base assets
+/// - redeem_request: redeem request PDA being fulfilled
+/// - user_position: position PDA of the request owner
+/// - shares_token_program
+#[derive(Accounts)]
+pub struct FulfillRedeem<'info> {
+    admin: Signer<'info>,
+    #[account(mut,
+        has_one = admin,
+        has_one = vault_authority,
+        has_one = shares_mint,
+        has_one = vault_base_asset_ata,
+        has_one = shares_token_program
+    )]
+    vault: AccountLoader<'info, Vault>,
+    #[account(mut, has_one = vault)]
+    price_history: AccountLoader<'info, PriceHistory>,
+    /// CHECK: vault authority checked (has_one)
+    vault_authority: AccountInfo<'info>,
+    #[account(mut)]
+    shares_mint: Box<InterfaceAccount<'info, Mint>>,
+    #[account(
+        mut,
+        associated_token::mint = shares_mint,
+        associated_token::authority = vault_authority,
+        associated_token::token_program = shares_token_program
+    )]
+    shares_escrow: Box<InterfaceAccount<'info, TokenAccount>>,
+    vault_base_asset_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(mut, has_one = vault)]
+    redeem_request: Box<Account<'info, RedeemRequest>>,
+    #[account(
+        mut,
+        seeds = [USER_POSITION_SEED.as_bytes(), vault.key().as_ref(), redeem_request.owner.as_ref()],
+        bump = user_position.bump
+    )]
+    user_position: Box<Account<'info, UserPosition>>,
+    shares_token_program: Interface<'info, TokenInterface>,
+}
+
+/// Locks in the price of a pending redeem request: burns the escrowed shares and
+/// reserves the base assets they are worth (rounded down) for the owner to claim.
+/// Called by the allocator once enough assets have been deallocated.
+pub fn handle(ctx: Context<FulfillRedeem>) -> Result<()> {
+    let mut vlt = ctx.accounts.vault.load_mut()?;
+    let request = &mut ctx.accounts.redeem_request;
+
+    let shares = request.pending_shares;
+    require!(shares > 0, Errors::NoPendingRedeemRequest);
+    require!(
+        !vlt.breaker_tripped.is_true(),
+        Errors::CircuitBreakerTripped
+    );
+
+    let assets = convert_to_assets(
+        shares,
+        vlt.total_base_assets,
+        ctx.accounts.shares_mint.supply,
+    )?;
+    require!(
+        vlt.idle_assets(ctx.accounts.vault_base_asset_ata.amount) >= assets,
+        Errors::InsufficientVaultLiquidity
+    );
+
+    msg!(
+        "fulfilling redeem request of {} for {} shares at {} base assets",
+        request.owner,
+        shares,
+        assets
+    );
+
+    // Burn escrowed shares
+    let vlt_address = ctx.accounts.vault.key();
+    let vlt_auth_seeds: &[&[&[u8]]] = &[&[
+        VAULT_AUTHORITY_SEED.as_bytes(),
+        vlt_address.as_ref(),
+        &[vlt.vault_authority_bump],
+    ]];
+    let burn_accounts = Burn {
+        mint: ctx.accounts.shares_mint.to_account_info(),
+        from: ctx.accounts.shares_escrow.to_account_info(),
+        authority: ctx.accounts.vault_authority.to_account_info(),
+    };
+    let burn_ctx = CpiContext::new_with_signer(
+        ctx.accounts.shares_token_program.to_account_info(),
+        burn_accounts,
+        vlt_auth_seeds,
+    );
+    burn(burn_ctx, shares)?;
+
+    // Move assets out of the share price into the claimable reserve
+    vlt.total_base_assets = vlt
+        .total_base_assets
+        .checked_sub(assets)
+        .ok_or(Errors::MathOverflow)?;
+    vlt.reserved_assets = vlt
+        .reserved_assets
+        .checked_add(assets)
+        .ok_or(Errors::MathOverflow)?;
+    vlt.pending_redeem_shares = vlt
+        .pending_redeem_shares
+        .checked_sub(shares)
+        .ok_or(Errors::MathOverflow)?;
+
+    ctx.accounts.shares_mint.reload()?;
+    ctx.accounts.price_history.load_mut()?.record(
+        Clock::get()?.unix_timestamp,
+        vlt.total_base_assets,
+        ctx.accounts.shares_mint.supply,
+    );
+
+    let realised_pnl = ctx.accounts.user_position.record_exit(shares, assets)?;
+
+    request.pending_shares = 0;
+    request.claimable_assets = request
+        .claimable_assets
+        .checked_add(assets)
+        .ok_or(Errors::MathOverflow)?;
+
+    emit!(RedeemFulfilledEvent {
+        vault: ctx.accounts.vault.key(),
+        owner: request.owner,
+        shares,
+        assets,
+        realised_pnl,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct RedeemFulfilledEvent {
+    pub vault: Pubkey,
+    pub owner: Pubkey,
+    pub shares: u64,
+    pub assets: u64,
+    pub realised_pnl: i64,
+}
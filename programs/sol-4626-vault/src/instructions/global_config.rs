@@ -0,0 +1,164 @@
+use crate::constant::GLOBAL_CONFIG_SEED;
+use crate::program::Sol4626Vault;
+use crate::state::GlobalConfig;
+use crate::util::{Errors, BPS_DENOMINATOR};
+use anchor_lang::prelude::*;
+
+/// Initialize global config accounts:
+/// - protocol_admin: program upgrade authority, pays for the config
+/// - program: this program
+/// - program_data: program data account of this program, holds the upgrade authority
+/// - global_config: protocol config PDA
+/// - system_program
+#[derive(Accounts)]
+pub struct InitializeGlobalConfig<'info> {
+    #[account(mut)]
+    protocol_admin: Signer<'info>,
+    #[account(constraint = program.programdata_address()? == Some(program_data.key()))]
+    program: Program<'info, Sol4626Vault>,
+    #[account(
+        constraint = program_data.upgrade_authority_address == Some(protocol_admin.key())
+            @ Errors::Unauthorized
+    )]
+    program_data: Account<'info, ProgramData>,
+    #[account(
+        init,
+        payer = protocol_admin,
+        space = 8 + GlobalConfig::MAX_SIZE,
+        seeds = [GLOBAL_CONFIG_SEED.as_bytes()],
+        bump
+    )]
+    global_config: Box<Account<'info, GlobalConfig>>,
+    system_program: Program<'info, System>,
+}
+
+/// Creates the protocol config singleton, the signer becomes the protocol admin.
+/// Only the program's upgrade authority can run it, so the deployer can't be front-run.
+/// Must run once right after deployment, before any vault is initialized.
+/// - fee_recipient: wallet receiving the protocol cut of vault fees
+/// - protocol_fee_bps: protocol cut of vault fees in bps
+pub fn initialize_global_config(
+    ctx: Context<InitializeGlobalConfig>,
+    fee_recipient: Pubkey,
+    protocol_fee_bps: u16,
+) -> Result<()> {
+    require!(
+        protocol_fee_bps as u64 <= BPS_DENOMINATOR,
+        Errors::InvalidProtocolFee
+    );
+
+    let protocol_admin = ctx.accounts.protocol_admin.key();
+    msg!(
+        "initializing global config with protocol admin {} fee recipient {} fee {} bps",
+        protocol_admin,
+        fee_recipient,
+        protocol_fee_bps
+    );
+
+    let config = &mut ctx.accounts.global_config;
+    config.protocol_admin = protocol_admin;
+    config.fee_recipient = fee_recipient;
+    config.protocol_fee_bps = protocol_fee_bps;
+    config.paused = false;
+    config.vault_count = 0;
+    config.bump = ctx.bumps.global_config;
+
+    emit!(InitializeGlobalConfigEvent {
+        protocol_admin,
+        fee_recipient,
+        protocol_fee_bps,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct InitializeGlobalConfigEvent {
+    pub protocol_admin: Pubkey,
+    pub fee_recipient: Pubkey,
+    pub protocol_fee_bps: u16,
+}
+
+/// Protocol admin accounts, shared by the protocol configuration instructions:
+/// - protocol_admin: protocol admin
+/// - global_config: protocol config PDA
+#[derive(Accounts)]
+pub struct ProtocolAdmin<'info> {
+    protocol_admin: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [GLOBAL_CONFIG_SEED.as_bytes()],
+        bump = global_config.bump,
+        has_one = protocol_admin
+    )]
+    global_config: Box<Account<'info, GlobalConfig>>,
+}
+
+/// Sets the protocol cut of vault fees and the wallet receiving it.
+/// Fees already accrued on vaults are paid to the new recipient.
+pub fn set_protocol_fee(
+    ctx: Context<ProtocolAdmin>,
+    fee_recipient: Pubkey,
+    protocol_fee_bps: u16,
+) -> Result<()> {
+    require!(
+        protocol_fee_bps as u64 <= BPS_DENOMINATOR,
+        Errors::InvalidProtocolFee
+    );
+
+    msg!(
+        "setting protocol fee to {} bps paid to {}",
+        protocol_fee_bps,
+        fee_recipient
+    );
+
+    let config = &mut ctx.accounts.global_config;
+    config.fee_recipient = fee_recipient;
+    config.protocol_fee_bps = protocol_fee_bps;
+
+    emit!(SetProtocolFeeEvent {
+        fee_recipient,
+        protocol_fee_bps,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct SetProtocolFeeEvent {
+    pub fee_recipient: Pubkey,
+    pub protocol_fee_bps: u16,
+}
+
+/// Pauses or resumes deposits and allocations of every vault at once.
+/// Withdrawals and redemptions keep working so depositors can always exit.
+pub fn set_global_pause(ctx: Context<ProtocolAdmin>, paused: bool) -> Result<()> {
+    msg!("setting global pause to {}", paused);
+
+    ctx.accounts.global_config.paused = paused;
+
+    emit!(SetGlobalPauseEvent { paused });
+
+    Ok(())
+}
+
+#[event]
+pub struct SetGlobalPauseEvent {
+    pub paused: bool,
+}
+
+/// Hands the protocol admin role over to `protocol_admin`.
+pub fn set_protocol_admin(ctx: Context<ProtocolAdmin>, protocol_admin: Pubkey) -> Result<()> {
+    msg!("setting protocol admin to {}", protocol_admin);
+
+    ctx.accounts.global_config.protocol_admin = protocol_admin;
+
+    emit!(SetProtocolAdminEvent { protocol_admin });
+
+    Ok(())
+}
+
+#[event]
+pub struct SetProtocolAdminEvent {
+    pub protocol_admin: Pubkey,
+}
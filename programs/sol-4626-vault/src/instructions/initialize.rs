@@ -1,26 +1,97 @@
-use crate::constant::{SHARES_MINT_SEED, VAULT_AUTHORITY_SEED};
-use crate::state::Vault;
+use crate::constant::{
+    GLOBAL_CONFIG_SEED, MINT_VAULTS_SEED, PRICE_HISTORY_SEED, SHARES_MINT_SEED,
+    TOKEN_METADATA_PROGRAM_ID, TOKEN_METADATA_SEED, VAULT_AUTHORITY_SEED, VAULT_SEED,
+};
+use crate::state::{GlobalConfig, MintVaults, PriceHistory, Vault};
+use crate::util::{create_metadata_account, Errors, MintExtensions, SharesMetadata};
 use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program_pack::Pack;
+use anchor_lang::system_program::{
+    allocate, assign, create_account, transfer, Allocate, Assign, CreateAccount, Transfer,
+};
 use anchor_spl::associated_token::AssociatedToken;
-use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use anchor_spl::token_2022::spl_token_2022::extension::ExtensionType;
+use anchor_spl::token_2022::spl_token_2022::state::Mint as MintState;
+use anchor_spl::token_2022::{self, initialize_mint2, InitializeMint2};
+use anchor_spl::token_interface::spl_pod::optional_keys::OptionalNonZeroPubkey;
+use anchor_spl::token_interface::spl_token_metadata_interface::state::TokenMetadata;
+use anchor_spl::token_interface::{
+    metadata_pointer_initialize, non_transferable_mint_initialize, token_metadata_initialize,
+    MetadataPointerInitialize, Mint, NonTransferableMintInitialize, TokenAccount, TokenInterface,
+    TokenMetadataInitialize,
+};
+use anchor_spl::token_interface::{transfer_hook_initialize, TransferHookInitialize};
+use shares_transfer_hook::cpi::accounts::InitializeExtraAccountMetaList;
+use shares_transfer_hook::program::SharesTransferHook;
+use shares_transfer_hook::state::HookConfig;
 
+/// Initialize accounts:
+/// - admin: vault admin, pays for the accounts
+/// - base_asset_mint: base token asset mint
+/// - global_config: protocol config PDA, factory registry of every vault
+/// - mint_vaults: vault registry of the base asset mint (created with its first vault)
+/// - vault: vault PDA `[VAULT_SEED, base_asset_mint, vault_index]`
+/// - price_history: share price history PDA of the vault
+/// - vault_authority: vault authority PDA
+/// - vault_base_asset_ata: vault's ATA for base assets
+/// - shares_mint: vault's shares mint PDA, created by the handler
+/// - shares_metadata: Metaplex metadata PDA of the shares mint, only for classic SPL Token
+///   shares (Token-2022 shares mints carry their metadata in the token-metadata extension)
+/// - token_program: token program of the base asset
+/// - shares_token_program: token program of the shares mint, Token-2022 for soulbound or
+///   hooked shares
+/// - associated_token_program
+/// - system_program
+/// - token_metadata_program: Metaplex Token Metadata program, only for classic SPL Token shares
+/// - extra_account_meta_list: shares transfer hook extra account metas, only with a hook
+/// - hook_config: shares transfer hook config, only with a hook
+/// - transfer_hook_program: shares transfer hook program, only with a hook
 #[derive(Accounts)]
-#[instruction()]
+#[instruction(vault_index: u64)]
 pub struct Initialize<'info> {
     #[account(mut)]
     admin: Signer<'info>,
-    #[account(zero)]
+    #[account(
+        mint::token_program = token_program
+    )]
+    base_asset_mint: InterfaceAccount<'info, Mint>,
+    #[account(
+        mut,
+        seeds = [GLOBAL_CONFIG_SEED.as_bytes()],
+        bump = global_config.bump
+    )]
+    global_config: Box<Account<'info, GlobalConfig>>,
+    #[account(
+        init_if_needed,
+        payer = admin,
+        space = 8 + MintVaults::MAX_SIZE,
+        seeds = [MINT_VAULTS_SEED.as_bytes(), base_asset_mint.key().as_ref()],
+        bump,
+        constraint = mint_vaults.vault_count == vault_index @ Errors::InvalidVaultIndex
+    )]
+    mint_vaults: Box<Account<'info, MintVaults>>,
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + Vault::MAX_SIZE,
+        seeds = [VAULT_SEED.as_bytes(), base_asset_mint.key().as_ref(), &vault_index.to_le_bytes()],
+        bump
+    )]
     vault: AccountLoader<'info, Vault>,
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + PriceHistory::MAX_SIZE,
+        seeds = [PRICE_HISTORY_SEED.as_bytes(), vault.key().as_ref()],
+        bump
+    )]
+    price_history: AccountLoader<'info, PriceHistory>,
     /// CHECK: PDA used only as signing authority
     #[account(
         seeds = [VAULT_AUTHORITY_SEED.as_bytes(), vault.key().as_ref()],
         bump
     )]
     vault_authority: AccountInfo<'info>,
-    #[account(
-        mint::token_program = token_program
-    )]
-    base_asset_mint: InterfaceAccount<'info, Mint>,
     #[account(
         init,
         payer = admin,
@@ -29,21 +100,65 @@ pub struct Initialize<'info> {
         associated_token::token_program = token_program
     )]
     vault_base_asset_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+    /// CHECK: shares mint PDA (checked by seeds), created by the handler
     #[account(
-        init,
-        payer = admin,
-        mint::decimals = base_asset_mint.decimals,
-        mint::authority = vault_authority,
+        mut,
         seeds = [SHARES_MINT_SEED.as_bytes(), vault_authority.key().as_ref()],
         bump
     )]
-    shares_mint: InterfaceAccount<'info, Mint>,
+    shares_mint: UncheckedAccount<'info>,
+    /// CHECK: Metaplex metadata PDA (checked by seeds), created by the token metadata program
+    #[account(
+        mut,
+        seeds = [
+            TOKEN_METADATA_SEED.as_bytes(),
+            TOKEN_METADATA_PROGRAM_ID.as_ref(),
+            shares_mint.key().as_ref()
+        ],
+        seeds::program = TOKEN_METADATA_PROGRAM_ID,
+        bump
+    )]
+    shares_metadata: Option<UncheckedAccount<'info>>,
     token_program: Interface<'info, TokenInterface>,
+    shares_token_program: Interface<'info, TokenInterface>,
     associated_token_program: Program<'info, AssociatedToken>,
     system_program: Program<'info, System>,
+    /// CHECK: Metaplex Token Metadata program (checked by address)
+    #[account(address = TOKEN_METADATA_PROGRAM_ID)]
+    token_metadata_program: Option<UncheckedAccount<'info>>,
+    /// CHECK: created by the transfer hook program (checked by its seeds)
+    #[account(mut)]
+    extra_account_meta_list: Option<UncheckedAccount<'info>>,
+    /// CHECK: created by the transfer hook program (checked by its seeds)
+    #[account(mut)]
+    hook_config: Option<UncheckedAccount<'info>>,
+    transfer_hook_program: Option<Program<'info, SharesTransferHook>>,
 }
 
-pub fn handle(ctx: Context<Initialize>) -> Result<()> {
+/// Creates the next vault of the base asset mint and registers it in the mint's registry
+/// and in the protocol-wide vault count. Rejected while the protocol is paused.
+/// The shares mint is created under the shares token program, which may differ from the
+/// base asset's, and gets the name, symbol and uri.
+/// Base asset mints able to seize or freeze the vault's assets are rejected unless
+/// each such feature is opted into.
+/// - vault_index: must equal the registry `vault_count`
+/// - name, symbol, uri: shares token metadata
+/// - allowed_mint_extensions: `MintExtensions` flags accepted on the base asset mint
+/// - soulbound_shares: create the Token-2022 shares mint NonTransferable, so shares never
+///   leave the depositor's wallet (requires Token-2022 as the shares token program)
+/// - transfer_hook_mode: point the Token-2022 shares mint to the shares transfer hook, which
+///   checks the vault blocklist or allowlist on every transfer (0 = no hook)
+#[allow(clippy::too_many_arguments)]
+pub fn handle(
+    ctx: Context<Initialize>,
+    vault_index: u64,
+    name: String,
+    symbol: String,
+    uri: String,
+    allowed_mint_extensions: u16,
+    soulbound_shares: bool,
+    transfer_hook_mode: u8,
+) -> Result<()> {
     msg!(
         "initializing vault address: {} vault authority {} shares_mint: {} base_asset_mint: {}",
         ctx.accounts.vault.key(),
@@ -52,42 +167,357 @@ pub fn handle(ctx: Context<Initialize>) -> Result<()> {
         ctx.accounts.base_asset_mint.key()
     );
 
+    require!(!ctx.accounts.global_config.paused, Errors::ProtocolPaused);
+    SharesMetadata::validate(&name, &symbol, &uri)?;
+    let mint_extensions = MintExtensions::of(&ctx.accounts.base_asset_mint.to_account_info())?;
+    MintExtensions::check(mint_extensions, allowed_mint_extensions)?;
+
+    create_shares_mint(
+        ctx.accounts,
+        &ctx.bumps,
+        &name,
+        &symbol,
+        &uri,
+        soulbound_shares,
+        transfer_hook_mode,
+    )?;
+    if transfer_hook_mode != 0 {
+        init_transfer_hook(ctx.accounts, &ctx.bumps, transfer_hook_mode)?;
+    }
+
     let vlt = &mut ctx.accounts.vault.load_init()?;
 
     let admin = ctx.accounts.admin.key();
     let vault_authority = ctx.accounts.vault_authority.key();
     let shares_mint = ctx.accounts.shares_mint.key();
-    let shares_mint_decimals = ctx.accounts.shares_mint.decimals;
+    let shares_mint_decimals = ctx.accounts.base_asset_mint.decimals;
     let base_asset_mint = ctx.accounts.base_asset_mint.key();
     let vault_base_asset_ata = ctx.accounts.vault_base_asset_ata.key();
     let token_program = ctx.accounts.token_program.key();
+    let shares_token_program = ctx.accounts.shares_token_program.key();
 
     vlt.initialize(
         admin,
         vault_authority,
         shares_mint,
         base_asset_mint,
-        vault_base_asset_ata,
         token_program,
+        shares_token_program,
+        vault_base_asset_ata,
         shares_mint_decimals,
+        vault_index,
+        allowed_mint_extensions,
+        soulbound_shares,
+        transfer_hook_mode,
+        ctx.bumps.vault,
         ctx.bumps.vault_authority,
         ctx.bumps.shares_mint,
     )?;
 
+    let history = &mut ctx.accounts.price_history.load_init()?;
+    history.initialize(ctx.accounts.vault.key(), ctx.bumps.price_history);
+    history.record(Clock::get()?.unix_timestamp, 0, 0);
+
+    let registry = &mut ctx.accounts.mint_vaults;
+    if registry.base_asset_mint == Pubkey::default() {
+        registry.base_asset_mint = base_asset_mint;
+        registry.bump = ctx.bumps.mint_vaults;
+    }
+    registry.vault_count = registry
+        .vault_count
+        .checked_add(1)
+        .ok_or(Errors::MathOverflow)?;
+
+    let config = &mut ctx.accounts.global_config;
+    config.vault_count = config
+        .vault_count
+        .checked_add(1)
+        .ok_or(Errors::MathOverflow)?;
+
     emit!(InitializeEvent {
         vault: ctx.accounts.vault.key(),
         admin,
         shares_mint,
         base_asset_mint,
+        vault_index,
+        name,
+        symbol,
+        uri,
+        allowed_mint_extensions,
+        soulbound_shares,
+        transfer_hook_mode,
     });
 
     Ok(())
 }
 
+/// Creates the shares mint PDA with the vault authority as mint and freeze authority.
+/// Token-2022 mints point their metadata to themselves and store it in the token-metadata
+/// extension, classic mints get a Metaplex metadata account. Soulbound shares mints
+/// also get the NonTransferable extension, hooked ones the TransferHook extension.
+fn create_shares_mint(
+    accounts: &mut Initialize,
+    bumps: &InitializeBumps,
+    name: &str,
+    symbol: &str,
+    uri: &str,
+    soulbound: bool,
+    hook_mode: u8,
+) -> Result<()> {
+    let is_token_2022 = accounts.shares_token_program.key() == token_2022::ID;
+    require!(
+        !soulbound || is_token_2022,
+        Errors::SoulboundRequiresToken2022
+    );
+    let hook_accounts = [
+        accounts.extra_account_meta_list.is_some(),
+        accounts.hook_config.is_some(),
+        accounts.transfer_hook_program.is_some(),
+    ];
+    require!(
+        match hook_mode {
+            0 => hook_accounts == [false; 3],
+            HookConfig::BLOCKLIST | HookConfig::ALLOWLIST => {
+                is_token_2022 && !soulbound && hook_accounts == [true; 3]
+            }
+            _ => false,
+        },
+        Errors::InvalidTransferHook
+    );
+    let metaplex_accounts = [
+        accounts.shares_metadata.is_some(),
+        accounts.token_metadata_program.is_some(),
+    ];
+    require!(
+        metaplex_accounts == [!is_token_2022; 2],
+        Errors::InvalidSharesMetadata
+    );
+
+    let vault = accounts.vault.key();
+    let vault_authority = accounts.vault_authority.key();
+    let shares_mint = accounts.shares_mint.key();
+    let mint_seeds: &[&[&[u8]]] = &[&[
+        SHARES_MINT_SEED.as_bytes(),
+        vault_authority.as_ref(),
+        &[bumps.shares_mint],
+    ]];
+    let vlt_auth_seeds: &[&[&[u8]]] = &[&[
+        VAULT_AUTHORITY_SEED.as_bytes(),
+        vault.as_ref(),
+        &[bumps.vault_authority],
+    ]];
+
+    // Token-2022 reallocates the mint when writing the metadata, fund it upfront
+    let (space, metadata_space) = if is_token_2022 {
+        let metadata = TokenMetadata {
+            update_authority: OptionalNonZeroPubkey::try_from(Some(vault_authority))?,
+            mint: shares_mint,
+            name: name.to_string(),
+            symbol: symbol.to_string(),
+            uri: uri.to_string(),
+            additional_metadata: vec![],
+        };
+        let mut extensions = vec![ExtensionType::MetadataPointer];
+        if soulbound {
+            extensions.push(ExtensionType::NonTransferable);
+        }
+        if hook_mode != 0 {
+            extensions.push(ExtensionType::TransferHook);
+        }
+        (
+            ExtensionType::try_calculate_account_len::<MintState>(&extensions)?,
+            metadata.tlv_size_of()?,
+        )
+    } else {
+        (MintState::LEN, 0)
+    };
+    let lamports = Rent::get()?.minimum_balance(space + metadata_space);
+    create_shares_mint_account(accounts, mint_seeds, lamports, space as u64)?;
+
+    let token_program = accounts.shares_token_program.to_account_info();
+    let mint = accounts.shares_mint.to_account_info();
+    if is_token_2022 {
+        let pointer_accounts = MetadataPointerInitialize {
+            token_program_id: token_program.clone(),
+            mint: mint.clone(),
+        };
+        metadata_pointer_initialize(
+            CpiContext::new(token_program.clone(), pointer_accounts),
+            Some(vault_authority),
+            Some(shares_mint),
+        )?;
+    }
+    if soulbound {
+        let non_transferable_accounts = NonTransferableMintInitialize {
+            token_program_id: token_program.clone(),
+            mint: mint.clone(),
+        };
+        non_transferable_mint_initialize(CpiContext::new(
+            token_program.clone(),
+            non_transferable_accounts,
+        ))?;
+    }
+    if hook_mode != 0 {
+        let hook_accounts = TransferHookInitialize {
+            token_program_id: token_program.clone(),
+            mint: mint.clone(),
+        };
+        transfer_hook_initialize(
+            CpiContext::new(token_program.clone(), hook_accounts),
+            Some(vault_authority),
+            Some(shares_transfer_hook::ID),
+        )?;
+    }
+
+    initialize_mint2(
+        CpiContext::new(
+            token_program.clone(),
+            InitializeMint2 { mint: mint.clone() },
+        ),
+        accounts.base_asset_mint.decimals,
+        &vault_authority,
+        Some(&vault_authority),
+    )?;
+
+    match (
+        accounts.shares_metadata.as_ref(),
+        accounts.token_metadata_program.as_ref(),
+    ) {
+        (Some(shares_metadata), Some(token_metadata_program)) => {
+            create_metadata_account(
+                token_metadata_program,
+                shares_metadata,
+                &mint,
+                &accounts.vault_authority.to_account_info(),
+                &accounts.admin.to_account_info(),
+                &accounts.system_program.to_account_info(),
+                name,
+                symbol,
+                uri,
+                vlt_auth_seeds,
+            )?;
+        }
+        _ => {
+            let metadata_accounts = TokenMetadataInitialize {
+                program_id: token_program.clone(),
+                metadata: mint.clone(),
+                update_authority: accounts.vault_authority.to_account_info(),
+                mint_authority: accounts.vault_authority.to_account_info(),
+                mint,
+            };
+            token_metadata_initialize(
+                CpiContext::new_with_signer(token_program, metadata_accounts, vlt_auth_seeds),
+                name.to_string(),
+                symbol.to_string(),
+                uri.to_string(),
+            )?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Creates the shares mint account owned by the shares token program. The address of the
+/// next vault's shares mint is predictable, so it may already hold lamports: like Anchor
+/// `init`, such an account is topped up to `lamports`, then allocated and assigned.
+fn create_shares_mint_account(
+    accounts: &Initialize,
+    mint_seeds: &[&[&[u8]]],
+    lamports: u64,
+    space: u64,
+) -> Result<()> {
+    let system_program = accounts.system_program.to_account_info();
+    let shares_mint = accounts.shares_mint.to_account_info();
+    let owner = accounts.shares_token_program.key();
+
+    let current_lamports = shares_mint.lamports();
+    if current_lamports == 0 {
+        let create_accounts = CreateAccount {
+            from: accounts.admin.to_account_info(),
+            to: shares_mint,
+        };
+        return create_account(
+            CpiContext::new_with_signer(system_program, create_accounts, mint_seeds),
+            lamports,
+            space,
+            &owner,
+        );
+    }
+
+    let missing_lamports = lamports.saturating_sub(current_lamports);
+    if missing_lamports > 0 {
+        let transfer_accounts = Transfer {
+            from: accounts.admin.to_account_info(),
+            to: shares_mint.clone(),
+        };
+        transfer(
+            CpiContext::new(system_program.clone(), transfer_accounts),
+            missing_lamports,
+        )?;
+    }
+    let allocate_accounts = Allocate {
+        account_to_allocate: shares_mint.clone(),
+    };
+    allocate(
+        CpiContext::new_with_signer(system_program.clone(), allocate_accounts, mint_seeds),
+        space,
+    )?;
+    let assign_accounts = Assign {
+        account_to_assign: shares_mint,
+    };
+    assign(
+        CpiContext::new_with_signer(system_program, assign_accounts, mint_seeds),
+        &owner,
+    )
+}
+
+/// Creates the extra account metas of the shares transfer hook, signed by the vault
+/// authority, so Token-2022 resolves the vault list entries on every transfer.
+fn init_transfer_hook(accounts: &Initialize, bumps: &InitializeBumps, mode: u8) -> Result<()> {
+    let (Some(hook_program), Some(extra_account_meta_list), Some(hook_config)) = (
+        accounts.transfer_hook_program.as_ref(),
+        accounts.extra_account_meta_list.as_ref(),
+        accounts.hook_config.as_ref(),
+    ) else {
+        return err!(Errors::InvalidTransferHook);
+    };
+
+    let vault = accounts.vault.key();
+    let vlt_auth_seeds: &[&[&[u8]]] = &[&[
+        VAULT_AUTHORITY_SEED.as_bytes(),
+        vault.as_ref(),
+        &[bumps.vault_authority],
+    ]];
+    let hook_accounts = InitializeExtraAccountMetaList {
+        payer: accounts.admin.to_account_info(),
+        vault_authority: accounts.vault_authority.to_account_info(),
+        shares_mint: accounts.shares_mint.to_account_info(),
+        extra_account_meta_list: extra_account_meta_list.to_account_info(),
+        hook_config: hook_config.to_account_info(),
+        system_program: accounts.system_program.to_account_info(),
+    };
+    shares_transfer_hook::cpi::initialize_extra_account_meta_list(
+        CpiContext::new_with_signer(
+            hook_program.to_account_info(),
+            hook_accounts,
+            vlt_auth_seeds,
+        ),
+        vault,
+        mode,
+    )
+}
+
 #[event]
 pub struct InitializeEvent {
     pub vault: Pubkey,
     pub admin: Pubkey,
     pub shares_mint: Pubkey,
     pub base_asset_mint: Pubkey,
+    pub vault_index: u64,
+    pub name: String,
+    pub symbol: String,
+    pub uri: String,
+    pub allowed_mint_extensions: u16,
+    pub soulbound_shares: bool,
+    pub transfer_hook_mode: u8,
 }
@@ -0,0 +1,111 @@
+use crate::constant::{BASKET_AUTHORITY_SEED, BASKET_SEED, GLOBAL_CONFIG_SEED, SHARES_MINT_SEED};
+use crate::state::{Basket, GlobalConfig};
+use crate::util::{Errors, BPS_DENOMINATOR};
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenInterface};
+
+/// Initialize basket accounts:
+/// - admin: basket admin, pays for the accounts
+/// - global_config: protocol config PDA (global pause)
+/// - basket: basket PDA `[BASKET_SEED, admin, basket_id]`
+/// - basket_authority: basket authority PDA
+/// - shares_mint: basket shares mint PDA, created
+/// - token_program: token program of the shares and every basket asset
+/// - system_program
+#[derive(Accounts)]
+#[instruction(basket_id: u64)]
+pub struct InitializeBasket<'info> {
+    #[account(mut)]
+    admin: Signer<'info>,
+    #[account(
+        seeds = [GLOBAL_CONFIG_SEED.as_bytes()],
+        bump = global_config.bump
+    )]
+    global_config: Box<Account<'info, GlobalConfig>>,
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + Basket::MAX_SIZE,
+        seeds = [BASKET_SEED.as_bytes(), admin.key().as_ref(), &basket_id.to_le_bytes()],
+        bump
+    )]
+    basket: Box<Account<'info, Basket>>,
+    /// CHECK: PDA used only as signing authority
+    #[account(
+        seeds = [BASKET_AUTHORITY_SEED.as_bytes(), basket.key().as_ref()],
+        bump
+    )]
+    basket_authority: AccountInfo<'info>,
+    #[account(
+        init,
+        payer = admin,
+        seeds = [SHARES_MINT_SEED.as_bytes(), basket_authority.key().as_ref()],
+        bump,
+        mint::decimals = Basket::VALUE_DECIMALS,
+        mint::authority = basket_authority,
+        mint::token_program = token_program
+    )]
+    shares_mint: Box<InterfaceAccount<'info, Mint>>,
+    token_program: Interface<'info, TokenInterface>,
+    system_program: Program<'info, System>,
+}
+
+/// Creates an empty basket, assets are whitelisted with `add_basket_asset`.
+/// Shares are denominated in the unit of account, with `Basket::VALUE_DECIMALS`.
+/// - basket_id: index of the basket among the admin's baskets
+/// - exit_fee_bps: fee on single-asset exits, stays in the basket
+/// - max_price_age: max age in seconds of the oracle prices valuing the basket
+/// - max_conf_bps: max oracle confidence interval, in bps of the price
+pub fn handle(
+    ctx: Context<InitializeBasket>,
+    basket_id: u64,
+    exit_fee_bps: u16,
+    max_price_age: u32,
+    max_conf_bps: u16,
+) -> Result<()> {
+    msg!(
+        "initializing basket {} shares_mint: {}",
+        ctx.accounts.basket.key(),
+        ctx.accounts.shares_mint.key()
+    );
+
+    require!(!ctx.accounts.global_config.paused, Errors::ProtocolPaused);
+    require!(
+        (exit_fee_bps as u64) < BPS_DENOMINATOR,
+        Errors::InvalidExitFee
+    );
+
+    let basket = &mut ctx.accounts.basket;
+    basket.admin = ctx.accounts.admin.key();
+    basket.basket_authority = ctx.accounts.basket_authority.key();
+    basket.shares_mint = ctx.accounts.shares_mint.key();
+    basket.token_program = ctx.accounts.token_program.key();
+    basket.basket_id = basket_id;
+    basket.exit_fee_bps = exit_fee_bps;
+    basket.max_price_age = max_price_age;
+    basket.max_conf_bps = max_conf_bps;
+    basket.assets = Vec::new();
+    basket.bump = ctx.bumps.basket;
+    basket.basket_authority_bump = ctx.bumps.basket_authority;
+
+    emit!(InitializeBasketEvent {
+        admin: basket.admin,
+        basket: basket.key(),
+        shares_mint: basket.shares_mint,
+        exit_fee_bps,
+        max_price_age,
+        max_conf_bps,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct InitializeBasketEvent {
+    pub admin: Pubkey,
+    pub basket: Pubkey,
+    pub shares_mint: Pubkey,
+    pub exit_fee_bps: u16,
+    pub max_price_age: u32,
+    pub max_conf_bps: u16,
+}
@@ -0,0 +1,10 @@
+use crate::instructions::deposit::{self, Deposit, EnterAmount};
+use anchor_lang::prelude::*;
+
+/// Mint an exact amount of shares, pulling the base assets they are worth (rounded up).
+/// Uses the `Deposit` accounts and is subject to the same pauses and caps.
+/// - shares: amount of shares to mint
+/// - proof: merkle proof of the signer in the vault allowlist (empty for open vaults)
+pub fn handle(ctx: Context<Deposit>, shares: u64, proof: Vec<[u8; 32]>) -> Result<()> {
+    deposit::enter(ctx, EnterAmount::Shares(shares), &proof, false)
+}
@@ -1,8 +1,97 @@
 pub mod initialize;
 pub use initialize::*;
 
+pub mod global_config;
+pub use global_config::*;
+
 pub mod deposit;
 pub use deposit::*;
 
+pub mod mint;
+
+pub mod deposit_sol;
+
 pub mod allocate;
 pub use allocate::*;
+
+pub mod allocation_target;
+pub use allocation_target::*;
+
+pub mod deallocate;
+pub use deallocate::*;
+
+pub mod write_down;
+pub use write_down::*;
+
+pub mod withdraw;
+pub use withdraw::*;
+
+pub mod redeem;
+
+pub mod withdraw_sol;
+
+pub mod request_redeem;
+pub use request_redeem::*;
+
+pub mod fulfill_redeem;
+pub use fulfill_redeem::*;
+
+pub mod claim_redeem;
+pub use claim_redeem::*;
+
+pub mod queue_deposit;
+pub use queue_deposit::*;
+
+pub mod queue_redeem;
+pub use queue_redeem::*;
+
+pub mod close_epoch;
+pub use close_epoch::*;
+
+pub mod claim_epoch;
+pub use claim_epoch::*;
+
+pub mod collect_protocol_fees;
+pub use collect_protocol_fees::*;
+
+pub mod shutdown;
+pub use shutdown::*;
+
+pub mod admin;
+pub use admin::*;
+
+pub mod update_shares_uri;
+pub use update_shares_uri::*;
+
+pub mod blocklist;
+pub use blocklist::*;
+
+pub mod transfer_allowlist;
+pub use transfer_allowlist::*;
+
+pub mod price_feed;
+pub use price_feed::*;
+
+pub mod initialize_basket;
+pub use initialize_basket::*;
+
+pub mod add_basket_asset;
+pub use add_basket_asset::*;
+
+pub mod deposit_basket;
+pub use deposit_basket::*;
+
+pub mod redeem_basket;
+pub use redeem_basket::*;
+
+pub mod redeem_basket_asset;
+pub use redeem_basket_asset::*;
+
+pub mod update_nav;
+pub use update_nav::*;
+
+pub mod reset_circuit_breaker;
+pub use reset_circuit_breaker::*;
+
+pub mod views;
+pub use views::*;
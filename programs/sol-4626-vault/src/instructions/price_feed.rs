@@ -0,0 +1,83 @@
+use crate::constant::PRICE_FEED_SEED;
+use crate::state::PriceFeed;
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint;
+
+/// Initialize price feed accounts:
+/// - authority: feed authority, pays for the feed
+/// - mint: priced token mint
+/// - price_feed: price feed PDA for (authority, mint), created
+/// - system_program
+#[derive(Accounts)]
+pub struct InitializePriceFeed<'info> {
+    #[account(mut)]
+    authority: Signer<'info>,
+    mint: InterfaceAccount<'info, Mint>,
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + PriceFeed::MAX_SIZE,
+        seeds = [PRICE_FEED_SEED.as_bytes(), authority.key().as_ref(), mint.key().as_ref()],
+        bump
+    )]
+    price_feed: Account<'info, PriceFeed>,
+    system_program: Program<'info, System>,
+}
+
+/// Update price feed accounts:
+/// - authority: feed authority
+/// - price_feed: price feed PDA
+#[derive(Accounts)]
+pub struct UpdatePriceFeed<'info> {
+    authority: Signer<'info>,
+    #[account(mut, has_one = authority)]
+    price_feed: Account<'info, PriceFeed>,
+}
+
+/// Creates an empty price feed for `mint`, priced once the authority pushes a price.
+pub fn create(ctx: Context<InitializePriceFeed>) -> Result<()> {
+    msg!(
+        "initializing price feed {} for mint {}",
+        ctx.accounts.price_feed.key(),
+        ctx.accounts.mint.key()
+    );
+
+    let feed = &mut ctx.accounts.price_feed;
+    feed.authority = ctx.accounts.authority.key();
+    feed.mint = ctx.accounts.mint.key();
+    feed.bump = ctx.bumps.price_feed;
+
+    Ok(())
+}
+
+/// Pushes the price of one whole token, `price * 10^expo` units of account give or take
+/// `conf` (same exponent).
+pub fn push(ctx: Context<UpdatePriceFeed>, price: i64, conf: u64, expo: i32) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let feed = &mut ctx.accounts.price_feed;
+    feed.price = price;
+    feed.conf = conf;
+    feed.expo = expo;
+    feed.publish_time = now;
+
+    emit!(PriceFeedUpdateEvent {
+        price_feed: feed.key(),
+        mint: feed.mint,
+        price,
+        conf,
+        expo,
+        publish_time: now,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct PriceFeedUpdateEvent {
+    pub price_feed: Pubkey,
+    pub mint: Pubkey,
+    pub price: i64,
+    pub conf: u64,
+    pub expo: i32,
+    pub publish_time: i64,
+}
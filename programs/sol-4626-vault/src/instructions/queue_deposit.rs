@@ -0,0 +1,216 @@
+use crate::constant::{
+    BLOCKED_ADDRESS_SEED, EPOCH_RECEIPT_SEED, EPOCH_SEED, GLOBAL_CONFIG_SEED, USER_POSITION_SEED,
+};
+use crate::instructions::deposit::admit;
+use crate::state::{Epoch, EpochReceipt, GlobalConfig, UserPosition, Vault};
+use crate::util::{transfer_fee, Errors};
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{
+    transfer_checked, Mint, TokenAccount, TokenInterface, TransferChecked,
+};
+
+/// Queue deposit accounts:
+/// - signer: depositor
+/// - vault: vault PDA
+/// - global_config: protocol config PDA (global pause)
+/// - base_asset_mint: base token asset mint
+/// - base_asset_ata: depositor's ATA holding base assets
+/// - vault_base_asset_ata: vault's ATA for base assets
+/// - epoch: current epoch PDA (created by the first queued entry)
+/// - epoch_receipt: depositor's receipt for the current epoch
+/// - user_position: depositor's position PDA (created on first deposit)
+/// - blocked_address: blocked address PDA of the signer, must be empty
+/// - instructions_sysvar: used to read the deposit voucher when the vault requires one
+/// - token_program
+/// - system_program
+#[derive(Accounts)]
+pub struct QueueDeposit<'info> {
+    #[account(mut)]
+    signer: Signer<'info>,
+    #[account(mut,
+        has_one = base_asset_mint,
+        has_one = vault_base_asset_ata,
+        has_one = token_program
+    )]
+    vault: AccountLoader<'info, Vault>,
+    #[account(
+        seeds = [GLOBAL_CONFIG_SEED.as_bytes()],
+        bump = global_config.bump
+    )]
+    global_config: Box<Account<'info, GlobalConfig>>,
+    #[account()]
+    base_asset_mint: Box<InterfaceAccount<'info, Mint>>,
+    #[account(
+        mut,
+        associated_token::mint = base_asset_mint,
+        associated_token::authority = signer,
+        associated_token::token_program = token_program
+    )]
+    base_asset_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(mut)]
+    vault_base_asset_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        init_if_needed,
+        payer = signer,
+        space = 8 + Epoch::MAX_SIZE,
+        seeds = [EPOCH_SEED.as_bytes(), vault.key().as_ref(), &vault.load()?.current_epoch.to_le_bytes()],
+        bump
+    )]
+    epoch: Box<Account<'info, Epoch>>,
+    #[account(
+        init_if_needed,
+        payer = signer,
+        space = 8 + EpochReceipt::MAX_SIZE,
+        seeds = [EPOCH_RECEIPT_SEED.as_bytes(), epoch.key().as_ref(), signer.key().as_ref()],
+        bump
+    )]
+    epoch_receipt: Box<Account<'info, EpochReceipt>>,
+    #[account(
+        init_if_needed,
+        payer = signer,
+        space = 8 + UserPosition::MAX_SIZE,
+        seeds = [USER_POSITION_SEED.as_bytes(), vault.key().as_ref(), signer.key().as_ref()],
+        bump
+    )]
+    user_position: Box<Account<'info, UserPosition>>,
+    /// CHECK: blocked address PDA of the signer (checked by seeds), must not exist
+    #[account(
+        seeds = [BLOCKED_ADDRESS_SEED.as_bytes(), vault.key().as_ref(), signer.key().as_ref()],
+        bump
+    )]
+    blocked_address: UncheckedAccount<'info>,
+    /// CHECK: instructions sysvar (address checked)
+    #[account(address = solana_sdk_ids::sysvar::instructions::ID)]
+    instructions_sysvar: AccountInfo<'info>,
+    token_program: Interface<'info, TokenInterface>,
+    system_program: Program<'info, System>,
+}
+
+/// Queues base assets into the current epoch. Shares are minted when the epoch
+/// is closed, at the single price of that close, and claimed with `claim_epoch`.
+/// Same admission rules as `deposit` (blocklist, pause, allowlist, caps, voucher).
+/// Unavailable with soulbound shares, which can't be claimed out of the escrow.
+/// - amount: amount of base asset to queue
+/// - proof: merkle proof of the signer in the vault allowlist (empty for open vaults)
+pub fn handle(ctx: Context<QueueDeposit>, amount: u64, proof: Vec<[u8; 32]>) -> Result<()> {
+    let mut vlt = ctx.accounts.vault.load_mut()?;
+
+    require!(vlt.epochs_enabled(), Errors::EpochsDisabled);
+    require!(
+        !vlt.soulbound_shares.is_true(),
+        Errors::SharesNonTransferable
+    );
+    require!(
+        ctx.accounts.base_asset_ata.amount >= amount,
+        Errors::InsufficientBaseAssetBalance
+    );
+    require!(amount > 0, Errors::ZeroDeposit);
+    require!(
+        ctx.accounts.blocked_address.data_is_empty(),
+        Errors::AddressBlocked
+    );
+
+    let epoch = &mut ctx.accounts.epoch;
+    if epoch.vault == Pubkey::default() {
+        epoch.vault = ctx.accounts.vault.key();
+        epoch.id = vlt.current_epoch;
+        epoch.bump = ctx.bumps.epoch;
+    }
+
+    let position = &mut ctx.accounts.user_position;
+    position.init_if_needed(
+        ctx.accounts.vault.key(),
+        ctx.accounts.signer.key(),
+        ctx.bumps.user_position,
+    );
+    let total_assets = vlt
+        .total_base_assets
+        .checked_add(epoch.queued_assets)
+        .ok_or(Errors::MathOverflow)?;
+    let net_amount = amount
+        .checked_sub(transfer_fee(
+            &ctx.accounts.base_asset_mint.to_account_info(),
+            amount,
+        )?)
+        .ok_or(Errors::MathOverflow)?;
+    admit(
+        &ctx.accounts.global_config,
+        &vlt,
+        &ctx.accounts.vault.key(),
+        &ctx.accounts.signer.key(),
+        position,
+        total_assets,
+        net_amount,
+        &proof,
+        &ctx.accounts.instructions_sysvar,
+    )?;
+
+    msg!(
+        "queueing {} base assets into epoch {} of vault {}",
+        amount,
+        epoch.id,
+        ctx.accounts.vault.key()
+    );
+
+    // Transfer base assets from user to vault
+    let transfer_accounts = TransferChecked {
+        mint: ctx.accounts.base_asset_mint.to_account_info(),
+        from: ctx.accounts.base_asset_ata.to_account_info(),
+        to: ctx.accounts.vault_base_asset_ata.to_account_info(),
+        authority: ctx.accounts.signer.to_account_info(),
+    };
+    let transfer_ctx = CpiContext::new(
+        ctx.accounts.token_program.to_account_info(),
+        transfer_accounts,
+    );
+    let balance_before = ctx.accounts.vault_base_asset_ata.amount;
+    transfer_checked(transfer_ctx, amount, ctx.accounts.base_asset_mint.decimals)?;
+
+    // Only the amount received net of any transfer fee is queued
+    ctx.accounts.vault_base_asset_ata.reload()?;
+    let received = ctx
+        .accounts
+        .vault_base_asset_ata
+        .amount
+        .checked_sub(balance_before)
+        .ok_or(Errors::MathOverflow)?;
+    require!(received > 0, Errors::ZeroDeposit);
+
+    // Queued assets stay out of the share price and idle liquidity until settlement
+    epoch.queued_assets = epoch
+        .queued_assets
+        .checked_add(received)
+        .ok_or(Errors::MathOverflow)?;
+    vlt.reserved_assets = vlt
+        .reserved_assets
+        .checked_add(received)
+        .ok_or(Errors::MathOverflow)?;
+
+    let receipt = &mut ctx.accounts.epoch_receipt;
+    receipt.init_if_needed(
+        epoch.key(),
+        ctx.accounts.signer.key(),
+        ctx.bumps.epoch_receipt,
+    );
+    receipt.queued_assets = receipt
+        .queued_assets
+        .checked_add(received)
+        .ok_or(Errors::MathOverflow)?;
+
+    emit!(QueueDepositEvent {
+        vault: ctx.accounts.vault.key(),
+        epoch: epoch.id,
+        depositor: ctx.accounts.signer.key(),
+        base_asset_amount: received,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct QueueDepositEvent {
+    pub vault: Pubkey,
+    pub epoch: u64,
+    pub depositor: Pubkey,
+    pub base_asset_amount: u64,
+}
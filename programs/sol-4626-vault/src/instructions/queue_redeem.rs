@@ -0,0 +1,184 @@
+use crate::constant::{BLOCKED_ADDRESS_SEED, EPOCH_RECEIPT_SEED, EPOCH_SEED, USER_POSITION_SEED};
+use crate::state::{Epoch, EpochReceipt, UserPosition, Vault};
+use crate::util::{transfer_shares, Errors};
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface, TransferChecked};
+
+/// Queue redeem accounts:
+/// - signer: shares owner
+/// - vault: vault PDA
+/// - vault_authority: vault authority PDA, owner of the shares escrow
+/// - shares_mint: vault's shares mint
+/// - shares_ata: owner's ATA for shares
+/// - shares_escrow: vault authority's ATA for shares, holds queued shares
+/// - epoch: current epoch PDA (created by the first queued entry)
+/// - epoch_receipt: owner's receipt for the current epoch
+/// - user_position: owner's position PDA, must be out of its lockup
+/// - blocked_address: blocked address PDA of the owner, must be empty
+/// - shares_token_program
+/// - associated_token_program
+/// - system_program
+#[derive(Accounts)]
+pub struct QueueRedeem<'info> {
+    #[account(mut)]
+    signer: Signer<'info>,
+    #[account(
+        has_one = shares_mint,
+        has_one = vault_authority,
+        has_one = shares_token_program
+    )]
+    vault: AccountLoader<'info, Vault>,
+    /// CHECK: vault authority checked (has_one)
+    vault_authority: AccountInfo<'info>,
+    #[account()]
+    shares_mint: Box<InterfaceAccount<'info, Mint>>,
+    #[account(
+        mut,
+        associated_token::mint = shares_mint,
+        associated_token::authority = signer,
+        associated_token::token_program = shares_token_program
+    )]
+    shares_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        init_if_needed,
+        payer = signer,
+        associated_token::mint = shares_mint,
+        associated_token::authority = vault_authority,
+        associated_token::token_program = shares_token_program
+    )]
+    shares_escrow: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        init_if_needed,
+        payer = signer,
+        space = 8 + Epoch::MAX_SIZE,
+        seeds = [EPOCH_SEED.as_bytes(), vault.key().as_ref(), &vault.load()?.current_epoch.to_le_bytes()],
+        bump
+    )]
+    epoch: Box<Account<'info, Epoch>>,
+    #[account(
+        init_if_needed,
+        payer = signer,
+        space = 8 + EpochReceipt::MAX_SIZE,
+        seeds = [EPOCH_RECEIPT_SEED.as_bytes(), epoch.key().as_ref(), signer.key().as_ref()],
+        bump
+    )]
+    epoch_receipt: Box<Account<'info, EpochReceipt>>,
+    #[account(
+        init_if_needed,
+        payer = signer,
+        space = 8 + UserPosition::MAX_SIZE,
+        seeds = [USER_POSITION_SEED.as_bytes(), vault.key().as_ref(), signer.key().as_ref()],
+        bump
+    )]
+    user_position: Box<Account<'info, UserPosition>>,
+    /// CHECK: blocked address PDA of the signer (checked by seeds), must not exist
+    #[account(
+        seeds = [BLOCKED_ADDRESS_SEED.as_bytes(), vault.key().as_ref(), signer.key().as_ref()],
+        bump
+    )]
+    blocked_address: UncheckedAccount<'info>,
+    shares_token_program: Interface<'info, TokenInterface>,
+    associated_token_program: Program<'info, AssociatedToken>,
+    system_program: Program<'info, System>,
+}
+
+/// Escrows shares into the current epoch. Base assets are reserved when the epoch
+/// is closed, at the single price of that close, and claimed with `claim_epoch`.
+/// Keeps working while the vault is shut down. Unavailable with soulbound shares.
+/// With a shares transfer hook, the remaining accounts carry the hook's extra accounts.
+/// - shares: amount of shares to queue
+pub fn handle<'info>(
+    ctx: Context<'_, '_, '_, 'info, QueueRedeem<'info>>,
+    shares: u64,
+) -> Result<()> {
+    let vlt = ctx.accounts.vault.load()?;
+
+    require!(vlt.epochs_enabled(), Errors::EpochsDisabled);
+    require!(
+        !vlt.soulbound_shares.is_true(),
+        Errors::SharesNonTransferable
+    );
+    require!(shares > 0, Errors::InvalidAmount);
+    require!(
+        ctx.accounts.shares_ata.amount >= shares,
+        Errors::InsufficientSharesBalance
+    );
+    require!(
+        ctx.accounts.blocked_address.data_is_empty(),
+        Errors::AddressBlocked
+    );
+
+    // No penalty can be charged on a deferred exit, so it must wait out the lockup
+    let position = &mut ctx.accounts.user_position;
+    position.init_if_needed(
+        ctx.accounts.vault.key(),
+        ctx.accounts.signer.key(),
+        ctx.bumps.user_position,
+    );
+    require!(
+        vlt.exit_penalty_bps(position.last_deposit_at, Clock::get()?.unix_timestamp)? == 0,
+        Errors::PositionLocked
+    );
+
+    let epoch = &mut ctx.accounts.epoch;
+    if epoch.vault == Pubkey::default() {
+        epoch.vault = ctx.accounts.vault.key();
+        epoch.id = vlt.current_epoch;
+        epoch.bump = ctx.bumps.epoch;
+    }
+
+    msg!(
+        "queueing {} shares into epoch {} of vault {}",
+        shares,
+        epoch.id,
+        ctx.accounts.vault.key()
+    );
+
+    // Escrow owner shares
+    let transfer_accounts = TransferChecked {
+        mint: ctx.accounts.shares_mint.to_account_info(),
+        from: ctx.accounts.shares_ata.to_account_info(),
+        to: ctx.accounts.shares_escrow.to_account_info(),
+        authority: ctx.accounts.signer.to_account_info(),
+    };
+    let transfer_ctx = CpiContext::new(
+        ctx.accounts.shares_token_program.to_account_info(),
+        transfer_accounts,
+    )
+    .with_remaining_accounts(ctx.remaining_accounts.to_vec());
+    transfer_shares(transfer_ctx, shares, ctx.accounts.shares_mint.decimals)?;
+
+    epoch.queued_shares = epoch
+        .queued_shares
+        .checked_add(shares)
+        .ok_or(Errors::MathOverflow)?;
+
+    let receipt = &mut ctx.accounts.epoch_receipt;
+    receipt.init_if_needed(
+        epoch.key(),
+        ctx.accounts.signer.key(),
+        ctx.bumps.epoch_receipt,
+    );
+    receipt.queued_shares = receipt
+        .queued_shares
+        .checked_add(shares)
+        .ok_or(Errors::MathOverflow)?;
+
+    emit!(QueueRedeemEvent {
+        vault: ctx.accounts.vault.key(),
+        epoch: epoch.id,
+        owner: ctx.accounts.signer.key(),
+        shares,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct QueueRedeemEvent {
+    pub vault: Pubkey,
+    pub epoch: u64,
+    pub owner: Pubkey,
+    pub shares: u64,
+}
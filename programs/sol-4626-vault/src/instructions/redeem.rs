@@ -0,0 +1,9 @@
+use crate::instructions::withdraw::{self, ExitAmount, Withdraw};
+use anchor_lang::prelude::*;
+
+/// Redeem an exact amount of shares for the base assets they are worth (rounded down).
+/// Uses the `Withdraw` accounts and keeps working while the vault is shut down.
+/// - shares: amount of shares to burn
+pub fn handle(ctx: Context<Withdraw>, shares: u64) -> Result<()> {
+    withdraw::exit(ctx, ExitAmount::Shares(shares), false)
+}
@@ -0,0 +1,132 @@
+use crate::constant::BASKET_AUTHORITY_SEED;
+use crate::state::Basket;
+use crate::util::{convert_to_assets, Errors};
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{
+    burn, transfer_checked, Burn, Mint, TokenAccount, TokenInterface, TransferChecked,
+};
+
+/// Redeem basket accounts:
+/// - signer: shares owner
+/// - basket: basket PDA
+/// - basket_authority: basket authority PDA
+/// - shares_mint: basket shares mint
+/// - shares_ata: owner's ATA for shares
+/// - token_program
+///
+/// Remaining accounts, per basket asset in order: the asset mint, the basket's ATA,
+/// then the owner's token account receiving the asset.
+#[derive(Accounts)]
+pub struct RedeemBasket<'info> {
+    signer: Signer<'info>,
+    #[account(
+        mut,
+        has_one = basket_authority,
+        has_one = shares_mint,
+        has_one = token_program
+    )]
+    basket: Box<Account<'info, Basket>>,
+    /// CHECK: basket authority checked (has_one)
+    basket_authority: AccountInfo<'info>,
+    #[account(mut)]
+    shares_mint: Box<InterfaceAccount<'info, Mint>>,
+    #[account(
+        mut,
+        associated_token::mint = shares_mint,
+        associated_token::authority = signer,
+        associated_token::token_program = token_program
+    )]
+    shares_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+    token_program: Interface<'info, TokenInterface>,
+}
+
+/// Redeem shares in kind: burns them for their pro-rata share of every tracked asset
+/// balance (rounded down), no price needed and no fee charged. Works while the protocol
+/// is paused.
+/// - shares: amount of shares to burn
+pub fn handle<'info>(
+    ctx: Context<'_, '_, '_, 'info, RedeemBasket<'info>>,
+    shares: u64,
+) -> Result<()> {
+    let basket = &mut ctx.accounts.basket;
+    let accounts = ctx.remaining_accounts;
+    require!(shares > 0, Errors::InvalidAmount);
+    require!(
+        ctx.accounts.shares_ata.amount >= shares,
+        Errors::InsufficientSharesBalance
+    );
+    require!(
+        accounts.len() == basket.assets.len() * 3,
+        Errors::InvalidBasketAccounts
+    );
+
+    // Pro-rata amounts against the supply before the burn
+    let total_shares = ctx.accounts.shares_mint.supply;
+    let mut amounts = Vec::with_capacity(basket.assets.len());
+    for (asset, triple) in basket.assets.iter().zip(accounts.chunks(3)) {
+        require_keys_eq!(triple[0].key(), asset.mint, Errors::InvalidBasketAccounts);
+        require_keys_eq!(triple[1].key(), asset.ata, Errors::InvalidBasketAccounts);
+        amounts.push(convert_to_assets(shares, asset.balance, total_shares)?);
+    }
+
+    msg!(
+        "redeeming {} shares in kind from basket {}",
+        shares,
+        basket.key()
+    );
+
+    let burn_accounts = Burn {
+        mint: ctx.accounts.shares_mint.to_account_info(),
+        from: ctx.accounts.shares_ata.to_account_info(),
+        authority: ctx.accounts.signer.to_account_info(),
+    };
+    let burn_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), burn_accounts);
+    burn(burn_ctx, shares)?;
+
+    let basket_address = basket.key();
+    let basket_auth_seeds: &[&[&[u8]]] = &[&[
+        BASKET_AUTHORITY_SEED.as_bytes(),
+        basket_address.as_ref(),
+        &[basket.basket_authority_bump],
+    ]];
+    for ((asset, triple), amount) in basket.assets.iter().zip(accounts.chunks(3)).zip(&amounts) {
+        if *amount == 0 {
+            continue;
+        }
+        let transfer_accounts = TransferChecked {
+            mint: triple[0].clone(),
+            from: triple[1].clone(),
+            to: triple[2].clone(),
+            authority: ctx.accounts.basket_authority.to_account_info(),
+        };
+        let transfer_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            transfer_accounts,
+            basket_auth_seeds,
+        );
+        transfer_checked(transfer_ctx, *amount, asset.decimals)?;
+    }
+    for (asset, amount) in basket.assets.iter_mut().zip(&amounts) {
+        asset.balance = asset
+            .balance
+            .checked_sub(*amount)
+            .ok_or(Errors::MathOverflow)?;
+    }
+
+    emit!(BasketRedeemEvent {
+        owner: ctx.accounts.signer.key(),
+        basket: basket_address,
+        shares_burned: shares,
+        amounts,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct BasketRedeemEvent {
+    pub owner: Pubkey,
+    pub basket: Pubkey,
+    pub shares_burned: u64,
+    pub amounts: Vec<u64>,
+}
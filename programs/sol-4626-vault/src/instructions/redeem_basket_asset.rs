@@ -0,0 +1,157 @@
+use crate::constant::BASKET_AUTHORITY_SEED;
+use crate::state::Basket;
+use crate::util::{convert_to_assets, Errors, BPS_DENOMINATOR};
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token_interface::{
+    burn, transfer_checked, Burn, Mint, TokenAccount, TokenInterface, TransferChecked,
+};
+
+/// Redeem basket asset accounts:
+/// - signer: shares owner
+/// - basket: basket PDA
+/// - basket_authority: basket authority PDA
+/// - shares_mint: basket shares mint
+/// - shares_ata: owner's ATA for shares
+/// - asset_mint: asset paid out
+/// - basket_asset_ata: basket's ATA for the asset
+/// - asset_ata: owner's ATA receiving the asset
+/// - token_program
+/// - associated_token_program
+/// - system_program
+///
+/// Remaining accounts, per basket asset in order: its price oracle.
+#[derive(Accounts)]
+pub struct RedeemBasketAsset<'info> {
+    #[account(mut)]
+    signer: Signer<'info>,
+    #[account(
+        mut,
+        has_one = basket_authority,
+        has_one = shares_mint,
+        has_one = token_program
+    )]
+    basket: Box<Account<'info, Basket>>,
+    /// CHECK: basket authority checked (has_one)
+    basket_authority: AccountInfo<'info>,
+    #[account(mut)]
+    shares_mint: Box<InterfaceAccount<'info, Mint>>,
+    #[account(
+        mut,
+        associated_token::mint = shares_mint,
+        associated_token::authority = signer,
+        associated_token::token_program = token_program
+    )]
+    shares_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+    asset_mint: Box<InterfaceAccount<'info, Mint>>,
+    #[account(mut)]
+    basket_asset_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        init_if_needed,
+        payer = signer,
+        associated_token::mint = asset_mint,
+        associated_token::authority = signer,
+        associated_token::token_program = token_program
+    )]
+    asset_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+    token_program: Interface<'info, TokenInterface>,
+    associated_token_program: Program<'info, AssociatedToken>,
+    system_program: Program<'info, System>,
+}
+
+/// Redeem shares for a single basket asset: burns them for their value against the whole
+/// basket value, paid in the chosen asset at its price minus the basket exit fee, which
+/// stays in the basket for the remaining holders. Works while the protocol is paused.
+/// - shares: amount of shares to burn
+pub fn handle(ctx: Context<RedeemBasketAsset>, shares: u64) -> Result<()> {
+    let basket = &mut ctx.accounts.basket;
+    require!(shares > 0, Errors::InvalidAmount);
+    require!(
+        ctx.accounts.shares_ata.amount >= shares,
+        Errors::InsufficientSharesBalance
+    );
+
+    let index = basket.asset_index(&ctx.accounts.asset_mint.key())?;
+    let asset = basket.assets[index];
+    require_keys_eq!(
+        ctx.accounts.basket_asset_ata.key(),
+        asset.ata,
+        Errors::InvalidBasketAccounts
+    );
+
+    let valuation = basket.valuation(ctx.remaining_accounts, Clock::get()?.unix_timestamp)?;
+    let value = convert_to_assets(
+        shares,
+        valuation.total_value,
+        ctx.accounts.shares_mint.supply,
+    )?;
+    let exit_fee = ((value as u128)
+        .checked_mul(basket.exit_fee_bps as u128)
+        .ok_or(Errors::MathOverflow)?
+        / BPS_DENOMINATOR as u128) as u64;
+    let amount = valuation.prices[index].amount_for(
+        value - exit_fee,
+        asset.decimals,
+        Basket::VALUE_DECIMALS,
+    )?;
+    require!(amount > 0, Errors::InvalidAmount);
+    require!(asset.balance >= amount, Errors::InsufficientVaultLiquidity);
+
+    msg!(
+        "redeeming {} shares for {} of {} from basket {} (exit fee {})",
+        shares,
+        amount,
+        asset.mint,
+        basket.key(),
+        exit_fee
+    );
+
+    let burn_accounts = Burn {
+        mint: ctx.accounts.shares_mint.to_account_info(),
+        from: ctx.accounts.shares_ata.to_account_info(),
+        authority: ctx.accounts.signer.to_account_info(),
+    };
+    let burn_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), burn_accounts);
+    burn(burn_ctx, shares)?;
+
+    let basket_address = basket.key();
+    let basket_auth_seeds: &[&[&[u8]]] = &[&[
+        BASKET_AUTHORITY_SEED.as_bytes(),
+        basket_address.as_ref(),
+        &[basket.basket_authority_bump],
+    ]];
+    let transfer_accounts = TransferChecked {
+        mint: ctx.accounts.asset_mint.to_account_info(),
+        from: ctx.accounts.basket_asset_ata.to_account_info(),
+        to: ctx.accounts.asset_ata.to_account_info(),
+        authority: ctx.accounts.basket_authority.to_account_info(),
+    };
+    let transfer_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        transfer_accounts,
+        basket_auth_seeds,
+    );
+    transfer_checked(transfer_ctx, amount, asset.decimals)?;
+    basket.assets[index].balance = asset.balance - amount;
+
+    emit!(BasketRedeemAssetEvent {
+        owner: ctx.accounts.signer.key(),
+        basket: basket_address,
+        mint: asset.mint,
+        shares_burned: shares,
+        amount,
+        exit_fee_value: exit_fee,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct BasketRedeemAssetEvent {
+    pub owner: Pubkey,
+    pub basket: Pubkey,
+    pub mint: Pubkey,
+    pub shares_burned: u64,
+    pub amount: u64,
+    pub exit_fee_value: u64,
+}
@@ -0,0 +1,167 @@
+use crate::constant::{BLOCKED_ADDRESS_SEED, REDEEM_REQUEST_SEED, USER_POSITION_SEED};
+use crate::state::{RedeemRequest, UserPosition, Vault};
+use crate::util::{transfer_shares, Errors};
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface, TransferChecked};
+
+/// Request redeem accounts:
+/// - signer: shares owner
+/// - shares_mint: vault's shares mint
+/// - vault: vault PDA
+/// - vault_authority: vault authority PDA, owner of the shares escrow
+/// - shares_ata: owner's ATA for shares
+/// - shares_escrow: vault authority's ATA for shares, holds requested shares
+/// - redeem_request: owner's redeem request PDA (created on first request)
+/// - user_position: owner's position PDA, must be out of its lockup
+/// - blocked_address: blocked address PDA of the owner, must be empty
+/// - shares_token_program
+/// - associated_token_program
+/// - system_program
+#[derive(Accounts)]
+pub struct RequestRedeem<'info> {
+    #[account(mut)]
+    signer: Signer<'info>,
+    #[account()]
+    shares_mint: Box<InterfaceAccount<'info, Mint>>,
+    #[account(mut,
+        has_one = shares_mint,
+        has_one = vault_authority,
+        has_one = shares_token_program
+    )]
+    vault: AccountLoader<'info, Vault>,
+    /// CHECK: vault authority checked (has_one)
+    vault_authority: AccountInfo<'info>,
+    #[account(
+        mut,
+        associated_token::mint = shares_mint,
+        associated_token::authority = signer,
+        associated_token::token_program = shares_token_program
+    )]
+    shares_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        init_if_needed,
+        payer = signer,
+        associated_token::mint = shares_mint,
+        associated_token::authority = vault_authority,
+        associated_token::token_program = shares_token_program
+    )]
+    shares_escrow: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        init_if_needed,
+        payer = signer,
+        space = 8 + RedeemRequest::MAX_SIZE,
+        seeds = [REDEEM_REQUEST_SEED.as_bytes(), vault.key().as_ref(), signer.key().as_ref()],
+        bump
+    )]
+    redeem_request: Box<Account<'info, RedeemRequest>>,
+    #[account(
+        init_if_needed,
+        payer = signer,
+        space = 8 + UserPosition::MAX_SIZE,
+        seeds = [USER_POSITION_SEED.as_bytes(), vault.key().as_ref(), signer.key().as_ref()],
+        bump
+    )]
+    user_position: Box<Account<'info, UserPosition>>,
+    /// CHECK: blocked address PDA of the signer (checked by seeds), must not exist
+    #[account(
+        seeds = [BLOCKED_ADDRESS_SEED.as_bytes(), vault.key().as_ref(), signer.key().as_ref()],
+        bump
+    )]
+    blocked_address: UncheckedAccount<'info>,
+    shares_token_program: Interface<'info, TokenInterface>,
+    associated_token_program: Program<'info, AssociatedToken>,
+    system_program: Program<'info, System>,
+}
+
+/// Escrows shares into the vault and records a pending redeem request.
+/// The price is locked in later, when the allocator fulfills the request
+/// after freeing liquidity. Keeps working while the vault is shut down.
+/// Unavailable with soulbound shares, which can't move into the escrow.
+/// With a shares transfer hook, the remaining accounts carry the hook's extra accounts.
+/// - shares: amount of shares to redeem
+pub fn handle<'info>(
+    ctx: Context<'_, '_, '_, 'info, RequestRedeem<'info>>,
+    shares: u64,
+) -> Result<()> {
+    let mut vlt = ctx.accounts.vault.load_mut()?;
+
+    require!(
+        !vlt.soulbound_shares.is_true(),
+        Errors::SharesNonTransferable
+    );
+    require!(shares > 0, Errors::InvalidAmount);
+    require!(
+        ctx.accounts.shares_ata.amount >= shares,
+        Errors::InsufficientSharesBalance
+    );
+    require!(
+        ctx.accounts.blocked_address.data_is_empty(),
+        Errors::AddressBlocked
+    );
+
+    // No penalty can be charged on a deferred exit, so it must wait out the lockup
+    let position = &mut ctx.accounts.user_position;
+    position.init_if_needed(
+        ctx.accounts.vault.key(),
+        ctx.accounts.signer.key(),
+        ctx.bumps.user_position,
+    );
+    require!(
+        vlt.exit_penalty_bps(position.last_deposit_at, Clock::get()?.unix_timestamp)? == 0,
+        Errors::PositionLocked
+    );
+
+    msg!(
+        "requesting redeem of {} shares from vault {}",
+        shares,
+        ctx.accounts.vault.key()
+    );
+
+    // Escrow owner shares
+    let transfer_accounts = TransferChecked {
+        mint: ctx.accounts.shares_mint.to_account_info(),
+        from: ctx.accounts.shares_ata.to_account_info(),
+        to: ctx.accounts.shares_escrow.to_account_info(),
+        authority: ctx.accounts.signer.to_account_info(),
+    };
+    let transfer_ctx = CpiContext::new(
+        ctx.accounts.shares_token_program.to_account_info(),
+        transfer_accounts,
+    )
+    .with_remaining_accounts(ctx.remaining_accounts.to_vec());
+    transfer_shares(transfer_ctx, shares, ctx.accounts.shares_mint.decimals)?;
+
+    // Record request
+    let request = &mut ctx.accounts.redeem_request;
+    if request.owner == Pubkey::default() {
+        request.vault = ctx.accounts.vault.key();
+        request.owner = ctx.accounts.signer.key();
+        request.bump = ctx.bumps.redeem_request;
+    }
+    request.pending_shares = request
+        .pending_shares
+        .checked_add(shares)
+        .ok_or(Errors::MathOverflow)?;
+    request.requested_at = Clock::get()?.unix_timestamp;
+
+    vlt.pending_redeem_shares = vlt
+        .pending_redeem_shares
+        .checked_add(shares)
+        .ok_or(Errors::MathOverflow)?;
+
+    emit!(RedeemRequestEvent {
+        vault: ctx.accounts.vault.key(),
+        owner: ctx.accounts.signer.key(),
+        shares,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct RedeemRequestEvent {
+    pub vault: Pubkey,
+    pub owner: Pubkey,
+    pub shares: u64,
+}
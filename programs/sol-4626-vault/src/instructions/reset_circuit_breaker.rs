@@ -0,0 +1,45 @@
+use crate::state::Vault;
+use crate::util::{BoolU8, Errors};
+use anchor_lang::prelude::*;
+
+/// Reset circuit breaker accounts:
+/// - guardian: vault guardian
+/// - vault: vault PDA
+#[derive(Accounts)]
+pub struct ResetCircuitBreaker<'info> {
+    guardian: Signer<'info>,
+    #[account(mut, has_one = guardian)]
+    vault: AccountLoader<'info, Vault>,
+}
+
+/// Resumes deposits and withdrawals paused by a tripped circuit breaker, once the guardian
+/// has checked the share price move. The breaker window restarts at the next report.
+pub fn handle(ctx: Context<ResetCircuitBreaker>) -> Result<()> {
+    let mut vlt = ctx.accounts.vault.load_mut()?;
+
+    require!(
+        vlt.breaker_tripped.is_true(),
+        Errors::CircuitBreakerNotTripped
+    );
+
+    msg!(
+        "resetting circuit breaker of vault {}",
+        ctx.accounts.vault.key()
+    );
+
+    vlt.breaker_tripped = BoolU8::new(false);
+    vlt.breaker_ref_at = 0;
+
+    emit!(ResetCircuitBreakerEvent {
+        vault: ctx.accounts.vault.key(),
+        guardian: ctx.accounts.guardian.key(),
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct ResetCircuitBreakerEvent {
+    pub vault: Pubkey,
+    pub guardian: Pubkey,
+}
@@ -0,0 +1,48 @@
+use crate::state::Vault;
+use crate::util::{BoolU8, Errors};
+use anchor_lang::prelude::*;
+
+/// Shutdown accounts:
+/// - authority: vault admin or guardian
+/// - vault: vault PDA
+#[derive(Accounts)]
+pub struct Shutdown<'info> {
+    authority: Signer<'info>,
+    #[account(mut)]
+    vault: AccountLoader<'info, Vault>,
+}
+
+/// Puts the vault into irreversible emergency shutdown.
+/// Deposits and allocations are rejected from now on, withdrawals, redemptions
+/// and deallocations keep working so depositors can always exit.
+pub fn handle(ctx: Context<Shutdown>) -> Result<()> {
+    let mut vlt = ctx.accounts.vault.load_mut()?;
+    let authority = ctx.accounts.authority.key();
+
+    require!(
+        authority == vlt.admin || authority == vlt.guardian,
+        Errors::Unauthorized
+    );
+    require!(!vlt.shutdown.is_true(), Errors::AlreadyShutdown);
+
+    msg!(
+        "shutting down vault {} by {}",
+        ctx.accounts.vault.key(),
+        authority
+    );
+
+    vlt.shutdown = BoolU8::new(true);
+
+    emit!(ShutdownEvent {
+        vault: ctx.accounts.vault.key(),
+        authority,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct ShutdownEvent {
+    pub vault: Pubkey,
+    pub authority: Pubkey,
+}
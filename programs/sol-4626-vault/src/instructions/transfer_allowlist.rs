@@ -0,0 +1,92 @@
+use crate::constant::ALLOWED_ADDRESS_SEED;
+use crate::state::{AllowedAddress, Vault};
+use anchor_lang::prelude::*;
+
+/// Allow address accounts:
+/// - compliance_authority: vault compliance role
+/// - vault: vault PDA
+/// - allowed_address: allowed address PDA for (vault, wallet), created
+/// - system_program
+#[derive(Accounts)]
+#[instruction(wallet: Pubkey)]
+pub struct AllowAddress<'info> {
+    #[account(mut)]
+    compliance_authority: Signer<'info>,
+    #[account(has_one = compliance_authority)]
+    vault: AccountLoader<'info, Vault>,
+    #[account(
+        init,
+        payer = compliance_authority,
+        space = 8 + AllowedAddress::MAX_SIZE,
+        seeds = [ALLOWED_ADDRESS_SEED.as_bytes(), vault.key().as_ref(), wallet.as_ref()],
+        bump
+    )]
+    allowed_address: Account<'info, AllowedAddress>,
+    system_program: Program<'info, System>,
+}
+
+/// Disallow address accounts:
+/// - compliance_authority: vault compliance role, receives the rent back
+/// - vault: vault PDA
+/// - allowed_address: allowed address PDA, closed
+#[derive(Accounts)]
+pub struct DisallowAddress<'info> {
+    #[account(mut)]
+    compliance_authority: Signer<'info>,
+    #[account(has_one = compliance_authority)]
+    vault: AccountLoader<'info, Vault>,
+    #[account(
+        mut,
+        close = compliance_authority,
+        has_one = vault
+    )]
+    allowed_address: Account<'info, AllowedAddress>,
+}
+
+/// Lets `wallet` send and receive shares of a vault whose transfer hook runs in allowlist mode.
+pub fn allow(ctx: Context<AllowAddress>, wallet: Pubkey) -> Result<()> {
+    msg!("allowing {} on vault {}", wallet, ctx.accounts.vault.key());
+
+    let allowed = &mut ctx.accounts.allowed_address;
+    allowed.vault = ctx.accounts.vault.key();
+    allowed.wallet = wallet;
+    allowed.allowed_at = Clock::get()?.unix_timestamp;
+    allowed.bump = ctx.bumps.allowed_address;
+
+    emit!(AllowAddressEvent {
+        vault: ctx.accounts.vault.key(),
+        wallet,
+    });
+
+    Ok(())
+}
+
+/// Revokes a wallet's share transfer approval by closing its allowed address PDA.
+pub fn disallow(ctx: Context<DisallowAddress>) -> Result<()> {
+    let wallet = ctx.accounts.allowed_address.wallet;
+
+    msg!(
+        "disallowing {} on vault {}",
+        wallet,
+        ctx.accounts.vault.key()
+    );
+
+    emit!(DisallowAddressEvent {
+        vault: ctx.accounts.vault.key(),
+        wallet,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct AllowAddressEvent {
+    pub vault: Pubkey,
+    pub wallet: Pubkey,
+}
+
+#[event]
+pub struct DisallowAddressEvent {
+    pub vault: Pubkey,
+    pub wallet: Pubkey,
+}
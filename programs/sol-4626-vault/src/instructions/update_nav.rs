@@ -0,0 +1,153 @@
+use crate::constant::NAV_UPDATE_SEED;
+use crate::state::{NavUpdate, PriceHistory, Vault};
+use crate::util::{load_preceding_ed25519_message, share_price, BoolU8, Errors, NavReport};
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint;
+
+/// Update NAV accounts:
+/// - submitter: relays the signed report, pays for the nav update record
+/// - vault: vault PDA
+/// - price_history: share price history PDA of the vault
+/// - shares_mint: vault's shares mint
+/// - nav_update: nav update PDA for the report sequence, created
+/// - instructions_sysvar: used to read the signed report
+/// - system_program
+#[derive(Accounts)]
+#[instruction(nav: u64, previous_nav: u64, timestamp: i64, sequence: u64)]
+pub struct UpdateNav<'info> {
+    #[account(mut)]
+    submitter: Signer<'info>,
+    #[account(mut, has_one = shares_mint)]
+    vault: AccountLoader<'info, Vault>,
+    #[account(mut, has_one = vault)]
+    price_history: AccountLoader<'info, PriceHistory>,
+    shares_mint: Box<InterfaceAccount<'info, Mint>>,
+    #[account(
+        init,
+        payer = submitter,
+        space = 8 + NavUpdate::MAX_SIZE,
+        seeds = [NAV_UPDATE_SEED.as_bytes(), vault.key().as_ref(), &sequence.to_le_bytes()],
+        bump
+    )]
+    nav_update: Account<'info, NavUpdate>,
+    /// CHECK: instructions sysvar (address checked)
+    #[account(address = solana_sdk_ids::sysvar::instructions::ID)]
+    instructions_sysvar: AccountInfo<'info>,
+    system_program: Program<'info, System>,
+}
+
+/// Sets the vault's total assets to `nav`, as reported by the valuation signer for
+/// strategies living off chain. The report is signed over (vault, nav, previous_nav,
+/// timestamp, sequence) in an Ed25519 program instruction right before this one, anyone
+/// can relay it.
+///
+/// The report values the whole vault, idle assets included, and replaces total assets.
+/// It only applies over the `previous_nav` total assets it was signed against, so any flow
+/// landing between signing and submission voids it, and only within the vault's
+/// `nav_max_age` of its timestamp. Each applied report is kept in its own `NavUpdate` account.
+///
+/// Share price moves beyond the circuit breaker thresholds emit `CircuitBreakerTripped`,
+/// then either fail, or apply the report and pause deposits and withdrawals until the
+/// guardian resets the breaker.
+pub fn handle(
+    ctx: Context<UpdateNav>,
+    nav: u64,
+    previous_nav: u64,
+    timestamp: i64,
+    sequence: u64,
+) -> Result<()> {
+    let mut vlt = ctx.accounts.vault.load_mut()?;
+    let vault = ctx.accounts.vault.key();
+
+    require!(vlt.accepts_nav_reports(), Errors::NavReportsDisabled);
+
+    let message =
+        load_preceding_ed25519_message(&ctx.accounts.instructions_sysvar, &vlt.valuation_signer)?;
+    let report = NavReport::try_from_slice(&message).map_err(|_| Errors::InvalidNavReport)?;
+    require!(
+        report
+            == NavReport {
+                vault,
+                nav,
+                previous_nav,
+                timestamp,
+                sequence,
+            },
+        Errors::InvalidNavReport
+    );
+
+    let now = Clock::get()?.unix_timestamp;
+    report.verify(&vault, &vlt, now)?;
+
+    let total_shares = ctx.accounts.shares_mint.supply;
+    let price = share_price(previous_nav, total_shares);
+    let new_price = share_price(nav, total_shares);
+    if vlt.breaker_breached(price, new_price, now) {
+        emit!(CircuitBreakerTripped {
+            vault,
+            sequence,
+            price,
+            new_price,
+            reference_price: vlt.breaker_ref_price,
+            paused: vlt.breaker_pause.is_true(),
+        });
+        require!(vlt.breaker_pause.is_true(), Errors::CircuitBreakerTripped);
+        vlt.breaker_tripped = BoolU8::new(true);
+    }
+
+    msg!(
+        "updating NAV of vault {} from {} to {} (report {})",
+        vault,
+        previous_nav,
+        nav,
+        sequence
+    );
+
+    vlt.total_base_assets = nav;
+    vlt.nav_sequence = sequence;
+    vlt.nav_updated_at = timestamp;
+    ctx.accounts
+        .price_history
+        .load_mut()?
+        .record(now, nav, total_shares);
+
+    ctx.accounts.nav_update.set_inner(NavUpdate {
+        vault,
+        sequence,
+        nav,
+        previous_nav,
+        timestamp,
+        submitted_at: now,
+        submitter: ctx.accounts.submitter.key(),
+        bump: ctx.bumps.nav_update,
+    });
+
+    emit!(NavUpdateEvent {
+        vault,
+        sequence,
+        nav,
+        previous_nav,
+        timestamp,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct NavUpdateEvent {
+    pub vault: Pubkey,
+    pub sequence: u64,
+    pub nav: u64,
+    pub previous_nav: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct CircuitBreakerTripped {
+    pub vault: Pubkey,
+    pub sequence: u64,
+    pub price: u64,
+    pub new_price: u64,
+    pub reference_price: u64,
+    pub paused: bool,
+}
@@ -0,0 +1,160 @@
+use crate::constant::{TOKEN_METADATA_PROGRAM_ID, TOKEN_METADATA_SEED, VAULT_AUTHORITY_SEED};
+use crate::state::Vault;
+use crate::util::{update_metadata_uri, Errors, SharesMetadata};
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+use anchor_spl::token_2022::spl_token_2022::extension::{
+    BaseStateWithExtensions, StateWithExtensions,
+};
+use anchor_spl::token_2022::spl_token_2022::state::Mint as MintState;
+use anchor_spl::token_interface::spl_token_metadata_interface::state::{Field, TokenMetadata};
+use anchor_spl::token_interface::{
+    token_metadata_update_field, TokenInterface, TokenMetadataUpdateField,
+};
+
+/// Update shares uri accounts:
+/// - admin: vault admin, tops up the Token-2022 mint rent when the metadata grows
+/// - vault: vault PDA
+/// - vault_authority: vault authority PDA, metadata update authority
+/// - shares_mint: vault's shares mint
+/// - shares_metadata: Metaplex metadata PDA of the shares mint, only for classic SPL Token
+///   shares mints
+/// - shares_token_program
+/// - system_program
+/// - token_metadata_program: Metaplex Token Metadata program, only for classic SPL Token
+///   shares mints
+#[derive(Accounts)]
+pub struct UpdateSharesUri<'info> {
+    #[account(mut)]
+    admin: Signer<'info>,
+    #[account(
+        has_one = admin,
+        has_one = vault_authority,
+        has_one = shares_mint,
+        has_one = shares_token_program
+    )]
+    vault: AccountLoader<'info, Vault>,
+    /// CHECK: vault authority checked (has_one)
+    vault_authority: AccountInfo<'info>,
+    /// CHECK: shares mint checked (has_one)
+    #[account(mut)]
+    shares_mint: AccountInfo<'info>,
+    /// CHECK: Metaplex metadata PDA (checked by seeds), owned by the token metadata program
+    #[account(
+        mut,
+        seeds = [
+            TOKEN_METADATA_SEED.as_bytes(),
+            TOKEN_METADATA_PROGRAM_ID.as_ref(),
+            shares_mint.key().as_ref()
+        ],
+        seeds::program = TOKEN_METADATA_PROGRAM_ID,
+        bump
+    )]
+    shares_metadata: Option<UncheckedAccount<'info>>,
+    shares_token_program: Interface<'info, TokenInterface>,
+    system_program: Program<'info, System>,
+    /// CHECK: Metaplex Token Metadata program (checked by address)
+    #[account(address = TOKEN_METADATA_PROGRAM_ID)]
+    token_metadata_program: Option<UncheckedAccount<'info>>,
+}
+
+/// Points the shares token metadata to a new off-chain JSON `uri`.
+pub fn handle(ctx: Context<UpdateSharesUri>, uri: String) -> Result<()> {
+    SharesMetadata::validate_uri(&uri)?;
+
+    msg!(
+        "updating shares uri of vault {} to {}",
+        ctx.accounts.vault.key(),
+        uri
+    );
+
+    let vlt_address = ctx.accounts.vault.key();
+    let vlt_auth_bump = ctx.accounts.vault.load()?.vault_authority_bump;
+    let vlt_auth_seeds: &[&[&[u8]]] = &[&[
+        VAULT_AUTHORITY_SEED.as_bytes(),
+        vlt_address.as_ref(),
+        &[vlt_auth_bump],
+    ]];
+    match (
+        ctx.accounts.shares_metadata.as_ref(),
+        ctx.accounts.token_metadata_program.as_ref(),
+    ) {
+        (Some(shares_metadata), Some(token_metadata_program)) => update_metadata_uri(
+            token_metadata_program,
+            shares_metadata,
+            &ctx.accounts.vault_authority,
+            &uri,
+            vlt_auth_seeds,
+        )?,
+        (None, None) => update_token_2022_uri(&ctx, &uri, vlt_auth_seeds)?,
+        _ => return err!(Errors::InvalidSharesMetadata),
+    }
+
+    emit!(UpdateSharesUriEvent {
+        vault: ctx.accounts.vault.key(),
+        shares_mint: ctx.accounts.shares_mint.key(),
+        uri,
+    });
+
+    Ok(())
+}
+
+/// Rewrites the uri in the token-metadata extension of a Token-2022 shares mint,
+/// first funding the rent of the resized mint.
+fn update_token_2022_uri(
+    ctx: &Context<UpdateSharesUri>,
+    uri: &str,
+    vlt_auth_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    let mint_info = ctx.accounts.shares_mint.to_account_info();
+    let new_len = {
+        let data = mint_info.try_borrow_data()?;
+        let mint = StateWithExtensions::<MintState>::unpack(&data)
+            .map_err(|_| Errors::InvalidSharesMetadata)?;
+        let mut metadata = mint
+            .get_variable_len_extension::<TokenMetadata>()
+            .map_err(|_| Errors::InvalidSharesMetadata)?;
+        let old_size = metadata.tlv_size_of()?;
+        metadata.update(Field::Uri, uri.to_string());
+        data.len() - old_size + metadata.tlv_size_of()?
+    };
+
+    let missing_rent = Rent::get()?
+        .minimum_balance(new_len)
+        .saturating_sub(mint_info.lamports());
+    if missing_rent > 0 {
+        let transfer_accounts = Transfer {
+            from: ctx.accounts.admin.to_account_info(),
+            to: mint_info.clone(),
+        };
+        transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                transfer_accounts,
+            ),
+            missing_rent,
+        )?;
+    }
+
+    let update_accounts = TokenMetadataUpdateField {
+        program_id: ctx.accounts.shares_token_program.to_account_info(),
+        metadata: mint_info,
+        update_authority: ctx.accounts.vault_authority.to_account_info(),
+    };
+    token_metadata_update_field(
+        CpiContext::new_with_signer(
+            ctx.accounts.shares_token_program.to_account_info(),
+            update_accounts,
+            vlt_auth_seeds,
+        ),
+        Field::Uri,
+        uri.to_string(),
+    )
+}
+
+#[event]
+pub struct UpdateSharesUriEvent {
+    pub vault: Pubkey,
+    pub shares_mint: Pubkey,
+    pub uri: String,
+}
@@ -0,0 +1,288 @@
+use crate::constant::GLOBAL_CONFIG_SEED;
+use crate::state::{GlobalConfig, PriceHistory, RedeemRequest, UserPosition, Vault};
+use crate::util::{
+    convert_to_assets, convert_to_assets_rounded_up, convert_to_shares,
+    convert_to_shares_rounded_up, exit_penalty,
+};
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount};
+
+/// Read-only vault accounts used by the preview/max views.
+/// Values are returned to the caller through return data.
+/// - vault: vault PDA
+/// - shares_mint: vault's shares mint
+/// - vault_base_asset_ata: vault's ATA for base assets (idle liquidity)
+/// - global_config: protocol config PDA (global pause)
+#[derive(Accounts)]
+pub struct VaultView<'info> {
+    #[account(
+        has_one = shares_mint,
+        has_one = vault_base_asset_ata
+    )]
+    vault: AccountLoader<'info, Vault>,
+    shares_mint: Box<InterfaceAccount<'info, Mint>>,
+    vault_base_asset_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        seeds = [GLOBAL_CONFIG_SEED.as_bytes()],
+        bump = global_config.bump
+    )]
+    global_config: Box<Account<'info, GlobalConfig>>,
+}
+
+/// Read-only vault accounts plus the shares account and position of the owner being queried.
+/// `user_position` is omitted for owners that never deposited (no lockup applies).
+#[derive(Accounts)]
+pub struct OwnerView<'info> {
+    #[account(
+        has_one = shares_mint,
+        has_one = vault_base_asset_ata
+    )]
+    vault: AccountLoader<'info, Vault>,
+    shares_mint: Box<InterfaceAccount<'info, Mint>>,
+    vault_base_asset_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(token::mint = shares_mint)]
+    owner_shares_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        has_one = vault,
+        constraint = user_position.owner == owner_shares_ata.owner
+    )]
+    user_position: Option<Account<'info, UserPosition>>,
+}
+
+/// Early-exit penalty currently charged to the owner, `None` while exits are locked.
+fn owner_exit_penalty_bps(
+    vlt: &Vault,
+    user_position: &Option<Account<UserPosition>>,
+    now: i64,
+) -> Option<u64> {
+    let Some(position) = user_position else {
+        return Some(0);
+    };
+
+    vlt.exit_penalty_bps(position.last_deposit_at, now).ok()
+}
+
+/// Read-only vault accounts plus the position of the depositor being queried.
+/// `user_position` is omitted for depositors that never deposited.
+#[derive(Accounts)]
+pub struct DepositorView<'info> {
+    #[account(has_one = shares_mint)]
+    vault: AccountLoader<'info, Vault>,
+    shares_mint: Box<InterfaceAccount<'info, Mint>>,
+    #[account(
+        seeds = [GLOBAL_CONFIG_SEED.as_bytes()],
+        bump = global_config.bump
+    )]
+    global_config: Box<Account<'info, GlobalConfig>>,
+    #[account(has_one = vault)]
+    user_position: Option<Account<'info, UserPosition>>,
+}
+
+/// Read-only vault accounts plus the position being queried.
+#[derive(Accounts)]
+pub struct PositionView<'info> {
+    #[account(has_one = shares_mint)]
+    vault: AccountLoader<'info, Vault>,
+    shares_mint: Box<InterfaceAccount<'info, Mint>>,
+    #[account(has_one = vault)]
+    user_position: Account<'info, UserPosition>,
+}
+
+/// Redeem request being queried.
+#[derive(Accounts)]
+pub struct RedeemRequestView<'info> {
+    redeem_request: Account<'info, RedeemRequest>,
+}
+
+/// Share price history being queried.
+#[derive(Accounts)]
+pub struct PriceHistoryView<'info> {
+    price_history: AccountLoader<'info, PriceHistory>,
+}
+
+/// Maximum base assets the depositor can still deposit (remaining cap headroom),
+/// zero while paused (globally or by the vault), shut down or settling in epochs.
+pub fn max_deposit(ctx: Context<DepositorView>) -> Result<u64> {
+    let vlt = ctx.accounts.vault.load()?;
+
+    if ctx.accounts.global_config.paused || !vlt.deposits_enabled() || vlt.epochs_enabled() {
+        return Ok(0);
+    }
+
+    let deposited_by_user = ctx
+        .accounts
+        .user_position
+        .as_ref()
+        .map_or(0, |position| position.deposited_assets);
+
+    Ok(vlt.deposit_headroom(deposited_by_user))
+}
+
+/// Maximum shares the depositor can still mint, zero while paused, shut down or settling
+/// in epochs.
+pub fn max_mint(ctx: Context<DepositorView>) -> Result<u64> {
+    let total_shares = ctx.accounts.shares_mint.supply;
+    let total_assets = ctx.accounts.vault.load()?.total_base_assets;
+    let headroom = max_deposit(ctx)?;
+
+    if headroom == u64::MAX {
+        return Ok(u64::MAX);
+    }
+
+    convert_to_shares(headroom, total_assets, total_shares)
+}
+
+/// Shares minted for a deposit of `amount` base assets, zero while paused, shut down or
+/// settling in epochs.
+pub fn preview_deposit(ctx: Context<VaultView>, amount: u64) -> Result<u64> {
+    let vlt = ctx.accounts.vault.load()?;
+
+    if ctx.accounts.global_config.paused || !vlt.deposits_enabled() || vlt.epochs_enabled() {
+        return Ok(0);
+    }
+
+    convert_to_shares(
+        amount,
+        vlt.total_base_assets,
+        ctx.accounts.shares_mint.supply,
+    )
+}
+
+/// Base assets pulled to mint `shares`, zero while paused, shut down or settling in epochs.
+pub fn preview_mint(ctx: Context<VaultView>, shares: u64) -> Result<u64> {
+    let vlt = ctx.accounts.vault.load()?;
+
+    if ctx.accounts.global_config.paused || !vlt.deposits_enabled() || vlt.epochs_enabled() {
+        return Ok(0);
+    }
+
+    convert_to_assets_rounded_up(
+        shares,
+        vlt.total_base_assets,
+        ctx.accounts.shares_mint.supply,
+    )
+}
+
+/// Shares burned to withdraw `amount` base assets, zero while settling in epochs.
+pub fn preview_withdraw(ctx: Context<VaultView>, amount: u64) -> Result<u64> {
+    let vlt = ctx.accounts.vault.load()?;
+
+    if vlt.epochs_enabled() {
+        return Ok(0);
+    }
+
+    convert_to_shares_rounded_up(
+        amount,
+        vlt.total_base_assets,
+        ctx.accounts.shares_mint.supply,
+    )
+}
+
+/// Base assets paid out for redeeming `shares`, zero while settling in epochs.
+pub fn preview_redeem(ctx: Context<VaultView>, shares: u64) -> Result<u64> {
+    let vlt = ctx.accounts.vault.load()?;
+
+    if vlt.epochs_enabled() {
+        return Ok(0);
+    }
+
+    convert_to_assets(
+        shares,
+        vlt.total_base_assets,
+        ctx.accounts.shares_mint.supply,
+    )
+}
+
+/// Maximum base assets the owner can withdraw net of any early-exit penalty, bounded by
+/// idle liquidity and the remaining outflow capacity of the rate limit.
+/// Zero while the owner is locked and early exits are rejected, the circuit breaker
+/// is tripped or the vault settles in epochs.
+pub fn max_withdraw(ctx: Context<OwnerView>) -> Result<u64> {
+    let vlt = ctx.accounts.vault.load()?;
+    let now = Clock::get()?.unix_timestamp;
+
+    if vlt.breaker_tripped.is_true() || vlt.epochs_enabled() {
+        return Ok(0);
+    }
+
+    let Some(penalty_bps) = owner_exit_penalty_bps(&vlt, &ctx.accounts.user_position, now) else {
+        return Ok(0);
+    };
+
+    let gross_assets = convert_to_assets(
+        ctx.accounts.owner_shares_ata.amount,
+        vlt.total_base_assets,
+        ctx.accounts.shares_mint.supply,
+    )?;
+    let owner_assets = gross_assets - exit_penalty(gross_assets, penalty_bps)?;
+
+    Ok(owner_assets
+        .min(vlt.idle_assets(ctx.accounts.vault_base_asset_ata.amount))
+        .min(vlt.outflow_capacity(now)))
+}
+
+/// Maximum shares the owner can redeem, bounded by idle liquidity and the remaining
+/// outflow capacity of the rate limit.
+/// Zero while the owner is locked and early exits are rejected, the circuit breaker
+/// is tripped or the vault settles in epochs.
+pub fn max_redeem(ctx: Context<OwnerView>) -> Result<u64> {
+    let vlt = ctx.accounts.vault.load()?;
+    let now = Clock::get()?.unix_timestamp;
+
+    if vlt.breaker_tripped.is_true()
+        || vlt.epochs_enabled()
+        || owner_exit_penalty_bps(&vlt, &ctx.accounts.user_position, now).is_none()
+    {
+        return Ok(0);
+    }
+
+    let idle_shares = convert_to_shares(
+        vlt.idle_assets(ctx.accounts.vault_base_asset_ata.amount)
+            .min(vlt.outflow_capacity(now)),
+        vlt.total_base_assets,
+        ctx.accounts.shares_mint.supply,
+    )?;
+
+    Ok(ctx.accounts.owner_shares_ata.amount.min(idle_shares))
+}
+
+/// Shares escrowed by the request and waiting for fulfillment.
+pub fn pending_redeem_request(ctx: Context<RedeemRequestView>) -> Result<u64> {
+    Ok(ctx.accounts.redeem_request.pending_shares)
+}
+
+/// Base assets locked in by the request and ready to claim.
+pub fn claimable_redeem_request(ctx: Context<RedeemRequestView>) -> Result<u64> {
+    Ok(ctx.accounts.redeem_request.claimable_assets)
+}
+
+/// Unrealised PnL of the position at the current share price: value of the shares in
+/// its cost basis minus what was paid for them. Shares received by transfer are not
+/// part of the cost basis.
+pub fn unrealised_pnl(ctx: Context<PositionView>) -> Result<i64> {
+    let vlt = ctx.accounts.vault.load()?;
+    let position = &ctx.accounts.user_position;
+
+    let value = convert_to_assets(
+        position.cost_basis_shares,
+        vlt.total_base_assets,
+        ctx.accounts.shares_mint.supply,
+    )?;
+
+    Ok(position.unrealised_pnl(value))
+}
+
+/// Weighted-average entry price of the position in base assets per share,
+/// scaled by `PRICE_SCALE`.
+pub fn avg_entry_price(ctx: Context<PositionView>) -> Result<u64> {
+    ctx.accounts.user_position.avg_entry_price()
+}
+
+/// Time-weighted average share price over the last `window` seconds, in base assets per
+/// share scaled by `PRICE_SCALE`. Errors unless the history covers the whole window.
+pub fn share_price_twap(ctx: Context<PriceHistoryView>, window: i64) -> Result<u64> {
+    ctx.accounts
+        .price_history
+        .load()?
+        .twap(Clock::get()?.unix_timestamp, window)
+}
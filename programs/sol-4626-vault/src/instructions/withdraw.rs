@@ -0,0 +1,264 @@
+use crate::constant::{BLOCKED_ADDRESS_SEED, USER_POSITION_SEED, VAULT_AUTHORITY_SEED};
+use crate::state::{PriceHistory, UserPosition, Vault};
+use crate::util::{
+    convert_to_assets, convert_to_shares_rounded_up, exit_penalty, gross_up_for_penalty,
+    gross_up_for_transfer_fee, is_native_mint, transfer_fee, unwrap_sol, Errors,
+};
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token_interface::{
+    burn, transfer_checked, Burn, Mint, TokenAccount, TokenInterface, TransferChecked,
+};
+
+/// Withdraw accounts (shared by `withdraw` and `redeem`):
+/// - signer: shares owner
+/// - shares_mint: vault's shares mint
+/// - vault: vault PDA
+/// - price_history: share price history PDA of the vault
+/// - vault_authority: vault authority PDA
+/// - shares_ata: owner's ATA for shares
+/// - base_asset_mint: base token asset mint
+/// - base_asset_ata: owner's ATA receiving base assets (wSOL ATA closed by `withdraw_sol`)
+/// - vault_base_asset_ata: vault's ATA for base assets
+/// - user_position: owner's position PDA
+/// - blocked_address: blocked address PDA of the owner, must be empty
+/// - token_program
+/// - shares_token_program
+/// - associated_token_program
+/// - system_program
+#[derive(Accounts)]
+pub struct Withdraw<'info> {
+    #[account(mut)]
+    signer: Signer<'info>,
+    #[account(mut)]
+    shares_mint: Box<InterfaceAccount<'info, Mint>>,
+    #[account(mut,
+        has_one = shares_mint,
+        has_one = base_asset_mint,
+        has_one = vault_authority,
+        has_one = vault_base_asset_ata,
+        has_one = token_program,
+        has_one = shares_token_program
+    )]
+    vault: AccountLoader<'info, Vault>,
+    #[account(mut, has_one = vault)]
+    price_history: AccountLoader<'info, PriceHistory>,
+    /// CHECK: vault authority checked (has_one)
+    vault_authority: AccountInfo<'info>,
+    #[account(
+        mut,
+        associated_token::mint = shares_mint,
+        associated_token::authority = signer,
+        associated_token::token_program = shares_token_program
+    )]
+    shares_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account()]
+    base_asset_mint: Box<InterfaceAccount<'info, Mint>>,
+    #[account(
+        init_if_needed,
+        payer = signer,
+        associated_token::mint = base_asset_mint,
+        associated_token::authority = signer,
+        associated_token::token_program = token_program
+    )]
+    base_asset_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(mut)]
+    vault_base_asset_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        init_if_needed,
+        payer = signer,
+        space = 8 + UserPosition::MAX_SIZE,
+        seeds = [USER_POSITION_SEED.as_bytes(), vault.key().as_ref(), signer.key().as_ref()],
+        bump
+    )]
+    user_position: Box<Account<'info, UserPosition>>,
+    /// CHECK: blocked address PDA of the signer (checked by seeds), must not exist
+    #[account(
+        seeds = [BLOCKED_ADDRESS_SEED.as_bytes(), vault.key().as_ref(), signer.key().as_ref()],
+        bump
+    )]
+    blocked_address: UncheckedAccount<'info>,
+    token_program: Interface<'info, TokenInterface>,
+    shares_token_program: Interface<'info, TokenInterface>,
+    associated_token_program: Program<'info, AssociatedToken>,
+    system_program: Program<'info, System>,
+}
+
+/// Withdraw an exact amount of base assets, burning the shares it is worth (rounded up).
+/// Keeps working while the vault is shut down.
+/// - amount: amount of base asset to withdraw
+pub fn handle(ctx: Context<Withdraw>, amount: u64) -> Result<()> {
+    exit(ctx, ExitAmount::Assets(amount), false)
+}
+
+/// Exact side of an exit requested by the owner.
+pub(crate) enum ExitAmount {
+    /// `withdraw`: exact base assets out, shares rounded up
+    Assets(u64),
+    /// `redeem`: exact shares in, base assets rounded down
+    Shares(u64),
+}
+
+/// Shared exit path of `withdraw` and `redeem`: burn owner shares, release base assets.
+/// Exits inside the lockup are rejected, or charged the decaying early-exit penalty,
+/// which stays in the vault for the remaining holders, uncut by the protocol. Exits
+/// beyond the vault outflow limit are rejected, larger exits go through `request_redeem`.
+/// Rejected while the vault settles in epochs, exits then go through `queue_redeem`.
+/// With a transfer-fee base asset, `withdraw` sends enough for the owner to receive the exact
+/// amount (burning the shares it is worth), `redeem` owners receive the payout net of the fee.
+/// With `native`, the owner's wSOL ATA is closed after the payout, unwrapping it to lamports.
+pub(crate) fn exit(ctx: Context<Withdraw>, amount: ExitAmount, native: bool) -> Result<()> {
+    require!(
+        !native || is_native_mint(&ctx.accounts.base_asset_mint.key()),
+        Errors::NotNativeMint
+    );
+
+    let mut vlt = ctx.accounts.vault.load_mut()?;
+    require!(!vlt.epochs_enabled(), Errors::EpochsEnabled);
+    require!(
+        !vlt.breaker_tripped.is_true(),
+        Errors::CircuitBreakerTripped
+    );
+
+    let total_shares = ctx.accounts.shares_mint.supply;
+    let total_assets = vlt.total_base_assets;
+
+    let position = &mut ctx.accounts.user_position;
+    position.init_if_needed(
+        ctx.accounts.vault.key(),
+        ctx.accounts.signer.key(),
+        ctx.bumps.user_position,
+    );
+    let now = Clock::get()?.unix_timestamp;
+    let penalty_bps = vlt.exit_penalty_bps(position.last_deposit_at, now)?;
+
+    let base_asset_mint = ctx.accounts.base_asset_mint.to_account_info();
+    // assets: amount leaving the vault
+    let (assets, shares, penalty) = match amount {
+        ExitAmount::Assets(net_assets) => {
+            let assets = gross_up_for_transfer_fee(&base_asset_mint, net_assets)?;
+            let gross = gross_up_for_penalty(assets, penalty_bps)?;
+            (
+                assets,
+                convert_to_shares_rounded_up(gross, total_assets, total_shares)?,
+                gross.checked_sub(assets).ok_or(Errors::MathOverflow)?,
+            )
+        }
+        ExitAmount::Shares(shares) => {
+            let gross = convert_to_assets(shares, total_assets, total_shares)?;
+            let penalty = exit_penalty(gross, penalty_bps)?;
+            (
+                gross.checked_sub(penalty).ok_or(Errors::MathOverflow)?,
+                shares,
+                penalty,
+            )
+        }
+    };
+
+    let fee = transfer_fee(&base_asset_mint, assets)?;
+
+    require!(assets > 0 && shares > 0, Errors::InvalidAmount);
+    require!(
+        ctx.accounts.blocked_address.data_is_empty(),
+        Errors::AddressBlocked
+    );
+    require!(
+        ctx.accounts.shares_ata.amount >= shares,
+        Errors::InsufficientSharesBalance
+    );
+    require!(
+        vlt.idle_assets(ctx.accounts.vault_base_asset_ata.amount) >= assets,
+        Errors::InsufficientVaultLiquidity
+    );
+    vlt.record_outflow(assets, now)?;
+
+    msg!(
+        "withdrawing {} base assets for {} shares from vault {} (early-exit penalty {} transfer fee {})",
+        assets,
+        shares,
+        ctx.accounts.vault.key(),
+        penalty,
+        fee
+    );
+
+    // Burn owner shares
+    let burn_accounts = Burn {
+        mint: ctx.accounts.shares_mint.to_account_info(),
+        from: ctx.accounts.shares_ata.to_account_info(),
+        authority: ctx.accounts.signer.to_account_info(),
+    };
+    let burn_ctx = CpiContext::new(
+        ctx.accounts.shares_token_program.to_account_info(),
+        burn_accounts,
+    );
+    burn(burn_ctx, shares)?;
+
+    // Transfer base assets from vault to owner
+    let vlt_address = ctx.accounts.vault.key();
+    let transfer_accounts = TransferChecked {
+        mint: ctx.accounts.base_asset_mint.to_account_info(),
+        from: ctx.accounts.vault_base_asset_ata.to_account_info(),
+        to: ctx.accounts.base_asset_ata.to_account_info(),
+        authority: ctx.accounts.vault_authority.to_account_info(),
+    };
+    let vlt_auth_seeds: &[&[&[u8]]] = &[&[
+        VAULT_AUTHORITY_SEED.as_bytes(),
+        vlt_address.as_ref(),
+        &[vlt.vault_authority_bump],
+    ]];
+    let transfer_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        transfer_accounts,
+        vlt_auth_seeds,
+    );
+    transfer_checked(transfer_ctx, assets, ctx.accounts.base_asset_mint.decimals)?;
+
+    // Update vault state, the penalty stays in the vault
+    vlt.total_base_assets = vlt
+        .total_base_assets
+        .checked_sub(assets)
+        .ok_or(Errors::MathOverflow)?;
+
+    ctx.accounts.shares_mint.reload()?;
+    ctx.accounts.price_history.load_mut()?.record(
+        now,
+        vlt.total_base_assets,
+        ctx.accounts.shares_mint.supply,
+    );
+
+    // Free per-user cap headroom, shares received by transfer may exceed own deposits
+    let position = &mut ctx.accounts.user_position;
+    position.deposited_assets = position
+        .deposited_assets
+        .saturating_sub(assets.saturating_add(penalty));
+    let realised_pnl = position.record_exit(shares, assets.saturating_sub(fee))?;
+
+    emit!(WithdrawEvent {
+        owner: ctx.accounts.signer.key(),
+        base_asset_amount: assets,
+        shares_burned: shares,
+        early_exit_penalty: penalty,
+        transfer_fee: fee,
+        realised_pnl,
+    });
+
+    if native {
+        unwrap_sol(
+            &ctx.accounts.token_program.to_account_info(),
+            &ctx.accounts.signer.to_account_info(),
+            &ctx.accounts.base_asset_ata.to_account_info(),
+        )?;
+    }
+
+    Ok(())
+}
+
+#[event]
+pub struct WithdrawEvent {
+    pub owner: Pubkey,
+    pub base_asset_amount: u64,
+    pub shares_burned: u64,
+    pub early_exit_penalty: u64,
+    pub transfer_fee: u64,
+    pub realised_pnl: i64,
+}
@@ -0,0 +1,10 @@
+use crate::instructions::withdraw::{self, ExitAmount, Withdraw};
+use anchor_lang::prelude::*;
+
+/// Withdraw an exact amount of SOL from a wSOL vault, burning the shares it is worth
+/// (rounded up). The payout lands in the owner's wSOL ATA, which is then closed to the
+/// owner as lamports. Uses the `Withdraw` accounts and keeps working while the vault is shut down.
+/// - lamports: amount of SOL to withdraw
+pub fn handle(ctx: Context<Withdraw>, lamports: u64) -> Result<()> {
+    withdraw::exit(ctx, ExitAmount::Assets(lamports), true)
+}
@@ -0,0 +1,82 @@
+use crate::state::{AllocationTarget, PriceHistory, Vault};
+use crate::util::{share_price, Errors};
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint;
+
+/// Write down accounts:
+/// - admin: vault admin
+/// - vault: vault PDA
+/// - price_history: share price history PDA of the vault
+/// - shares_mint: vault's shares mint
+/// - allocation_target: allocation target PDA of the strategy taking the loss
+#[derive(Accounts)]
+pub struct WriteDown<'info> {
+    admin: Signer<'info>,
+    #[account(mut, has_one = admin, has_one = shares_mint)]
+    vault: AccountLoader<'info, Vault>,
+    #[account(mut, has_one = vault)]
+    price_history: AccountLoader<'info, PriceHistory>,
+    shares_mint: Box<InterfaceAccount<'info, Mint>>,
+    #[account(mut, has_one = vault)]
+    allocation_target: Box<Account<'info, AllocationTarget>>,
+}
+
+/// Recognises `amount` of a strategy's deployed assets as lost (e.g. after an exploit):
+/// reduces the strategy's debt and the vault's total assets by it, so the loss is shared
+/// by every shareholder through the share price. Shares already priced (claimable redeems
+/// and settled epochs) are not affected, pending requests settle at the lower price.
+///
+/// The vault has no profit unlocking: gains reach the share price as soon as they are
+/// reported, so there is no locked profit to absorb the loss first and all of it is
+/// recognised at once. The loss is not checked against the circuit breaker, whose window
+/// restarts at the next report so it does not trip on the written-down price.
+pub fn handle(ctx: Context<WriteDown>, amount: u64) -> Result<()> {
+    require!(amount > 0, Errors::InvalidAmount);
+
+    let mut vlt = ctx.accounts.vault.load_mut()?;
+    let target = &mut ctx.accounts.allocation_target;
+    target.write_down(amount)?;
+
+    let total_shares = ctx.accounts.shares_mint.supply;
+    let price = share_price(vlt.total_base_assets, total_shares);
+    vlt.total_base_assets = vlt
+        .total_base_assets
+        .checked_sub(amount)
+        .ok_or(Errors::MathOverflow)?;
+    vlt.breaker_ref_at = 0;
+    let new_price = share_price(vlt.total_base_assets, total_shares);
+
+    msg!(
+        "writing down {} base assets allocated to {} by vault {}",
+        amount,
+        target.target_ata,
+        ctx.accounts.vault.key()
+    );
+
+    ctx.accounts.price_history.load_mut()?.record(
+        Clock::get()?.unix_timestamp,
+        vlt.total_base_assets,
+        total_shares,
+    );
+
+    emit!(LossRecognised {
+        vault: ctx.accounts.vault.key(),
+        target_ata: target.target_ata,
+        amount,
+        remaining_debt: target.allocated_assets,
+        price,
+        new_price,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct LossRecognised {
+    pub vault: Pubkey,
+    pub target_ata: Pubkey,
+    pub amount: u64,
+    pub remaining_debt: u64,
+    pub price: u64,
+    pub new_price: u64,
+}
@@ -15,15 +15,349 @@ pub mod sol_4626_vault {
     use super::*;
     use crate::instructions::initialize;
 
-    pub fn initialize(ctx: Context<Initialize>) -> Result<()> {
-        initialize::handle(ctx)
+    pub fn initialize_global_config(
+        ctx: Context<InitializeGlobalConfig>,
+        fee_recipient: Pubkey,
+        protocol_fee_bps: u16,
+    ) -> Result<()> {
+        global_config::initialize_global_config(ctx, fee_recipient, protocol_fee_bps)
     }
 
-    pub fn deposit(ctx: Context<Deposit>, amount: u64) -> Result<()> {
-        deposit::handle(ctx, amount)
+    pub fn set_protocol_fee(
+        ctx: Context<ProtocolAdmin>,
+        fee_recipient: Pubkey,
+        protocol_fee_bps: u16,
+    ) -> Result<()> {
+        global_config::set_protocol_fee(ctx, fee_recipient, protocol_fee_bps)
+    }
+
+    pub fn set_global_pause(ctx: Context<ProtocolAdmin>, paused: bool) -> Result<()> {
+        global_config::set_global_pause(ctx, paused)
+    }
+
+    pub fn set_protocol_admin(ctx: Context<ProtocolAdmin>, protocol_admin: Pubkey) -> Result<()> {
+        global_config::set_protocol_admin(ctx, protocol_admin)
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn initialize(
+        ctx: Context<Initialize>,
+        vault_index: u64,
+        name: String,
+        symbol: String,
+        uri: String,
+        allowed_mint_extensions: u16,
+        soulbound_shares: bool,
+        transfer_hook_mode: u8,
+    ) -> Result<()> {
+        initialize::handle(
+            ctx,
+            vault_index,
+            name,
+            symbol,
+            uri,
+            allowed_mint_extensions,
+            soulbound_shares,
+            transfer_hook_mode,
+        )
+    }
+
+    pub fn deposit(ctx: Context<Deposit>, amount: u64, proof: Vec<[u8; 32]>) -> Result<()> {
+        deposit::handle(ctx, amount, proof)
+    }
+
+    pub fn mint(ctx: Context<Deposit>, shares: u64, proof: Vec<[u8; 32]>) -> Result<()> {
+        mint::handle(ctx, shares, proof)
+    }
+
+    pub fn deposit_sol(ctx: Context<Deposit>, lamports: u64, proof: Vec<[u8; 32]>) -> Result<()> {
+        deposit_sol::handle(ctx, lamports, proof)
     }
 
     pub fn allocate(ctx: Context<Allocate>, amount: u64) -> Result<()> {
         allocate::handle(ctx, amount)
     }
+
+    pub fn deallocate(ctx: Context<Deallocate>, amount: u64) -> Result<()> {
+        deallocate::handle(ctx, amount)
+    }
+
+    pub fn write_down(ctx: Context<WriteDown>, amount: u64) -> Result<()> {
+        write_down::handle(ctx, amount)
+    }
+
+    pub fn set_allocation_target(
+        ctx: Context<SetAllocationTarget>,
+        period_limit: u64,
+        period_seconds: i64,
+        active: bool,
+    ) -> Result<()> {
+        allocation_target::set_allocation_target(ctx, period_limit, period_seconds, active)
+    }
+
+    pub fn withdraw(ctx: Context<Withdraw>, amount: u64) -> Result<()> {
+        withdraw::handle(ctx, amount)
+    }
+
+    pub fn redeem(ctx: Context<Withdraw>, shares: u64) -> Result<()> {
+        redeem::handle(ctx, shares)
+    }
+
+    pub fn withdraw_sol(ctx: Context<Withdraw>, lamports: u64) -> Result<()> {
+        withdraw_sol::handle(ctx, lamports)
+    }
+
+    pub fn request_redeem<'info>(
+        ctx: Context<'_, '_, '_, 'info, RequestRedeem<'info>>,
+        shares: u64,
+    ) -> Result<()> {
+        request_redeem::handle(ctx, shares)
+    }
+
+    pub fn fulfill_redeem(ctx: Context<FulfillRedeem>) -> Result<()> {
+        fulfill_redeem::handle(ctx)
+    }
+
+    pub fn claim_redeem(ctx: Context<ClaimRedeem>) -> Result<()> {
+        claim_redeem::handle(ctx)
+    }
+
+    pub fn queue_deposit(
+        ctx: Context<QueueDeposit>,
+        amount: u64,
+        proof: Vec<[u8; 32]>,
+    ) -> Result<()> {
+        queue_deposit::handle(ctx, amount, proof)
+    }
+
+    pub fn queue_redeem<'info>(
+        ctx: Context<'_, '_, '_, 'info, QueueRedeem<'info>>,
+        shares: u64,
+    ) -> Result<()> {
+        queue_redeem::handle(ctx, shares)
+    }
+
+    pub fn close_epoch(ctx: Context<CloseEpoch>) -> Result<()> {
+        close_epoch::handle(ctx)
+    }
+
+    pub fn claim_epoch<'info>(ctx: Context<'_, '_, '_, 'info, ClaimEpoch<'info>>) -> Result<()> {
+        claim_epoch::handle(ctx)
+    }
+
+    pub fn collect_protocol_fees(ctx: Context<CollectProtocolFees>) -> Result<()> {
+        collect_protocol_fees::handle(ctx)
+    }
+
+    pub fn shutdown(ctx: Context<Shutdown>) -> Result<()> {
+        shutdown::handle(ctx)
+    }
+
+    pub fn set_guardian(ctx: Context<VaultAdmin>, guardian: Pubkey) -> Result<()> {
+        admin::set_guardian(ctx, guardian)
+    }
+
+    pub fn set_deposit_caps(
+        ctx: Context<VaultAdmin>,
+        deposit_cap: u64,
+        user_deposit_cap: u64,
+    ) -> Result<()> {
+        admin::set_deposit_caps(ctx, deposit_cap, user_deposit_cap)
+    }
+
+    pub fn set_allowlist_root(ctx: Context<VaultAdmin>, allowlist_root: [u8; 32]) -> Result<()> {
+        admin::set_allowlist_root(ctx, allowlist_root)
+    }
+
+    pub fn set_compliance_signer(
+        ctx: Context<VaultAdmin>,
+        compliance_signer: Pubkey,
+    ) -> Result<()> {
+        admin::set_compliance_signer(ctx, compliance_signer)
+    }
+
+    pub fn set_valuation_signer(
+        ctx: Context<VaultAdmin>,
+        valuation_signer: Pubkey,
+        max_nav_change_bps: u16,
+        max_nav_age: i64,
+    ) -> Result<()> {
+        admin::set_valuation_signer(ctx, valuation_signer, max_nav_change_bps, max_nav_age)
+    }
+
+    pub fn set_circuit_breaker(
+        ctx: Context<VaultAdmin>,
+        report_bps: u16,
+        window_bps: u16,
+        window: i64,
+        pause: bool,
+    ) -> Result<()> {
+        admin::set_circuit_breaker(ctx, report_bps, window_bps, window, pause)
+    }
+
+    pub fn set_compliance_authority(
+        ctx: Context<VaultAdmin>,
+        compliance_authority: Pubkey,
+    ) -> Result<()> {
+        admin::set_compliance_authority(ctx, compliance_authority)
+    }
+
+    pub fn set_epoch_duration(ctx: Context<VaultAdmin>, epoch_duration: i64) -> Result<()> {
+        admin::set_epoch_duration(ctx, epoch_duration)
+    }
+
+    pub fn set_lockup(
+        ctx: Context<VaultAdmin>,
+        lockup_seconds: i64,
+        early_exit_penalty_bps: u16,
+    ) -> Result<()> {
+        admin::set_lockup(ctx, lockup_seconds, early_exit_penalty_bps)
+    }
+
+    pub fn set_outflow_limit(
+        ctx: Context<VaultAdmin>,
+        outflow_limit_bps: u16,
+        outflow_window: i64,
+    ) -> Result<()> {
+        admin::set_outflow_limit(ctx, outflow_limit_bps, outflow_window)
+    }
+
+    pub fn update_shares_uri(ctx: Context<UpdateSharesUri>, uri: String) -> Result<()> {
+        update_shares_uri::handle(ctx, uri)
+    }
+
+    pub fn block_address(ctx: Context<BlockAddress>, wallet: Pubkey) -> Result<()> {
+        blocklist::block(ctx, wallet)
+    }
+
+    pub fn unblock_address(ctx: Context<UnblockAddress>) -> Result<()> {
+        blocklist::unblock(ctx)
+    }
+
+    pub fn allow_address(ctx: Context<AllowAddress>, wallet: Pubkey) -> Result<()> {
+        transfer_allowlist::allow(ctx, wallet)
+    }
+
+    pub fn disallow_address(ctx: Context<DisallowAddress>) -> Result<()> {
+        transfer_allowlist::disallow(ctx)
+    }
+
+    pub fn freeze_holder(ctx: Context<SetHolderFrozen>) -> Result<()> {
+        blocklist::freeze_holder(ctx)
+    }
+
+    pub fn thaw_holder(ctx: Context<SetHolderFrozen>) -> Result<()> {
+        blocklist::thaw_holder(ctx)
+    }
+
+    pub fn initialize_price_feed(ctx: Context<InitializePriceFeed>) -> Result<()> {
+        price_feed::create(ctx)
+    }
+
+    pub fn update_price_feed(
+        ctx: Context<UpdatePriceFeed>,
+        price: i64,
+        conf: u64,
+        expo: i32,
+    ) -> Result<()> {
+        price_feed::push(ctx, price, conf, expo)
+    }
+
+    pub fn initialize_basket(
+        ctx: Context<InitializeBasket>,
+        basket_id: u64,
+        exit_fee_bps: u16,
+        max_price_age: u32,
+        max_conf_bps: u16,
+    ) -> Result<()> {
+        initialize_basket::handle(ctx, basket_id, exit_fee_bps, max_price_age, max_conf_bps)
+    }
+
+    pub fn add_basket_asset(
+        ctx: Context<AddBasketAsset>,
+        allowed_mint_extensions: u16,
+    ) -> Result<()> {
+        add_basket_asset::handle(ctx, allowed_mint_extensions)
+    }
+
+    pub fn deposit_basket(ctx: Context<DepositBasket>, amount: u64) -> Result<()> {
+        deposit_basket::handle(ctx, amount)
+    }
+
+    pub fn redeem_basket<'info>(
+        ctx: Context<'_, '_, '_, 'info, RedeemBasket<'info>>,
+        shares: u64,
+    ) -> Result<()> {
+        redeem_basket::handle(ctx, shares)
+    }
+
+    pub fn redeem_basket_asset(ctx: Context<RedeemBasketAsset>, shares: u64) -> Result<()> {
+        redeem_basket_asset::handle(ctx, shares)
+    }
+
+    pub fn update_nav(
+        ctx: Context<UpdateNav>,
+        nav: u64,
+        previous_nav: u64,
+        timestamp: i64,
+        sequence: u64,
+    ) -> Result<()> {
+        update_nav::handle(ctx, nav, previous_nav, timestamp, sequence)
+    }
+
+    pub fn reset_circuit_breaker(ctx: Context<ResetCircuitBreaker>) -> Result<()> {
+        reset_circuit_breaker::handle(ctx)
+    }
+
+    pub fn max_deposit(ctx: Context<DepositorView>) -> Result<u64> {
+        views::max_deposit(ctx)
+    }
+
+    pub fn max_mint(ctx: Context<DepositorView>) -> Result<u64> {
+        views::max_mint(ctx)
+    }
+
+    pub fn preview_mint(ctx: Context<VaultView>, shares: u64) -> Result<u64> {
+        views::preview_mint(ctx, shares)
+    }
+
+    pub fn preview_deposit(ctx: Context<VaultView>, amount: u64) -> Result<u64> {
+        views::preview_deposit(ctx, amount)
+    }
+
+    pub fn preview_withdraw(ctx: Context<VaultView>, amount: u64) -> Result<u64> {
+        views::preview_withdraw(ctx, amount)
+    }
+
+    pub fn preview_redeem(ctx: Context<VaultView>, shares: u64) -> Result<u64> {
+        views::preview_redeem(ctx, shares)
+    }
+
+    pub fn max_withdraw(ctx: Context<OwnerView>) -> Result<u64> {
+        views::max_withdraw(ctx)
+    }
+
+    pub fn max_redeem(ctx: Context<OwnerView>) -> Result<u64> {
+        views::max_redeem(ctx)
+    }
+
+    pub fn pending_redeem_request(ctx: Context<RedeemRequestView>) -> Result<u64> {
+        views::pending_redeem_request(ctx)
+    }
+
+    pub fn claimable_redeem_request(ctx: Context<RedeemRequestView>) -> Result<u64> {
+        views::claimable_redeem_request(ctx)
+    }
+
+    pub fn unrealised_pnl(ctx: Context<PositionView>) -> Result<i64> {
+        views::unrealised_pnl(ctx)
+    }
+
+    pub fn avg_entry_price(ctx: Context<PositionView>) -> Result<u64> {
+        views::avg_entry_price(ctx)
+    }
+
+    pub fn share_price_twap(ctx: Context<PriceHistoryView>, window: i64) -> Result<u64> {
+        views::share_price_twap(ctx, window)
+    }
 }
@@ -0,0 +1,116 @@
+use crate::util::Errors;
+use anchor_lang::prelude::*;
+
+/// Destination approved for `allocate`, with its own per-period allocation limit.
+#[account]
+pub struct AllocationTarget {
+    pub vault: Pubkey,          // Vault allocating to the target
+    pub target_ata: Pubkey,     // Approved destination token account
+    pub active: bool,           // Whether allocations to the target are allowed
+    pub period_limit: u64,      // Max base assets allocated per period
+    pub period_seconds: i64,    // Length of an allocation period in seconds
+    pub period_started_at: i64, // Unix timestamp the current period started at
+    pub period_allocated: u64,  // Base assets allocated in the current period
+    pub allocated_assets: u64,  // Base assets currently deployed (allocated - deallocated)
+    pub bump: u8,               // allocation target bump
+}
+
+impl AllocationTarget {
+    pub const MAX_SIZE: usize = 32 + // Pubkey: vault
+        32 + // Pubkey: target_ata
+        1 +  // bool: active
+        8 +  // u64: period_limit
+        8 +  // i64: period_seconds
+        8 +  // i64: period_started_at
+        8 +  // u64: period_allocated
+        8 +  // u64: allocated_assets
+        1; // u8: bump
+
+    /// Base assets that can still be allocated in the period running at `now`.
+    pub fn period_remaining(&self, now: i64) -> u64 {
+        if self.period_ended(now) {
+            return self.period_limit;
+        }
+        self.period_limit.saturating_sub(self.period_allocated)
+    }
+
+    /// Records an allocation, starting a new period first if the current one is over.
+    /// The period counts the `amount` sent, the deployed assets what the target `received`.
+    pub fn record_allocation(&mut self, amount: u64, received: u64, now: i64) -> Result<()> {
+        if self.period_ended(now) {
+            self.period_started_at = now;
+            self.period_allocated = 0;
+        }
+
+        self.period_allocated = self
+            .period_allocated
+            .checked_add(amount)
+            .ok_or(Errors::MathOverflow)?;
+        self.allocated_assets = self
+            .allocated_assets
+            .checked_add(received)
+            .ok_or(Errors::MathOverflow)?;
+        Ok(())
+    }
+
+    /// Removes `amount` recognised as lost from the deployed assets.
+    pub fn write_down(&mut self, amount: u64) -> Result<()> {
+        self.allocated_assets = self
+            .allocated_assets
+            .checked_sub(amount)
+            .ok_or(Errors::WriteDownExceedsDebt)?;
+        Ok(())
+    }
+
+    fn period_ended(&self, now: i64) -> bool {
+        now >= self.period_started_at.saturating_add(self.period_seconds)
+    }
+}
+
+#[cfg(test)]
+mod test_allocation_target {
+    use super::*;
+
+    fn target() -> AllocationTarget {
+        AllocationTarget {
+            vault: Pubkey::default(),
+            target_ata: Pubkey::default(),
+            active: true,
+            period_limit: 1_000,
+            period_seconds: 100,
+            period_started_at: 0,
+            period_allocated: 0,
+            allocated_assets: 0,
+            bump: 0,
+        }
+    }
+
+    #[test]
+    fn period_limit_resets_after_period() {
+        let mut target = target();
+
+        target.record_allocation(600, 600, 1_000).unwrap();
+        assert_eq!(target.period_started_at, 1_000);
+        assert_eq!(target.period_remaining(1_050), 400);
+
+        target.record_allocation(400, 400, 1_050).unwrap();
+        assert_eq!(target.period_remaining(1_099), 0);
+
+        assert_eq!(target.period_remaining(1_100), 1_000);
+        target.record_allocation(100, 99, 1_100).unwrap();
+        assert_eq!(target.period_allocated, 100);
+        assert_eq!(target.allocated_assets, 1_099);
+    }
+
+    #[test]
+    fn write_down_is_bounded_by_deployed_assets() {
+        let mut target = target();
+        target.record_allocation(1_000, 1_000, 0).unwrap();
+
+        target.write_down(400).unwrap();
+        assert_eq!(target.allocated_assets, 600);
+        assert!(target.write_down(601).is_err());
+        target.write_down(600).unwrap();
+        assert_eq!(target.allocated_assets, 0);
+    }
+}
@@ -0,0 +1,18 @@
+use anchor_lang::prelude::*;
+
+/// Marks a wallet as allowed to hold and move the vault's shares when the shares mint
+/// transfer hook runs in allowlist mode, its existence is the flag.
+#[account]
+pub struct AllowedAddress {
+    pub vault: Pubkey,   // Vault allowing the wallet
+    pub wallet: Pubkey,  // Allowed wallet
+    pub allowed_at: i64, // Unix timestamp of the approval
+    pub bump: u8,        // allowed address bump
+}
+
+impl AllowedAddress {
+    pub const MAX_SIZE: usize = 32 + // Pubkey: vault
+        32 + // Pubkey: wallet
+        8 +  // i64: allowed_at
+        1; // u8: bump
+}
@@ -0,0 +1,103 @@
+use crate::util::{load_oracle_price, Errors, OraclePrice};
+use anchor_lang::prelude::*;
+
+/// Whitelisted asset of a basket, held in the basket authority's ATA. The basket tracks the
+/// amount it holds itself: tokens sent straight to the ATA are not valued, so a donation
+/// can't move the share price.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default)]
+pub struct BasketAsset {
+    pub mint: Pubkey,       // Asset mint
+    pub ata: Pubkey,        // Basket authority's ATA holding the asset
+    pub price_feed: Pubkey, // Price oracle valuing the asset (`PriceFeed` or Pyth-style)
+    pub decimals: u8,       // Asset mint decimals
+    pub balance: u64,       // Amount held for the shareholders, donations excluded
+}
+
+impl BasketAsset {
+    pub const SIZE: usize = 32 + // Pubkey: mint
+        32 + // Pubkey: ata
+        32 + // Pubkey: price_feed
+        1 + // u8: decimals
+        8; // u64: balance
+}
+
+/// Multi-asset vault: shares are minted on the basket value in a common unit of account,
+/// each asset valued through its price oracle.
+#[account]
+pub struct Basket {
+    pub admin: Pubkey,             // Role managing the basket assets
+    pub basket_authority: Pubkey,  // PDA owning the asset ATAs and the shares mint
+    pub shares_mint: Pubkey,       // Basket shares mint
+    pub token_program: Pubkey,     // Token program of the shares and every asset
+    pub basket_id: u64,            // Index of the basket among the admin's baskets
+    pub exit_fee_bps: u16,         // Fee on single-asset exits, stays in the basket
+    pub max_price_age: u32,        // Max age of the oracle prices in seconds
+    pub max_conf_bps: u16,         // Max oracle confidence interval in bps of the price
+    pub assets: Vec<BasketAsset>,  // Whitelisted assets, at most MAX_ASSETS
+    pub bump: u8,                  // basket bump
+    pub basket_authority_bump: u8, // basket authority bump
+}
+
+/// Basket value read from the tracked asset balances and price oracles, in the basket asset order.
+pub struct BasketValuation {
+    pub total_value: u64,
+    pub prices: Vec<OraclePrice>,
+}
+
+impl Basket {
+    /// Assets a basket can hold
+    pub const MAX_ASSETS: usize = 8;
+    /// Decimals of the unit of account, and of the shares
+    pub const VALUE_DECIMALS: u8 = 6;
+
+    pub const MAX_SIZE: usize = 32 + // Pubkey: admin
+        32 + // Pubkey: basket_authority
+        32 + // Pubkey: shares_mint
+        32 + // Pubkey: token_program
+        8 +  // u64: basket_id
+        2 +  // u16: exit_fee_bps
+        4 +  // u32: max_price_age
+        2 +  // u16: max_conf_bps
+        4 + Self::MAX_ASSETS * BasketAsset::SIZE + // Vec<BasketAsset>: assets
+        1 +  // u8: bump
+        1; // u8: basket_authority_bump
+
+    /// Position of `mint` in the basket assets.
+    pub fn asset_index(&self, mint: &Pubkey) -> Result<usize> {
+        self.assets
+            .iter()
+            .position(|asset| asset.mint == *mint)
+            .ok_or(Errors::AssetNotInBasket.into())
+    }
+
+    /// Values every asset's tracked balance with its price oracle from `accounts`, one per
+    /// asset in order. Prices must be fresh and tight enough at `now`.
+    pub fn valuation(&self, accounts: &[AccountInfo], now: i64) -> Result<BasketValuation> {
+        require!(
+            accounts.len() == self.assets.len(),
+            Errors::InvalidBasketAccounts
+        );
+
+        let mut total_value: u64 = 0;
+        let mut prices = Vec::with_capacity(self.assets.len());
+        for (asset, oracle) in self.assets.iter().zip(accounts) {
+            require_keys_eq!(
+                oracle.key(),
+                asset.price_feed,
+                Errors::InvalidBasketAccounts
+            );
+
+            let (price, _) = load_oracle_price(oracle)?;
+            let price = price.checked(now, self.max_price_age, self.max_conf_bps)?;
+            total_value = total_value
+                .checked_add(price.value_of(asset.balance, asset.decimals, Self::VALUE_DECIMALS)?)
+                .ok_or(Errors::MathOverflow)?;
+            prices.push(price);
+        }
+
+        Ok(BasketValuation {
+            total_value,
+            prices,
+        })
+    }
+}
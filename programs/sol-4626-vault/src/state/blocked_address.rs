@@ -0,0 +1,17 @@
+use anchor_lang::prelude::*;
+
+/// Marks a wallet as refused by the vault, its existence is the flag.
+#[account]
+pub struct BlockedAddress {
+    pub vault: Pubkey,   // Vault refusing the wallet
+    pub wallet: Pubkey,  // Blocked wallet
+    pub blocked_at: i64, // Unix timestamp of the block
+    pub bump: u8,        // blocked address bump
+}
+
+impl BlockedAddress {
+    pub const MAX_SIZE: usize = 32 + // Pubkey: vault
+        32 + // Pubkey: wallet
+        8 +  // i64: blocked_at
+        1; // u8: bump
+}
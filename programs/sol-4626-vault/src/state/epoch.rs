@@ -0,0 +1,91 @@
+use crate::util::Errors;
+use anchor_lang::prelude::*;
+
+/// Settlement batch of a vault. Deposits and redeems queued while the epoch is open
+/// all settle at the single share price snapshotted by `close_epoch`.
+#[account]
+pub struct Epoch {
+    pub vault: Pubkey,       // Vault this epoch belongs to
+    pub id: u64,             // Sequential epoch id
+    pub queued_assets: u64,  // Base assets queued for deposit
+    pub queued_shares: u64,  // Shares queued for redeem
+    pub total_assets: u64,   // Vault total assets snapshotted at close
+    pub total_shares: u64,   // Shares supply snapshotted at close
+    pub settled_shares: u64, // Shares minted for the queued deposits
+    pub settled_assets: u64, // Base assets reserved for the queued redeems
+    pub closed_at: i64,      // Unix timestamp of the close (0 = open)
+    pub bump: u8,            // epoch bump
+}
+
+impl Epoch {
+    pub const MAX_SIZE: usize = 32 + // Pubkey: vault
+        8 +  // u64: id
+        8 +  // u64: queued_assets
+        8 +  // u64: queued_shares
+        8 +  // u64: total_assets
+        8 +  // u64: total_shares
+        8 +  // u64: settled_shares
+        8 +  // u64: settled_assets
+        8 +  // i64: closed_at
+        1; // u8: bump
+
+    pub fn is_settled(&self) -> bool {
+        self.closed_at != 0
+    }
+
+    /// Whether the queued deposits minted no shares at the close, and are refunded on claim.
+    pub fn deposits_refunded(&self) -> bool {
+        self.settled_shares == 0
+    }
+
+    /// Pro-rata share of the settled shares owed for `assets` queued, rounded down.
+    pub fn shares_for(&self, assets: u64) -> Result<u64> {
+        pro_rata(assets, self.settled_shares, self.queued_assets)
+    }
+
+    /// Pro-rata share of the settled assets owed for `shares` queued, rounded down.
+    pub fn assets_for(&self, shares: u64) -> Result<u64> {
+        pro_rata(shares, self.settled_assets, self.queued_shares)
+    }
+}
+
+fn pro_rata(part: u64, settled: u64, queued: u64) -> Result<u64> {
+    if queued == 0 {
+        return Ok(0);
+    }
+
+    Ok((part as u128)
+        .checked_mul(settled as u128)
+        .ok_or(Errors::MathOverflow)?
+        .checked_div(queued as u128)
+        .ok_or(Errors::MathOverflow)?
+        .try_into()
+        .map_err(|_| Errors::MathOverflow)?)
+}
+
+#[cfg(test)]
+mod test_epoch {
+    use super::*;
+
+    #[test]
+    fn claims_are_pro_rata_and_never_exceed_settlement() {
+        let epoch = Epoch {
+            vault: Pubkey::default(),
+            id: 0,
+            queued_assets: 300,
+            queued_shares: 3,
+            total_assets: 0,
+            total_shares: 0,
+            settled_shares: 200,
+            settled_assets: 10,
+            closed_at: 1,
+            bump: 0,
+        };
+
+        assert_eq!(epoch.shares_for(100).unwrap(), 66);
+        assert_eq!(epoch.shares_for(200).unwrap(), 133);
+        assert_eq!(epoch.assets_for(1).unwrap(), 3);
+        assert_eq!(epoch.assets_for(3).unwrap(), 10);
+        assert!(!epoch.deposits_refunded());
+    }
+}
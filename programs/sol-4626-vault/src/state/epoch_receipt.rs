@@ -0,0 +1,28 @@
+use anchor_lang::prelude::*;
+
+/// Amounts an owner queued into one epoch, claimed once the epoch is settled.
+#[account]
+pub struct EpochReceipt {
+    pub epoch: Pubkey,      // Epoch this receipt belongs to
+    pub owner: Pubkey,      // Owner of the queued amounts
+    pub queued_assets: u64, // Base assets queued for deposit
+    pub queued_shares: u64, // Shares queued for redeem
+    pub bump: u8,           // epoch receipt bump
+}
+
+impl EpochReceipt {
+    pub const MAX_SIZE: usize = 32 + // Pubkey: epoch
+        32 + // Pubkey: owner
+        8 +  // u64: queued_assets
+        8 +  // u64: queued_shares
+        1; // u8: bump
+
+    /// Binds a freshly created receipt to its epoch and owner, no-op afterwards.
+    pub fn init_if_needed(&mut self, epoch: Pubkey, owner: Pubkey, bump: u8) {
+        if self.owner == Pubkey::default() {
+            self.epoch = epoch;
+            self.owner = owner;
+            self.bump = bump;
+        }
+    }
+}
@@ -0,0 +1,21 @@
+use anchor_lang::prelude::*;
+
+/// Protocol-wide singleton: factory registry of every vault and the controls shared by all of them.
+#[account]
+pub struct GlobalConfig {
+    pub protocol_admin: Pubkey, // Role managing the protocol configuration
+    pub fee_recipient: Pubkey,  // Wallet receiving the protocol cut of vault fees
+    pub protocol_fee_bps: u16,  // Protocol cut of vault fees in bps
+    pub paused: bool,           // Global pause of deposits and allocations of every vault
+    pub vault_count: u64,       // Number of vaults created through the factory
+    pub bump: u8,               // global config bump
+}
+
+impl GlobalConfig {
+    pub const MAX_SIZE: usize = 32 + // Pubkey: protocol_admin
+        32 + // Pubkey: fee_recipient
+        2 +  // u16: protocol_fee_bps
+        1 +  // bool: paused
+        8 +  // u64: vault_count
+        1; // u8: bump
+}
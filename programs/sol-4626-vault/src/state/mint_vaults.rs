@@ -0,0 +1,17 @@
+use anchor_lang::prelude::*;
+
+/// Per-mint vault registry. The vaults of a base asset mint are the PDAs
+/// `[VAULT_SEED, base_asset_mint, index]` for every index below `vault_count`,
+/// so clients enumerate them without scanning program accounts.
+#[account]
+pub struct MintVaults {
+    pub base_asset_mint: Pubkey, // Base asset mint of the registered vaults
+    pub vault_count: u64,        // Number of vaults created for the mint, next vault index
+    pub bump: u8,                // mint vaults bump
+}
+
+impl MintVaults {
+    pub const MAX_SIZE: usize = 32 + // Pubkey: base_asset_mint
+        8 + // u64: vault_count
+        1; // u8: bump
+}
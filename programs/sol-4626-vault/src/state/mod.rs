@@ -1,2 +1,41 @@
 pub mod vault;
-pub use vault::*;
\ No newline at end of file
+pub use vault::*;
+
+pub mod user_position;
+pub use user_position::*;
+
+pub mod blocked_address;
+pub use blocked_address::*;
+
+pub mod redeem_request;
+pub use redeem_request::*;
+
+pub mod epoch;
+pub use epoch::*;
+
+pub mod epoch_receipt;
+pub use epoch_receipt::*;
+
+pub mod allocation_target;
+pub use allocation_target::*;
+
+pub mod mint_vaults;
+pub use mint_vaults::*;
+
+pub mod global_config;
+pub use global_config::*;
+
+pub mod allowed_address;
+pub use allowed_address::*;
+
+pub mod price_feed;
+pub use price_feed::*;
+
+pub mod basket;
+pub use basket::*;
+
+pub mod nav_update;
+pub use nav_update::*;
+
+pub mod price_history;
+pub use price_history::*;
@@ -0,0 +1,26 @@
+use anchor_lang::prelude::*;
+
+/// NAV report applied to a vault, one account per report sequence number.
+/// Together they form the vault's on-chain valuation history.
+#[account]
+pub struct NavUpdate {
+    pub vault: Pubkey,     // Vault the report valued
+    pub sequence: u64,     // Report sequence number
+    pub nav: u64,          // Reported total base assets
+    pub previous_nav: u64, // Total base assets the report replaced
+    pub timestamp: i64,    // Unix timestamp the valuation was taken at
+    pub submitted_at: i64, // Unix timestamp the report was applied at
+    pub submitter: Pubkey, // Wallet that relayed the report
+    pub bump: u8,          // nav update bump
+}
+
+impl NavUpdate {
+    pub const MAX_SIZE: usize = 32 + // Pubkey: vault
+        8 +  // u64: sequence
+        8 +  // u64: nav
+        8 +  // u64: previous_nav
+        8 +  // i64: timestamp
+        8 +  // i64: submitted_at
+        32 + // Pubkey: submitter
+        1; // u8: bump
+}
@@ -0,0 +1,24 @@
+use anchor_lang::prelude::*;
+
+/// Price of one whole token of `mint` in the unit of account, `price * 10^expo` give or
+/// take `conf`, pushed by the feed authority. Read through the `PriceOracle` adapter.
+#[account]
+pub struct PriceFeed {
+    pub authority: Pubkey, // Role pushing prices
+    pub mint: Pubkey,      // Priced token mint
+    pub price: i64,        // Price mantissa
+    pub conf: u64,         // Confidence interval, same exponent as the price
+    pub expo: i32,         // Price exponent
+    pub publish_time: i64, // Unix timestamp of the last push
+    pub bump: u8,          // price feed bump
+}
+
+impl PriceFeed {
+    pub const MAX_SIZE: usize = 32 + // Pubkey: authority
+        32 + // Pubkey: mint
+        8 +  // i64: price
+        8 +  // u64: conf
+        4 +  // i32: expo
+        8 +  // i64: publish_time
+        1; // u8: bump
+}
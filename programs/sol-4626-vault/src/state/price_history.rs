@@ -0,0 +1,232 @@
+use crate::util::{share_price, Errors};
+use anchor_lang::prelude::*;
+#[cfg(test)]
+use bytemuck::Zeroable;
+
+/// Share price observed at a point in time, with the price accumulator up to it.
+#[zero_copy]
+#[repr(C)]
+pub struct PriceObservation {
+    pub timestamp: i64,  // Unix timestamp of the observation
+    pub price: u64,      // Share price from the timestamp on, scaled by `PRICE_SCALE`
+    pub cumulative: u64, // Sum of price * seconds held since the first observation, wrapping
+}
+
+/// Share price accumulator of the vault, updated by every instruction moving total assets
+/// or the shares supply, and a ring buffer of its values taken at most once per
+/// `OBSERVATION_INTERVAL`. However often the price is recorded, the buffer covers at least
+/// `CAPACITY * OBSERVATION_INTERVAL` seconds.
+#[account(zero_copy)]
+#[repr(C)]
+pub struct PriceHistory {
+    pub vault: Pubkey,            // Vault whose share price is recorded
+    pub head: u64,                // Index of the next observation to write
+    pub count: u64,               // Observations recorded, up to `CAPACITY`
+    pub latest: PriceObservation, // Share price and accumulator at the last record
+    pub bump: u8,                 // price history bump
+    pub _padding: [u8; 7],        // padding for alignment
+    pub observations: [PriceObservation; PriceHistory::CAPACITY], // Oldest overwritten first
+}
+
+impl PriceHistory {
+    /// Observations kept before the oldest is overwritten
+    pub const CAPACITY: usize = 256;
+    /// Min seconds between two observations of the buffer
+    pub const OBSERVATION_INTERVAL: i64 = 60;
+
+    pub const MAX_SIZE: usize = 32 + // Pubkey: vault
+        8 + // u64: head
+        8 + // u64: count
+        24 + // PriceObservation: latest
+        1 + // u8: bump
+        7 + // padding
+        Self::CAPACITY * 24; // [PriceObservation; CAPACITY]: observations
+
+    pub fn initialize(&mut self, vault: Pubkey, bump: u8) {
+        self.vault = vault;
+        self.head = 0;
+        self.count = 0;
+        self.bump = bump;
+    }
+
+    /// Records the share price of `total_assets` over `total_shares` at `now`, accruing the
+    /// previous price over the seconds it was held. A price moved and restored within one
+    /// second weighs nothing in the TWAP. The buffer takes the new accumulator value once
+    /// `OBSERVATION_INTERVAL` has passed since its newest observation.
+    pub fn record(&mut self, now: i64, total_assets: u64, total_shares: u64) {
+        let price = share_price(total_assets, total_shares);
+        let cumulative = match self.count {
+            0 => 0,
+            _ => self.cumulative_since_latest(now),
+        };
+        self.latest = PriceObservation {
+            timestamp: now,
+            price,
+            cumulative,
+        };
+
+        let newest = self.index_back(0);
+        if self.count > 0 && self.observations[newest].timestamp == now {
+            self.observations[newest] = self.latest;
+        } else if self.count == 0
+            || now - self.observations[newest].timestamp >= Self::OBSERVATION_INTERVAL
+        {
+            self.observations[self.head as usize] = self.latest;
+            self.head = (self.head + 1) % Self::CAPACITY as u64;
+            self.count = (self.count + 1).min(Self::CAPACITY as u64);
+        }
+    }
+
+    /// Time-weighted average share price over the `window` seconds ending at `now`.
+    /// Exact when the window starts at or after the newest observation of the buffer,
+    /// otherwise the accumulator is interpolated between the two observations around the
+    /// window start, off by at most one `OBSERVATION_INTERVAL` of price moves.
+    /// Errors unless the history covers the whole window.
+    pub fn twap(&self, now: i64, window: i64) -> Result<u64> {
+        require!(window > 0, Errors::InvalidTwapWindow);
+        let from = now.checked_sub(window).ok_or(Errors::MathOverflow)?;
+        require!(self.count > 0, Errors::InsufficientPriceHistory);
+
+        let accrued = self
+            .cumulative_since_latest(now)
+            .wrapping_sub(self.cumulative_at(from)?);
+        Ok(accrued / window as u64)
+    }
+
+    /// Accumulator value at `now`, the latest price held since the last record.
+    fn cumulative_since_latest(&self, now: i64) -> u64 {
+        let held = now.saturating_sub(self.latest.timestamp).max(0) as u64;
+        self.latest
+            .cumulative
+            .wrapping_add(self.latest.price.wrapping_mul(held))
+    }
+
+    /// Accumulator value at `at`, interpolated between the observations around it.
+    fn cumulative_at(&self, at: i64) -> Result<u64> {
+        if at >= self.latest.timestamp {
+            return Ok(self.cumulative_since_latest(at));
+        }
+
+        let mut next = self.latest;
+        for back in 0..self.count as usize {
+            let observation = self.observations[self.index_back(back)];
+            if observation.timestamp <= at {
+                let span = (next.timestamp - observation.timestamp) as u128;
+                let accrued = next.cumulative.wrapping_sub(observation.cumulative) as u128;
+                let elapsed = (at - observation.timestamp) as u128;
+                return Ok(observation
+                    .cumulative
+                    .wrapping_add((accrued * elapsed / span) as u64));
+            }
+            next = observation;
+        }
+
+        err!(Errors::InsufficientPriceHistory)
+    }
+
+    /// Buffer index of the observation `back` positions before the newest one.
+    fn index_back(&self, back: usize) -> usize {
+        (self.head as usize + Self::CAPACITY - 1 - back) % Self::CAPACITY
+    }
+
+    #[cfg(test)]
+    pub fn empty() -> Self {
+        Self::zeroed()
+    }
+}
+
+#[cfg(test)]
+mod test_price_history {
+    use super::*;
+    use crate::util::PRICE_SCALE;
+
+    const INTERVAL: i64 = PriceHistory::OBSERVATION_INTERVAL;
+
+    #[test]
+    fn max_size_matches_layout() {
+        assert_eq!(PriceHistory::MAX_SIZE, std::mem::size_of::<PriceHistory>());
+    }
+
+    #[test]
+    fn records_at_most_one_observation_per_interval() {
+        let mut history = PriceHistory::empty();
+        assert_eq!(history.count, 0);
+
+        history.record(100, 0, 0);
+        history.record(100, 2_000, 1_000);
+        assert_eq!(history.count, 1);
+        assert_eq!(history.latest.price, 2 * PRICE_SCALE);
+        assert_eq!(history.observations[0].price, 2 * PRICE_SCALE);
+
+        //the accumulator moves on every record, the buffer once per interval
+        history.record(101, 3_000, 1_000);
+        assert_eq!(history.count, 1);
+        assert_eq!(history.latest.cumulative, 2 * PRICE_SCALE);
+        history.record(100 + INTERVAL, 3_000, 1_000);
+        assert_eq!(history.count, 2);
+        assert_eq!(
+            history.latest.cumulative,
+            2 * PRICE_SCALE + 3 * PRICE_SCALE * (INTERVAL as u64 - 1)
+        );
+    }
+
+    #[test]
+    fn dust_records_do_not_shrink_the_covered_period() {
+        let mut history = PriceHistory::empty();
+        //one record per second over more than the buffer capacity
+        let records = PriceHistory::CAPACITY as i64 * 2;
+        for second in 0..records {
+            history.record(second, 1_000, 1_000);
+        }
+        assert!(history.count < PriceHistory::CAPACITY as u64);
+        assert_eq!(history.twap(records, records).unwrap(), PRICE_SCALE);
+    }
+
+    #[test]
+    fn wraps_around_keeping_the_latest_observations() {
+        let mut history = PriceHistory::empty();
+        for step in 0..PriceHistory::CAPACITY as i64 + 10 {
+            history.record(step * INTERVAL, 1_000, 1_000);
+        }
+
+        assert_eq!(history.count, PriceHistory::CAPACITY as u64);
+        assert_eq!(history.head, 10);
+        //oldest kept observation is at step 10
+        let now = (PriceHistory::CAPACITY as i64 + 10) * INTERVAL;
+        assert_eq!(history.twap(now, now - 10 * INTERVAL).unwrap(), PRICE_SCALE);
+        assert!(history.twap(now, now - 10 * INTERVAL + 1).is_err());
+    }
+
+    #[test]
+    fn twap_weighs_prices_by_time_held() {
+        let mut history = PriceHistory::empty();
+        //1.0 from 0, 2.0 from 100, 4.0 from 150
+        history.record(0, 1_000, 1_000);
+        history.record(100, 2_000, 1_000);
+        history.record(150, 4_000, 1_000);
+
+        //last 50s all at 4.0
+        assert_eq!(history.twap(200, 50).unwrap(), 4 * PRICE_SCALE);
+        //50s at 2.0 and 50s at 4.0
+        assert_eq!(history.twap(200, 100).unwrap(), 3 * PRICE_SCALE);
+        //100s at 1.0, 50s at 2.0, 50s at 4.0
+        assert_eq!(history.twap(200, 200).unwrap(), 2 * PRICE_SCALE);
+        //before the first observation
+        assert!(history.twap(200, 201).is_err());
+        assert!(history.twap(200, 0).is_err());
+    }
+
+    #[test]
+    fn twap_interpolates_within_an_interval() {
+        let mut history = PriceHistory::empty();
+        //3.0 held from 30 to 60 is merged into the observation at 60
+        history.record(0, 1_000, 1_000);
+        history.record(30, 3_000, 1_000);
+        history.record(INTERVAL, 1_000, 1_000);
+        assert_eq!(history.count, 2);
+
+        //the 120 price-seconds of [0, 60] are spread evenly over it
+        let twap = history.twap(INTERVAL * 2, INTERVAL + 30).unwrap();
+        assert_eq!(twap, 4 * PRICE_SCALE / 3);
+    }
+}
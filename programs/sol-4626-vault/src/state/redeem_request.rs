@@ -0,0 +1,23 @@
+use anchor_lang::prelude::*;
+
+/// Asynchronous redemption request of an owner (ERC-7540 style).
+/// Shares move from `pending_shares` to `claimable_assets` when the allocator fulfills
+/// the request at the current price.
+#[account]
+pub struct RedeemRequest {
+    pub vault: Pubkey,         // Vault this request belongs to
+    pub owner: Pubkey,         // Owner of the escrowed shares
+    pub pending_shares: u64,   // Shares escrowed, waiting for fulfillment
+    pub claimable_assets: u64, // Base assets locked in at fulfillment, ready to claim
+    pub requested_at: i64,     // Unix timestamp of the last request
+    pub bump: u8,              // redeem request bump
+}
+
+impl RedeemRequest {
+    pub const MAX_SIZE: usize = 32 + // Pubkey: vault
+        32 + // Pubkey: owner
+        8 +  // u64: pending_shares
+        8 +  // u64: claimable_assets
+        8 +  // i64: requested_at
+        1; // u8: bump
+}
@@ -0,0 +1,187 @@
+use crate::util::{Errors, PRICE_SCALE};
+use anchor_lang::prelude::*;
+
+#[account]
+pub struct UserPosition {
+    pub vault: Pubkey,               // Vault this position belongs to
+    pub owner: Pubkey,               // Owner of the position
+    pub deposited_assets: u64,       // Net base assets deposited (deposits - withdrawals)
+    pub last_voucher_nonce: u64,     // Last deposit voucher nonce consumed
+    pub first_deposit_at: i64,       // Unix timestamp of the first deposit (0 = never deposited)
+    pub last_deposit_at: i64,        // Unix timestamp of the last deposit, starts the lockup
+    pub cost_basis_assets: u64,      // Base assets paid for the shares still held
+    pub cost_basis_shares: u64,      // Shares acquired through deposits and still held
+    pub total_deposited_assets: u64, // Cumulative base assets deposited
+    pub total_withdrawn_assets: u64, // Cumulative base assets received on exits
+    pub realised_pnl: i64,           // Cumulative realised profit and loss in base assets
+    pub bump: u8,                    // user position bump
+}
+
+impl UserPosition {
+    pub const MAX_SIZE: usize = 32 + // Pubkey: vault
+        32 + // Pubkey: owner
+        8 +  // u64: deposited_assets
+        8 +  // u64: last_voucher_nonce
+        8 +  // i64: first_deposit_at
+        8 +  // i64: last_deposit_at
+        8 +  // u64: cost_basis_assets
+        8 +  // u64: cost_basis_shares
+        8 +  // u64: total_deposited_assets
+        8 +  // u64: total_withdrawn_assets
+        8 +  // i64: realised_pnl
+        1; // u8: bump
+
+    /// Binds a freshly created position to its vault and owner, no-op afterwards.
+    pub fn init_if_needed(&mut self, vault: Pubkey, owner: Pubkey, bump: u8) {
+        if self.owner == Pubkey::default() {
+            self.vault = vault;
+            self.owner = owner;
+            self.bump = bump;
+        }
+    }
+
+    /// Records a deposit timestamp, restarting the lockup of the whole position.
+    pub fn record_deposit(&mut self, now: i64) {
+        if self.first_deposit_at == 0 {
+            self.first_deposit_at = now;
+        }
+        self.last_deposit_at = now;
+    }
+
+    /// Adds shares bought for `assets` to the cost basis, moving the weighted-average
+    /// entry price.
+    pub fn record_entry(&mut self, assets: u64, shares: u64) -> Result<()> {
+        self.cost_basis_assets = self
+            .cost_basis_assets
+            .checked_add(assets)
+            .ok_or(Errors::MathOverflow)?;
+        self.cost_basis_shares = self
+            .cost_basis_shares
+            .checked_add(shares)
+            .ok_or(Errors::MathOverflow)?;
+        self.total_deposited_assets = self
+            .total_deposited_assets
+            .checked_add(assets)
+            .ok_or(Errors::MathOverflow)?;
+        Ok(())
+    }
+
+    /// Removes `shares` sold for `assets` from the cost basis at the average entry price
+    /// and returns the realised PnL. Shares beyond the cost basis (received by transfer)
+    /// have no known entry price and realise no PnL.
+    pub fn record_exit(&mut self, shares: u64, assets: u64) -> Result<i64> {
+        self.total_withdrawn_assets = self
+            .total_withdrawn_assets
+            .checked_add(assets)
+            .ok_or(Errors::MathOverflow)?;
+
+        let tracked_shares = shares.min(self.cost_basis_shares);
+        if tracked_shares == 0 {
+            return Ok(0);
+        }
+
+        let basis = mul_div(
+            self.cost_basis_assets,
+            tracked_shares,
+            self.cost_basis_shares,
+        )?;
+        let proceeds = mul_div(assets, tracked_shares, shares)?;
+        let pnl = (proceeds as i128 - basis as i128) as i64;
+
+        self.cost_basis_assets -= basis;
+        self.cost_basis_shares -= tracked_shares;
+        self.realised_pnl = self
+            .realised_pnl
+            .checked_add(pnl)
+            .ok_or(Errors::MathOverflow)?;
+
+        Ok(pnl)
+    }
+
+    /// Weighted-average entry price in base assets per share, scaled by `PRICE_SCALE`.
+    pub fn avg_entry_price(&self) -> Result<u64> {
+        if self.cost_basis_shares == 0 {
+            return Ok(0);
+        }
+        mul_div(self.cost_basis_assets, PRICE_SCALE, self.cost_basis_shares)
+    }
+
+    /// PnL of the shares in the cost basis if they were sold for `value` base assets.
+    pub fn unrealised_pnl(&self, value: u64) -> i64 {
+        (value as i128 - self.cost_basis_assets as i128) as i64
+    }
+}
+
+fn mul_div(value: u64, numerator: u64, denominator: u64) -> Result<u64> {
+    Ok((value as u128)
+        .checked_mul(numerator as u128)
+        .ok_or(Errors::MathOverflow)?
+        .checked_div(denominator as u128)
+        .ok_or(Errors::MathOverflow)?
+        .try_into()
+        .map_err(|_| Errors::MathOverflow)?)
+}
+
+#[cfg(test)]
+mod test_user_position {
+    use super::*;
+
+    fn position() -> UserPosition {
+        UserPosition {
+            vault: Pubkey::default(),
+            owner: Pubkey::default(),
+            deposited_assets: 0,
+            last_voucher_nonce: 0,
+            first_deposit_at: 0,
+            last_deposit_at: 0,
+            cost_basis_assets: 0,
+            cost_basis_shares: 0,
+            total_deposited_assets: 0,
+            total_withdrawn_assets: 0,
+            realised_pnl: 0,
+            bump: 0,
+        }
+    }
+
+    #[test]
+    fn entries_average_the_entry_price() {
+        let mut pos = position();
+        pos.record_entry(1_000, 1_000).unwrap();
+        pos.record_entry(2_000, 1_000).unwrap();
+
+        assert_eq!(pos.avg_entry_price().unwrap(), 1_500_000_000);
+        assert_eq!(pos.total_deposited_assets, 3_000);
+    }
+
+    #[test]
+    fn exits_realise_pnl_against_the_average_price() {
+        let mut pos = position();
+        pos.record_entry(1_000, 1_000).unwrap();
+        pos.record_entry(2_000, 1_000).unwrap();
+
+        assert_eq!(pos.record_exit(1_000, 1_800).unwrap(), 300);
+        assert_eq!(pos.record_exit(1_000, 1_200).unwrap(), -300);
+        assert_eq!(pos.realised_pnl, 0);
+        assert_eq!(pos.cost_basis_assets, 0);
+        assert_eq!(pos.total_withdrawn_assets, 3_000);
+    }
+
+    #[test]
+    fn untracked_shares_realise_nothing() {
+        let mut pos = position();
+        pos.record_entry(1_000, 1_000).unwrap();
+
+        // half of the exited shares were received by transfer
+        assert_eq!(pos.record_exit(2_000, 4_000).unwrap(), 1_000);
+        assert_eq!(pos.cost_basis_shares, 0);
+        assert_eq!(pos.record_exit(500, 1_000).unwrap(), 0);
+    }
+
+    #[test]
+    fn unrealised_pnl_is_value_over_cost() {
+        let mut pos = position();
+        pos.record_entry(1_000, 1_000).unwrap();
+        assert_eq!(pos.unrealised_pnl(1_250), 250);
+        assert_eq!(pos.unrealised_pnl(900), -100);
+    }
+}
@@ -1,4 +1,6 @@
+use crate::util::{BoolU8, Errors, BPS_DENOMINATOR};
 use anchor_lang::prelude::*;
+#[cfg(test)]
 use bytemuck::Zeroable;
 
 #[account(zero_copy)]
@@ -9,14 +11,51 @@ pub struct Vault {
     pub shares_mint: Pubkey,          // SPL mint for vault shares
     pub base_asset_mint: Pubkey,      // SPL mint accepted for deposits
     pub vault_base_asset_ata: Pubkey, // SPL vault base token associated account
-    pub token_program: Pubkey,        // Token program address
+    pub token_program: Pubkey,        // Token program of the base asset
+    pub shares_token_program: Pubkey, // Token program of the shares mint
+    pub guardian: Pubkey,             // Guardian allowed to trigger emergency shutdown
+    pub allowlist_root: [u8; 32],     // Merkle root of allowed depositors (zeroed = open vault)
+    pub compliance_signer: Pubkey,    // Off-chain signer of deposit vouchers (zeroed = no vouchers)
+    pub compliance_authority: Pubkey, // Role managing the address blocklist
+    pub valuation_signer: Pubkey,     // Off-chain signer of NAV reports (zeroed = no reports)
     pub total_base_assets: u64,       // Total amount of base asset managed by the vault
+    pub deposit_cap: u64,             // Max total base assets accepted by the vault (0 = uncapped)
+    pub user_deposit_cap: u64,        // Max net base assets deposited per user (0 = uncapped)
+    pub pending_redeem_shares: u64,   // Shares escrowed by redeem requests, not yet fulfilled
+    pub reserved_assets: u64,         // Base assets queued in epochs or owed to redeemers
+    pub current_epoch: u64,           // Id of the epoch accepting queued entries
+    pub epoch_started_at: i64,        // Unix timestamp the current epoch started at
+    pub epoch_duration: i64,          // Minimum epoch length in seconds (0 = epochs disabled)
+    pub lockup_seconds: i64,          // Minimum holding period after a deposit (0 = no lockup)
+    pub outflow_window: i64,          // Rolling window of the withdrawal rate limit in seconds
+    pub outflow_counter: u64,         // Assets withdrawn in the window, decays linearly over it
+    pub outflow_updated_at: i64,      // Unix timestamp of the last outflow counter update
+    pub vault_index: u64,             // Index of the vault among the vaults of its base asset mint
+    pub protocol_fees: u64,           // Protocol cut of vault fees, owed to the fee recipient
+    pub nav_sequence: u64,            // Sequence number of the last applied NAV report
+    pub nav_updated_at: i64,          // Timestamp of the last applied NAV report
+    pub nav_max_age: i64,             // Max age in seconds of a NAV report when relayed
+    pub breaker_window: i64,          // Circuit breaker window in seconds (0 = no window check)
+    pub breaker_ref_price: u64,       // Share price the current breaker window started at
+    pub breaker_ref_at: i64,          // Unix timestamp the current breaker window started at
     pub mint_shares_decimals: u8,     // Mint shares decimals
-    pub deposit_paused: u8,       // Flag to pause deposits
-    pub allocate_paused: u8,      // Flag to pause allocations
+    pub deposit_paused: u8,           // Flag to pause deposits
+    pub allocate_paused: u8,          // Flag to pause allocations
+    pub shutdown: BoolU8,             // Irreversible emergency shutdown (withdraw-only)
     pub vault_authority_bump: u8,     // vault authority bump
     pub mint_shares_bump: u8,         // vault authority bump
-    pub _padding: [u8; 3],            //padding for alignment
+    pub early_exit_penalty_bps: u16,  // Exit penalty at lockup start (0 = reject)
+    pub outflow_limit_bps: u16,       // Max outflow per window, bps of total assets (0 = unlimited)
+    pub allowed_mint_extensions: u16, // Risky base mint features opted into (`MintExtensions` flags)
+    pub max_nav_change_bps: u16,      // Max total assets change per NAV report (0 = unlimited)
+    pub breaker_report_bps: u16,      // Max share price change per report (0 = unlimited)
+    pub breaker_window_bps: u16,      // Max share price change per breaker window (0 = unlimited)
+    pub vault_bump: u8,               // vault bump
+    pub soulbound_shares: BoolU8,     // Shares mint is Token-2022 NonTransferable
+    pub transfer_hook_mode: u8,       // Address list checked on share transfers (0 = no hook)
+    pub breaker_pause: BoolU8,        // Breaches pause flows instead of reverting
+    pub breaker_tripped: BoolU8,      // Flows paused until the guardian resets
+    pub _padding: [u8; 1],            // padding for alignment
 }
 
 impl Vault {
@@ -26,14 +65,52 @@ impl Vault {
         32 + // Pubkey: base_mint
         32 + // Pubkey: vault base asset ata
         32 + // Pubkey: token program address
+        32 + // Pubkey: shares token program address
+        32 + // Pubkey: guardian
+        32 + // [u8; 32]: allowlist_root
+        32 + // Pubkey: compliance_signer
+        32 + // Pubkey: compliance_authority
+        32 + // Pubkey: valuation_signer
         8 +  // u64: total_base_assets
+        8 +  // u64: deposit_cap
+        8 +  // u64: user_deposit_cap
+        8 +  // u64: pending_redeem_shares
+        8 +  // u64: reserved_assets
+        8 +  // u64: current_epoch
+        8 +  // i64: epoch_started_at
+        8 +  // i64: epoch_duration
+        8 +  // i64: lockup_seconds
+        8 +  // i64: outflow_window
+        8 +  // u64: outflow_counter
+        8 +  // i64: outflow_updated_at
+        8 +  // u64: vault_index
+        8 +  // u64: protocol_fees
+        8 +  // u64: nav_sequence
+        8 +  // i64: nav_updated_at
+        8 +  // i64: nav_max_age
+        8 +  // i64: breaker_window
+        8 +  // u64: breaker_ref_price
+        8 +  // i64: breaker_ref_at
         1 +  // u64: mint shares decimals
         1 +  // u8: deposit_paused
         1 + // u8: allocate_paused
+        1 + // u8: shutdown
         1 + // u8: vault authority bump
         1 + // u8: mint shares bump
-        3; // padding
+        2 + // u16: early_exit_penalty_bps
+        2 + // u16: outflow_limit_bps
+        2 + // u16: allowed_mint_extensions
+        2 + // u16: max_nav_change_bps
+        2 + // u16: breaker_report_bps
+        2 + // u16: breaker_window_bps
+        1 + // u8: vault bump
+        1 + // u8: soulbound_shares
+        1 + // u8: transfer_hook_mode
+        1 + // u8: breaker_pause
+        1 + // u8: breaker_tripped
+        1; // padding
 
+    #[allow(clippy::too_many_arguments)]
     pub fn initialize(
         &mut self,
         admin: Pubkey,
@@ -41,8 +118,14 @@ impl Vault {
         shares_mint: Pubkey,
         base_asset_mint: Pubkey,
         token_program: Pubkey,
+        shares_token_program: Pubkey,
         vault_base_asset_ata: Pubkey,
         mint_shares_decimals: u8,
+        vault_index: u64,
+        allowed_mint_extensions: u16,
+        soulbound_shares: bool,
+        transfer_hook_mode: u8,
+        vault_bump: u8,
         vault_authority_bump: u8,
         mint_shares_bump: u8,
     ) -> Result<()> {
@@ -51,20 +134,192 @@ impl Vault {
         self.shares_mint = shares_mint;
         self.base_asset_mint = base_asset_mint;
         self.token_program = token_program;
+        self.shares_token_program = shares_token_program;
         self.vault_base_asset_ata = vault_base_asset_ata;
         self.mint_shares_decimals = mint_shares_decimals;
+        self.vault_index = vault_index;
+        self.allowed_mint_extensions = allowed_mint_extensions;
+        self.soulbound_shares = BoolU8::new(soulbound_shares);
+        self.transfer_hook_mode = transfer_hook_mode;
+        self.vault_bump = vault_bump;
         self.vault_authority_bump = vault_authority_bump;
         self.mint_shares_bump = mint_shares_bump;
 
         //default fields
+        self.guardian = admin;
+        self.allowlist_root = [0; 32];
+        self.compliance_signer = Pubkey::default();
+        self.compliance_authority = admin;
+        self.valuation_signer = Pubkey::default();
         self.total_base_assets = 0;
+        self.deposit_cap = 0;
+        self.user_deposit_cap = 0;
+        self.pending_redeem_shares = 0;
+        self.reserved_assets = 0;
+        self.current_epoch = 0;
+        self.epoch_started_at = 0;
+        self.epoch_duration = 0;
+        self.lockup_seconds = 0;
+        self.outflow_window = 0;
+        self.outflow_counter = 0;
+        self.outflow_updated_at = 0;
+        self.protocol_fees = 0;
+        self.nav_sequence = 0;
+        self.nav_updated_at = 0;
+        self.nav_max_age = 0;
+        self.breaker_window = 0;
+        self.breaker_ref_price = 0;
+        self.breaker_ref_at = 0;
         self.deposit_paused = 0;
         self.allocate_paused = 0;
-        self._padding = [0; 3];
+        self.shutdown = BoolU8::new(false);
+        self.early_exit_penalty_bps = 0;
+        self.outflow_limit_bps = 0;
+        self.max_nav_change_bps = 0;
+        self.breaker_report_bps = 0;
+        self.breaker_window_bps = 0;
+        self.breaker_pause = BoolU8::new(false);
+        self.breaker_tripped = BoolU8::new(false);
+        self._padding = [0; 1];
 
         Ok(())
     }
 
+    /// Deposits are accepted only while the vault is neither paused nor shut down.
+    pub fn deposits_enabled(&self) -> bool {
+        self.deposit_paused == 0 && !self.shutdown.is_true() && !self.breaker_tripped.is_true()
+    }
+
+    /// Checks a share price move from `price` to `new_price` at `now` against the circuit
+    /// breaker thresholds, starting a new breaker window at `price` once the current one
+    /// is over. Returns whether a threshold is breached.
+    pub fn breaker_breached(&mut self, price: u64, new_price: u64, now: i64) -> bool {
+        if self.breaker_window > 0 && now >= self.breaker_ref_at.saturating_add(self.breaker_window)
+        {
+            self.breaker_ref_price = price;
+            self.breaker_ref_at = now;
+        }
+
+        let exceeds = |from: u64, max_bps: u16| {
+            max_bps > 0
+                && new_price.abs_diff(from) as u128 * BPS_DENOMINATOR as u128
+                    > from as u128 * max_bps as u128
+        };
+        exceeds(price, self.breaker_report_bps)
+            || (self.breaker_window > 0 && exceeds(self.breaker_ref_price, self.breaker_window_bps))
+    }
+
+    /// Remaining base assets that can be deposited before hitting the vault or user cap.
+    /// - deposited_by_user: net base assets already deposited by the user
+    pub fn deposit_headroom(&self, deposited_by_user: u64) -> u64 {
+        let vault_headroom = match self.deposit_cap {
+            0 => u64::MAX,
+            cap => cap.saturating_sub(self.total_base_assets),
+        };
+        let user_headroom = match self.user_deposit_cap {
+            0 => u64::MAX,
+            cap => cap.saturating_sub(deposited_by_user),
+        };
+
+        vault_headroom.min(user_headroom)
+    }
+
+    /// Whether deposits are restricted to the merkle allowlist.
+    pub fn has_allowlist(&self) -> bool {
+        self.allowlist_root != [0; 32]
+    }
+
+    /// Whether deposits require a voucher signed by the compliance signer.
+    pub fn requires_voucher(&self) -> bool {
+        self.compliance_signer != Pubkey::default()
+    }
+
+    /// Whether total assets can be updated from NAV reports signed by the valuation signer.
+    pub fn accepts_nav_reports(&self) -> bool {
+        self.valuation_signer != Pubkey::default()
+    }
+
+    /// Base assets in the vault ATA that are free to leave, i.e. not queued in the
+    /// current epoch nor owed to redeemers or to the protocol.
+    /// - balance: current vault base asset ATA balance
+    pub fn idle_assets(&self, balance: u64) -> u64 {
+        balance
+            .saturating_sub(self.reserved_assets)
+            .saturating_sub(self.protocol_fees)
+    }
+
+    pub fn epochs_enabled(&self) -> bool {
+        self.epoch_duration > 0
+    }
+
+    /// Whether the current epoch has run for at least `epoch_duration` and can be closed.
+    /// Still true once epochs are disabled, so already queued entries can settle.
+    pub fn epoch_ended(&self, now: i64) -> bool {
+        now >= self.epoch_started_at.saturating_add(self.epoch_duration)
+    }
+
+    /// Early-exit penalty in bps for a position last deposited at `deposited_at`.
+    /// Decays linearly from `early_exit_penalty_bps` to 0 over the lockup (rounded up),
+    /// errors while locked if the vault rejects early exits instead of charging them.
+    pub fn exit_penalty_bps(&self, deposited_at: i64, now: i64) -> Result<u64> {
+        let remaining = deposited_at
+            .saturating_add(self.lockup_seconds)
+            .saturating_sub(now);
+        if remaining <= 0 {
+            return Ok(0);
+        }
+        require!(self.early_exit_penalty_bps > 0, Errors::PositionLocked);
+
+        let lockup = self.lockup_seconds as u128;
+        let numerator = (self.early_exit_penalty_bps as u128)
+            .checked_mul(remaining as u128)
+            .ok_or(Errors::MathOverflow)?;
+
+        Ok(numerator
+            .checked_add(lockup - 1)
+            .ok_or(Errors::MathOverflow)?
+            .checked_div(lockup)
+            .ok_or(Errors::MathOverflow)? as u64)
+    }
+
+    /// Outflow counter at `now`, decayed linearly to 0 over the rolling window.
+    pub fn decayed_outflow(&self, now: i64) -> u64 {
+        if self.outflow_window <= 0 {
+            return 0;
+        }
+        let elapsed = now.saturating_sub(self.outflow_updated_at).max(0);
+        if elapsed >= self.outflow_window {
+            return 0;
+        }
+
+        let left = (self.outflow_window - elapsed) as u128;
+        (self.outflow_counter as u128 * left / self.outflow_window as u128) as u64
+    }
+
+    /// Base assets that can still leave through withdraw/redeem at `now`,
+    /// `u64::MAX` when the vault has no outflow limit.
+    pub fn outflow_capacity(&self, now: i64) -> u64 {
+        if self.outflow_limit_bps == 0 {
+            return u64::MAX;
+        }
+
+        let limit = (self.total_base_assets as u128 * self.outflow_limit_bps as u128
+            / BPS_DENOMINATOR as u128) as u64;
+        limit.saturating_sub(self.decayed_outflow(now))
+    }
+
+    /// Adds an outflow to the decaying counter, rejecting it beyond the remaining capacity.
+    pub fn record_outflow(&mut self, assets: u64, now: i64) -> Result<()> {
+        require!(
+            assets <= self.outflow_capacity(now),
+            Errors::WithdrawalRateLimited
+        );
+
+        self.outflow_counter = self.decayed_outflow(now).saturating_add(assets);
+        self.outflow_updated_at = now;
+        Ok(())
+    }
+
     #[cfg(test)]
     pub fn empty() -> Self {
         Self {
@@ -73,14 +328,175 @@ impl Vault {
             shares_mint: Pubkey::zeroed(),
             base_asset_mint: Pubkey::zeroed(),
             token_program: Pubkey::zeroed(),
+            shares_token_program: Pubkey::zeroed(),
             vault_base_asset_ata: Pubkey::zeroed(),
+            guardian: Pubkey::zeroed(),
+            allowlist_root: [0; 32],
+            compliance_signer: Pubkey::zeroed(),
+            compliance_authority: Pubkey::zeroed(),
+            valuation_signer: Pubkey::zeroed(),
             mint_shares_decimals: 0,
             vault_authority_bump: 0,
             mint_shares_bump: 0,
             total_base_assets: 0,
+            deposit_cap: 0,
+            user_deposit_cap: 0,
+            pending_redeem_shares: 0,
+            reserved_assets: 0,
+            current_epoch: 0,
+            epoch_started_at: 0,
+            epoch_duration: 0,
+            lockup_seconds: 0,
+            outflow_window: 0,
+            outflow_counter: 0,
+            outflow_updated_at: 0,
+            vault_index: 0,
+            protocol_fees: 0,
+            nav_sequence: 0,
+            nav_updated_at: 0,
+            nav_max_age: 0,
+            breaker_window: 0,
+            breaker_ref_price: 0,
+            breaker_ref_at: 0,
+            vault_bump: 0,
             deposit_paused: 0,
             allocate_paused: 0,
-            _padding: [0; 3],
+            shutdown: BoolU8::new(false),
+            early_exit_penalty_bps: 0,
+            outflow_limit_bps: 0,
+            allowed_mint_extensions: 0,
+            max_nav_change_bps: 0,
+            breaker_report_bps: 0,
+            breaker_window_bps: 0,
+            soulbound_shares: BoolU8::new(false),
+            transfer_hook_mode: 0,
+            breaker_pause: BoolU8::new(false),
+            breaker_tripped: BoolU8::new(false),
+            _padding: [0; 1],
         }
     }
 }
+
+#[cfg(test)]
+mod test_vault {
+    use super::*;
+
+    #[test]
+    fn max_size_matches_layout() {
+        assert_eq!(Vault::MAX_SIZE, std::mem::size_of::<Vault>());
+    }
+
+    #[test]
+    fn shutdown_disables_deposits() {
+        let mut vlt = Vault::empty();
+        assert!(vlt.deposits_enabled());
+
+        vlt.shutdown = BoolU8::new(true);
+        assert!(!vlt.deposits_enabled());
+    }
+
+    #[test]
+    fn pause_disables_deposits() {
+        let mut vlt = Vault::empty();
+        vlt.deposit_paused = 1;
+        assert!(!vlt.deposits_enabled());
+    }
+
+    #[test]
+    fn deposit_headroom_is_bounded_by_both_caps() {
+        let mut vlt = Vault::empty();
+        assert_eq!(vlt.deposit_headroom(0), u64::MAX);
+
+        vlt.total_base_assets = 700;
+        vlt.deposit_cap = 1_000;
+        assert_eq!(vlt.deposit_headroom(0), 300);
+
+        vlt.user_deposit_cap = 500;
+        assert_eq!(vlt.deposit_headroom(400), 100);
+        assert_eq!(vlt.deposit_headroom(600), 0);
+
+        vlt.total_base_assets = 1_200;
+        assert_eq!(vlt.deposit_headroom(0), 0);
+    }
+
+    #[test]
+    fn tripped_breaker_disables_deposits() {
+        let mut vlt = Vault::empty();
+        vlt.breaker_tripped = BoolU8::new(true);
+        assert!(!vlt.deposits_enabled());
+    }
+
+    #[test]
+    fn breaker_checks_report_and_window_moves() {
+        let mut vlt = Vault::empty();
+        assert!(!vlt.breaker_breached(1_000, 5_000, 0));
+
+        //10% per report
+        vlt.breaker_report_bps = 1_000;
+        assert!(!vlt.breaker_breached(1_000, 1_100, 0));
+        assert!(!vlt.breaker_breached(1_000, 900, 0));
+        assert!(vlt.breaker_breached(1_000, 1_101, 0));
+        assert!(vlt.breaker_breached(1_000, 899, 0));
+
+        //15% per hour, the first check starts the window
+        vlt.breaker_window_bps = 1_500;
+        vlt.breaker_window = 3_600;
+        assert!(!vlt.breaker_breached(1_000, 1_100, 10_000));
+        assert_eq!(vlt.breaker_ref_price, 1_000);
+        assert!(!vlt.breaker_breached(1_100, 1_150, 10_100));
+        assert!(vlt.breaker_breached(1_150, 1_151, 13_599));
+
+        //next window starts at the pre-report price
+        assert!(!vlt.breaker_breached(1_150, 1_250, 13_600));
+        assert_eq!(vlt.breaker_ref_price, 1_150);
+        assert_eq!(vlt.breaker_ref_at, 13_600);
+    }
+
+    #[test]
+    fn epoch_ends_after_duration() {
+        let mut vlt = Vault::empty();
+        assert!(!vlt.epochs_enabled());
+        assert!(vlt.epoch_ended(0));
+
+        vlt.epoch_duration = 3_600;
+        vlt.epoch_started_at = 1_000;
+        assert!(!vlt.epoch_ended(4_599));
+        assert!(vlt.epoch_ended(4_600));
+    }
+
+    #[test]
+    fn exit_penalty_decays_over_lockup() {
+        let mut vlt = Vault::empty();
+        assert_eq!(vlt.exit_penalty_bps(1_000, 1_000).unwrap(), 0);
+
+        vlt.lockup_seconds = 1_000;
+        assert!(vlt.exit_penalty_bps(1_000, 1_500).is_err());
+        assert_eq!(vlt.exit_penalty_bps(1_000, 2_000).unwrap(), 0);
+
+        vlt.early_exit_penalty_bps = 500;
+        assert_eq!(vlt.exit_penalty_bps(1_000, 1_000).unwrap(), 500);
+        assert_eq!(vlt.exit_penalty_bps(1_000, 1_500).unwrap(), 250);
+        assert_eq!(vlt.exit_penalty_bps(1_000, 1_999).unwrap(), 1);
+        assert_eq!(vlt.exit_penalty_bps(1_000, 2_000).unwrap(), 0);
+    }
+
+    #[test]
+    fn outflow_counter_decays_over_window() {
+        let mut vlt = Vault::empty();
+        vlt.total_base_assets = 1_000_000;
+        assert_eq!(vlt.outflow_capacity(0), u64::MAX);
+
+        vlt.outflow_limit_bps = 1_000;
+        vlt.outflow_window = 86_400;
+        assert_eq!(vlt.outflow_capacity(0), 100_000);
+
+        vlt.record_outflow(100_000, 0).unwrap();
+        assert_eq!(vlt.outflow_capacity(0), 0);
+        assert!(vlt.record_outflow(1, 0).is_err());
+
+        // half of the window later half of the counter has decayed
+        assert_eq!(vlt.decayed_outflow(43_200), 50_000);
+        assert_eq!(vlt.outflow_capacity(43_200), 50_000);
+        assert_eq!(vlt.outflow_capacity(86_400), 100_000);
+    }
+}
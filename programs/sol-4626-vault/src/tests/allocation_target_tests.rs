@@ -0,0 +1,116 @@
+#[cfg(test)]
+mod test_allocation_target {
+    use crate::tests::helper::{assert_rejected, ata, VaultFixture};
+    use crate::util::Errors;
+    use anchor_lang::error::ErrorCode;
+    use litesvm_token::{Approve, CreateAssociatedTokenAccount};
+    use solana_sdk::signature::{Keypair, Signer};
+
+    #[test]
+    pub fn test_allocate_requires_approved_destination() {
+        let mut fx = VaultFixture::new();
+        let admin = fx.admin.insecure_clone();
+        let user = fx.create_depositor(1_000_000);
+        let ix = fx.deposit_ix(&user.pubkey(), 1_000_000);
+        fx.send(ix, &[&user]).unwrap();
+
+        let target = fx.create_strategy(&admin);
+        let ix = fx.allocate_ix(&target, 100_000);
+        assert_rejected(fx.send(ix, &[&admin]), Errors::DestinationNotApproved);
+
+        //approved by the guardian (admin by default)
+        let ix = fx.set_allocation_target_ix(&target, 300_000, 86_400, true);
+        fx.send(ix, &[&admin]).unwrap();
+        let ix = fx.allocate_ix(&target, 100_000);
+        fx.send(ix, &[&admin]).unwrap();
+        assert_eq!(fx.token_balance(&target), 100_000);
+
+        //revoked destinations are refused again
+        let ix = fx.set_allocation_target_ix(&target, 300_000, 86_400, false);
+        fx.send(ix, &[&admin]).unwrap();
+        let ix = fx.allocate_ix(&target, 100_000);
+        assert_rejected(fx.send(ix, &[&admin]), Errors::DestinationNotApproved);
+    }
+
+    #[test]
+    pub fn test_allocate_period_limit() {
+        let mut fx = VaultFixture::new();
+        fx.set_unix_timestamp(1_000);
+        let admin = fx.admin.insecure_clone();
+        let user = fx.create_depositor(1_000_000);
+        let ix = fx.deposit_ix(&user.pubkey(), 1_000_000);
+        fx.send(ix, &[&user]).unwrap();
+
+        let target = fx.create_strategy(&admin);
+        let ix = fx.set_allocation_target_ix(&target, 300_000, 86_400, true);
+        fx.send(ix, &[&admin]).unwrap();
+
+        let ix = fx.allocate_ix(&target, 200_000);
+        fx.send(ix, &[&admin]).unwrap();
+        let ix = fx.allocate_ix(&target, 100_001);
+        let result = fx.send(ix, &[&admin]);
+        //`RateLimitHit` only shows in the failed transaction logs
+        assert!(result
+            .as_ref()
+            .unwrap_err()
+            .meta
+            .logs
+            .iter()
+            .any(|log| log.starts_with("Program data: ")));
+        assert_rejected(result, Errors::AllocationLimitExceeded);
+
+        //the limit resets with the next period
+        fx.set_unix_timestamp(1_000 + 86_400);
+        let ix = fx.allocate_ix(&target, 300_000);
+        fx.send(ix, &[&admin]).unwrap();
+        assert_eq!(fx.token_balance(&target), 500_000);
+    }
+
+    #[test]
+    pub fn test_allocate_requires_vault_owned_destination() {
+        let mut fx = VaultFixture::new();
+        let admin = fx.admin.insecure_clone();
+        let user = fx.create_depositor(1_000_000);
+        let ix = fx.deposit_ix(&user.pubkey(), 1_000_000);
+        fx.send(ix, &[&user]).unwrap();
+
+        //approved, but the vault authority could not pull the assets back
+        let wallet_ata =
+            CreateAssociatedTokenAccount::new(&mut fx.svm, &admin, &fx.base_asset_mint)
+                .send()
+                .unwrap();
+        //a delegation can be revoked before the admin pulls the assets back
+        let delegator = fx.create_depositor(0);
+        let delegated_ata = ata(&delegator.pubkey(), &fx.base_asset_mint);
+        Approve::new(
+            &mut fx.svm,
+            &delegator,
+            &fx.vault_authority,
+            &delegated_ata,
+            u64::MAX,
+        )
+        .send()
+        .unwrap();
+        //the vault's own ATA is no escrow
+        let vault_ata = fx.vault_base_asset_ata;
+        for target in [wallet_ata, delegated_ata, vault_ata] {
+            let ix = fx.set_allocation_target_ix(&target, u64::MAX, 86_400, true);
+            fx.send(ix, &[&admin]).unwrap();
+            let ix = fx.allocate_ix(&target, 100_000);
+            assert_rejected(fx.send(ix, &[&admin]), Errors::TargetNotVaultOwned);
+        }
+    }
+
+    #[test]
+    pub fn test_only_guardian_approves_destinations() {
+        let mut fx = VaultFixture::new();
+        let admin = fx.admin.insecure_clone();
+        let guardian = Keypair::new();
+        fx.send_admin("set_guardian", guardian.pubkey().as_ref())
+            .unwrap();
+
+        let target = fx.create_strategy(&admin);
+        let ix = fx.set_allocation_target_ix(&target, u64::MAX, 86_400, true);
+        assert_rejected(fx.send(ix, &[&admin]), ErrorCode::ConstraintHasOne);
+    }
+}
@@ -0,0 +1,41 @@
+#[cfg(test)]
+mod test_allowlist {
+    use crate::tests::helper::{assert_rejected, to_pubkey, VaultFixture};
+    use crate::util::{allowlist_leaf, hash_pair, Errors};
+    use solana_sdk::signature::Signer;
+
+    #[test]
+    pub fn test_allowlist_deposit() {
+        let mut fx = VaultFixture::new();
+        let alice = fx.create_depositor(1_000_000);
+        let bob = fx.create_depositor(1_000_000);
+        let mallory = fx.create_depositor(1_000_000);
+
+        //two-leaf allowlist: alice, bob
+        let alice_leaf = allowlist_leaf(&to_pubkey(&alice.pubkey()));
+        let bob_leaf = allowlist_leaf(&to_pubkey(&bob.pubkey()));
+        let root = hash_pair(&alice_leaf, &bob_leaf);
+        fx.send_admin("set_allowlist_root", &root).unwrap();
+
+        //allowlisted depositors prove membership
+        let ix = fx.enter_ix_with_proof("deposit", &alice.pubkey(), 500_000, &[bob_leaf]);
+        fx.send(ix, &[&alice]).unwrap();
+        let ix = fx.enter_ix_with_proof("mint", &bob.pubkey(), 500_000, &[alice_leaf]);
+        fx.send(ix, &[&bob]).unwrap();
+
+        //missing proof
+        let ix = fx.deposit_ix(&alice.pubkey(), 100_000);
+        assert_rejected(fx.send(ix, &[&alice]), Errors::NotAllowlisted);
+
+        //outsider reusing a valid proof
+        let ix = fx.enter_ix_with_proof("deposit", &mallory.pubkey(), 500_000, &[bob_leaf]);
+        assert_rejected(fx.send(ix, &[&mallory]), Errors::NotAllowlisted);
+
+        //zeroed root opens the vault again
+        fx.send_admin("set_allowlist_root", &[0; 32]).unwrap();
+        let ix = fx.deposit_ix(&mallory.pubkey(), 500_000);
+        fx.send(ix, &[&mallory]).unwrap();
+
+        assert_eq!(fx.vault_state().total_base_assets, 1_500_000);
+    }
+}
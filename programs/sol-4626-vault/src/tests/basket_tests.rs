@@ -0,0 +1,446 @@
+#[cfg(test)]
+mod test_basket {
+    use crate::constant::{BASKET_AUTHORITY_SEED, BASKET_SEED, PRICE_FEED_SEED, SHARES_MINT_SEED};
+    use crate::state::{Basket, PriceFeed};
+    use crate::tests::helper::{assert_rejected, to_address, VaultFixture};
+    use crate::util::Errors;
+    use crate::ID;
+    use anchor_lang::prelude::Pubkey;
+    use anchor_lang::{system_program, AccountDeserialize, Id};
+    use anchor_spl::associated_token::AssociatedToken;
+    use litesvm_token::{CreateAssociatedTokenAccount, CreateMint, MintTo};
+    use solana_sdk::message::{AccountMeta, Address, Instruction};
+    use solana_sdk::signature::{Keypair, Signer};
+
+    const EXIT_FEE_BPS: u16 = 100;
+    const MAX_PRICE_AGE: u32 = 60;
+    const MAX_CONF_BPS: u16 = 50;
+    //1% on every transfer of a fee-charging asset
+    const FEE_BPS: u16 = 100;
+    const MAX_FEE: u64 = 1_000_000_000;
+
+    /// Basket over two assets of a vault fixture's svm: A (6 decimals, 1.00) and
+    /// B (9 decimals, 2.50), priced by mock feeds.
+    struct BasketSetup {
+        fx: VaultFixture,
+        basket: Address,
+        basket_authority: Address,
+        shares_mint: Address,
+        mints: [Address; 2],
+        feeds: [Address; 2],
+    }
+
+    impl BasketSetup {
+        fn new() -> Self {
+            Self::with_fixture(VaultFixture::new(), None)
+        }
+
+        /// Basket whose asset A is the fixture's Token-2022 base asset, charging `FEE_BPS`
+        /// on every transfer.
+        fn with_transfer_fee() -> Self {
+            let fx = VaultFixture::with_transfer_fee(FEE_BPS, MAX_FEE);
+            let mint_a = fx.base_asset_mint;
+            Self::with_fixture(fx, Some(mint_a))
+        }
+
+        fn with_fixture(mut fx: VaultFixture, mint_a: Option<Address>) -> Self {
+            let admin = fx.admin.insecure_clone();
+            let (basket, _) = Pubkey::find_program_address(
+                &[
+                    BASKET_SEED.as_bytes(),
+                    admin.pubkey().as_ref(),
+                    &0u64.to_le_bytes(),
+                ],
+                &ID,
+            );
+            let (basket_authority, _) = Pubkey::find_program_address(
+                &[BASKET_AUTHORITY_SEED.as_bytes(), basket.as_ref()],
+                &ID,
+            );
+            let (shares_mint, _) = Pubkey::find_program_address(
+                &[SHARES_MINT_SEED.as_bytes(), basket_authority.as_ref()],
+                &ID,
+            );
+
+            let token_program = fx.token_program;
+            let mut create_mint = |decimals| {
+                CreateMint::new(&mut fx.svm, &fx.mint_authority)
+                    .decimals(decimals)
+                    .authority(&fx.mint_authority.pubkey())
+                    .token_program_id(&token_program)
+                    .send()
+                    .unwrap()
+            };
+            let mints = [mint_a.unwrap_or_else(|| create_mint(6)), create_mint(9)];
+            let feeds = [Keypair::new().pubkey(), Keypair::new().pubkey()];
+            fx.set_price_feed(&feeds[0], &mints[0], 100, 0, -2);
+            fx.set_price_feed(&feeds[1], &mints[1], 250, 0, -2);
+
+            let mut setup = Self {
+                fx,
+                basket: to_address(&basket),
+                basket_authority: to_address(&basket_authority),
+                shares_mint: to_address(&shares_mint),
+                mints,
+                feeds,
+            };
+
+            let mut args = 0u64.to_le_bytes().to_vec();
+            args.extend_from_slice(&EXIT_FEE_BPS.to_le_bytes());
+            args.extend_from_slice(&MAX_PRICE_AGE.to_le_bytes());
+            args.extend_from_slice(&MAX_CONF_BPS.to_le_bytes());
+            let ix = setup.fx.instruction(
+                "initialize_basket",
+                &args,
+                vec![
+                    AccountMeta::new(admin.pubkey(), true),
+                    AccountMeta::new_readonly(setup.fx.global_config, false),
+                    AccountMeta::new(setup.basket, false),
+                    AccountMeta::new_readonly(setup.basket_authority, false),
+                    AccountMeta::new(setup.shares_mint, false),
+                    AccountMeta::new_readonly(setup.fx.token_program, false),
+                    AccountMeta::new_readonly(to_address(&system_program::ID), false),
+                ],
+            );
+            setup.fx.send(ix, &[&admin]).unwrap();
+            for i in 0..2 {
+                let ix = setup.add_asset_ix(&setup.mints[i], &setup.feeds[i]);
+                setup.fx.send(ix, &[&admin]).unwrap();
+            }
+            setup
+        }
+
+        fn add_asset_ix(&self, mint: &Address, feed: &Address) -> Instruction {
+            self.fx.instruction(
+                "add_basket_asset",
+                &0u16.to_le_bytes(),
+                vec![
+                    AccountMeta::new(self.fx.admin.pubkey(), true),
+                    AccountMeta::new(self.basket, false),
+                    AccountMeta::new_readonly(self.basket_authority, false),
+                    AccountMeta::new_readonly(*mint, false),
+                    AccountMeta::new_readonly(*feed, false),
+                    AccountMeta::new(self.fx.ata(&self.basket_authority, mint), false),
+                    AccountMeta::new_readonly(self.fx.token_program, false),
+                    AccountMeta::new_readonly(to_address(&AssociatedToken::id()), false),
+                    AccountMeta::new_readonly(to_address(&system_program::ID), false),
+                ],
+            )
+        }
+
+        /// Basket ATA and price feed of every asset, in basket order.
+        fn valuation_accounts(&self) -> Vec<AccountMeta> {
+            self.feeds
+                .iter()
+                .map(|feed| AccountMeta::new_readonly(*feed, false))
+                .collect()
+        }
+
+        fn create_user(&mut self, amounts: [u64; 2]) -> Keypair {
+            let user = Keypair::new();
+            self.fx.svm.airdrop(&user.pubkey(), 1_000_000_000).unwrap();
+            for (mint, amount) in self.mints.iter().zip(amounts) {
+                let ata = CreateAssociatedTokenAccount::new(&mut self.fx.svm, &user, mint)
+                    .token_program_id(&self.fx.token_program)
+                    .send()
+                    .unwrap();
+                MintTo::new(
+                    &mut self.fx.svm,
+                    &self.fx.mint_authority,
+                    mint,
+                    &ata,
+                    amount,
+                )
+                .token_program_id(&self.fx.token_program)
+                .send()
+                .unwrap();
+            }
+            user
+        }
+
+        fn deposit_ix(&self, user: &Address, asset: usize, amount: u64) -> Instruction {
+            let mint = &self.mints[asset];
+            let mut accounts = vec![
+                AccountMeta::new(*user, true),
+                AccountMeta::new(self.basket, false),
+                AccountMeta::new_readonly(self.basket_authority, false),
+                AccountMeta::new_readonly(self.fx.global_config, false),
+                AccountMeta::new(self.shares_mint, false),
+                AccountMeta::new(self.fx.ata(user, &self.shares_mint), false),
+                AccountMeta::new_readonly(*mint, false),
+                AccountMeta::new(self.fx.ata(user, mint), false),
+                AccountMeta::new(self.fx.ata(&self.basket_authority, mint), false),
+                AccountMeta::new_readonly(self.fx.token_program, false),
+                AccountMeta::new_readonly(to_address(&AssociatedToken::id()), false),
+                AccountMeta::new_readonly(to_address(&system_program::ID), false),
+            ];
+            accounts.extend(self.valuation_accounts());
+            self.fx
+                .instruction("deposit_basket", &amount.to_le_bytes(), accounts)
+        }
+
+        fn redeem_asset_ix(&self, user: &Address, asset: usize, shares: u64) -> Instruction {
+            let mint = &self.mints[asset];
+            let mut accounts = vec![
+                AccountMeta::new(*user, true),
+                AccountMeta::new(self.basket, false),
+                AccountMeta::new_readonly(self.basket_authority, false),
+                AccountMeta::new(self.shares_mint, false),
+                AccountMeta::new(self.fx.ata(user, &self.shares_mint), false),
+                AccountMeta::new_readonly(*mint, false),
+                AccountMeta::new(self.fx.ata(&self.basket_authority, mint), false),
+                AccountMeta::new(self.fx.ata(user, mint), false),
+                AccountMeta::new_readonly(self.fx.token_program, false),
+                AccountMeta::new_readonly(to_address(&AssociatedToken::id()), false),
+                AccountMeta::new_readonly(to_address(&system_program::ID), false),
+            ];
+            accounts.extend(self.valuation_accounts());
+            self.fx
+                .instruction("redeem_basket_asset", &shares.to_le_bytes(), accounts)
+        }
+
+        fn redeem_ix(&self, user: &Address, shares: u64) -> Instruction {
+            let mut accounts = vec![
+                AccountMeta::new_readonly(*user, true),
+                AccountMeta::new(self.basket, false),
+                AccountMeta::new_readonly(self.basket_authority, false),
+                AccountMeta::new(self.shares_mint, false),
+                AccountMeta::new(self.fx.ata(user, &self.shares_mint), false),
+                AccountMeta::new_readonly(self.fx.token_program, false),
+            ];
+            for mint in &self.mints {
+                accounts.extend([
+                    AccountMeta::new_readonly(*mint, false),
+                    AccountMeta::new(self.fx.ata(&self.basket_authority, mint), false),
+                    AccountMeta::new(self.fx.ata(user, mint), false),
+                ]);
+            }
+            self.fx
+                .instruction("redeem_basket", &shares.to_le_bytes(), accounts)
+        }
+    }
+
+    #[test]
+    pub fn test_basket_deposit_and_exits() {
+        let mut setup = BasketSetup::new();
+        let acc = setup.fx.svm.get_account(&setup.basket).unwrap();
+        let basket = Basket::try_deserialize(&mut &acc.data[..]).unwrap();
+        assert_eq!(basket.assets.len(), 2);
+        assert_eq!(basket.exit_fee_bps, EXIT_FEE_BPS);
+
+        let user = setup.create_user([100_000_000, 10_000_000_000]);
+        let user_key = user.pubkey();
+        let user_shares = setup.fx.ata(&user_key, &setup.shares_mint);
+
+        //100 A worth 100.00, first deposit mints 1:1 with the value
+        let ix = setup.deposit_ix(&user_key, 0, 100_000_000);
+        setup.fx.send(ix, &[&user]).unwrap();
+        assert_eq!(setup.fx.token_balance(&user_shares), 100_000_000);
+
+        //10 B worth 25.00
+        let ix = setup.deposit_ix(&user_key, 1, 10_000_000_000);
+        setup.fx.send(ix, &[&user]).unwrap();
+        assert_eq!(setup.fx.token_balance(&user_shares), 125_000_000);
+
+        //B doubles: basket worth 150.00 for 125 shares
+        let feed = setup.feeds[1];
+        let mint = setup.mints[1];
+        setup.fx.set_price_feed(&feed, &mint, 500, 0, -2);
+
+        //25 shares worth 30.00, paid in A minus the 1% exit fee
+        let ix = setup.redeem_asset_ix(&user_key, 0, 25_000_000);
+        setup.fx.send(ix, &[&user]).unwrap();
+        let user_a = setup.fx.ata(&user_key, &setup.mints[0]);
+        assert_eq!(setup.fx.token_balance(&user_a), 29_700_000);
+
+        //the last shares take everything left in kind
+        let ix = setup.redeem_ix(&user_key, 100_000_000);
+        setup.fx.send(ix, &[&user]).unwrap();
+        assert_eq!(setup.fx.token_balance(&user_shares), 0);
+        assert_eq!(setup.fx.token_balance(&user_a), 100_000_000);
+        let user_b = setup.fx.ata(&user_key, &setup.mints[1]);
+        assert_eq!(setup.fx.token_balance(&user_b), 10_000_000_000);
+    }
+
+    #[test]
+    pub fn test_basket_ignores_donations() {
+        let mut setup = BasketSetup::new();
+        let first = setup.create_user([1, 0]);
+        let first_key = first.pubkey();
+        let ix = setup.deposit_ix(&first_key, 0, 1);
+        setup.fx.send(ix, &[&first]).unwrap();
+
+        //tokens sent straight to the basket ATA are not valued
+        let mint = setup.mints[0];
+        let basket_a = setup.fx.ata(&setup.basket_authority, &mint);
+        MintTo::new(
+            &mut setup.fx.svm,
+            &setup.fx.mint_authority,
+            &mint,
+            &basket_a,
+            1_000_000_000,
+        )
+        .send()
+        .unwrap();
+
+        let user = setup.create_user([100_000_000, 0]);
+        let user_key = user.pubkey();
+        let ix = setup.deposit_ix(&user_key, 0, 100_000_000);
+        setup.fx.send(ix, &[&user]).unwrap();
+        let user_shares = setup.fx.ata(&user_key, &setup.shares_mint);
+        assert_eq!(setup.fx.token_balance(&user_shares), 100_000_000);
+
+        let acc = setup.fx.svm.get_account(&setup.basket).unwrap();
+        let basket = Basket::try_deserialize(&mut &acc.data[..]).unwrap();
+        assert_eq!(basket.assets[0].balance, 100_000_001);
+
+        //nor paid out
+        let ix = setup.redeem_ix(&user_key, 100_000_000);
+        setup.fx.send(ix, &[&user]).unwrap();
+        let user_a = setup.fx.ata(&user_key, &mint);
+        assert_eq!(setup.fx.token_balance(&user_a), 100_000_000);
+    }
+
+    #[test]
+    pub fn test_basket_deposit_priced_net_of_transfer_fee() {
+        let mut setup = BasketSetup::with_transfer_fee();
+        let user = setup.create_user([100_000_000, 0]);
+        let user_key = user.pubkey();
+
+        //the basket only gets 99 A out of the 100 sent
+        let ix = setup.deposit_ix(&user_key, 0, 100_000_000);
+        setup.fx.send(ix, &[&user]).unwrap();
+        let user_shares = setup.fx.ata(&user_key, &setup.shares_mint);
+        assert_eq!(setup.fx.token_balance(&user_shares), 99_000_000);
+
+        let acc = setup.fx.svm.get_account(&setup.basket).unwrap();
+        let basket = Basket::try_deserialize(&mut &acc.data[..]).unwrap();
+        let basket_a = setup.fx.ata(&setup.basket_authority, &setup.mints[0]);
+        assert_eq!(basket.assets[0].balance, 99_000_000);
+        assert_eq!(setup.fx.token_balance(&basket_a), 99_000_000);
+    }
+
+    #[test]
+    pub fn test_basket_rejects_mismatched_accounts() {
+        let mut setup = BasketSetup::new();
+        let user = setup.create_user([100_000_000, 0]);
+        let user_key = user.pubkey();
+
+        //price feeds swapped
+        let mut ix = setup.deposit_ix(&user_key, 0, 100_000_000);
+        let len = ix.accounts.len();
+        ix.accounts.swap(len - 2, len - 1);
+        assert_rejected(setup.fx.send(ix, &[&user]), Errors::InvalidBasketAccounts);
+
+        //missing valuation accounts
+        let mut ix = setup.deposit_ix(&user_key, 0, 100_000_000);
+        ix.accounts.truncate(len - 1);
+        assert_rejected(setup.fx.send(ix, &[&user]), Errors::InvalidBasketAccounts);
+
+        //assets are whitelisted once
+        let admin = setup.fx.admin.insecure_clone();
+        let ix = setup.add_asset_ix(&setup.mints[0], &setup.feeds[0]);
+        assert_rejected(setup.fx.send(ix, &[&admin]), Errors::AssetAlreadyInBasket);
+    }
+
+    #[test]
+    pub fn test_basket_oracle_checks() {
+        let mut setup = BasketSetup::new();
+        setup.fx.set_unix_timestamp(1_000);
+        let (feeds, mints) = (setup.feeds, setup.mints);
+        setup.fx.set_price_feed(&feeds[0], &mints[0], 100, 0, -2);
+        //B priced by a Pyth-style account: 2.50 +- 0.001
+        setup
+            .fx
+            .set_pyth_price(&feeds[1], 250_000_000, 100_000, -8, 1_000);
+
+        let user = setup.create_user([200_000_000, 10_000_000_000]);
+        let user_key = user.pubkey();
+        let user_shares = setup.fx.ata(&user_key, &setup.shares_mint);
+        let ix = setup.deposit_ix(&user_key, 0, 100_000_000);
+        setup.fx.send(ix, &[&user]).unwrap();
+        let ix = setup.deposit_ix(&user_key, 1, 10_000_000_000);
+        setup.fx.send(ix, &[&user]).unwrap();
+        assert_eq!(setup.fx.token_balance(&user_shares), 125_000_000);
+
+        //every price valuing the basket must be fresh
+        setup
+            .fx
+            .set_unix_timestamp(1_000 + MAX_PRICE_AGE as i64 + 1);
+        setup.fx.set_price_feed(&feeds[0], &mints[0], 100, 0, -2);
+        let ix = setup.deposit_ix(&user_key, 0, 50_000_000);
+        assert_rejected(setup.fx.send(ix, &[&user]), Errors::StalePrice);
+
+        //and tight: 0.02 on 2.50 is 80 bps
+        let now = 1_000 + MAX_PRICE_AGE as i64 + 1;
+        setup
+            .fx
+            .set_pyth_price(&feeds[1], 250_000_000, 2_000_000, -8, now);
+        let ix = setup.deposit_ix(&user_key, 0, 50_000_000);
+        assert_rejected(setup.fx.send(ix, &[&user]), Errors::PriceConfidenceTooWide);
+
+        setup
+            .fx
+            .set_pyth_price(&feeds[1], 250_000_000, 100_000, -8, now);
+        let ix = setup.deposit_ix(&user_key, 0, 50_000_000);
+        setup.fx.send(ix, &[&user]).unwrap();
+        assert_eq!(setup.fx.token_balance(&user_shares), 175_000_000);
+    }
+
+    #[test]
+    pub fn test_price_feed_must_price_the_asset() {
+        let mut setup = BasketSetup::new();
+        let admin = setup.fx.admin.insecure_clone();
+        let mint_a = setup.mints[0];
+        let (feed, _) = Pubkey::find_program_address(
+            &[
+                PRICE_FEED_SEED.as_bytes(),
+                admin.pubkey().as_ref(),
+                mint_a.as_ref(),
+            ],
+            &ID,
+        );
+        let feed = to_address(&feed);
+
+        //admin-pushed feed of A
+        let ix = setup.fx.instruction(
+            "initialize_price_feed",
+            &[],
+            vec![
+                AccountMeta::new(admin.pubkey(), true),
+                AccountMeta::new_readonly(mint_a, false),
+                AccountMeta::new(feed, false),
+                AccountMeta::new_readonly(to_address(&system_program::ID), false),
+            ],
+        );
+        setup.fx.send(ix, &[&admin]).unwrap();
+        let mut args = 101i64.to_le_bytes().to_vec();
+        args.extend_from_slice(&0u64.to_le_bytes());
+        args.extend_from_slice(&(-2i32).to_le_bytes());
+        let ix = setup.fx.instruction(
+            "update_price_feed",
+            &args,
+            vec![
+                AccountMeta::new_readonly(admin.pubkey(), true),
+                AccountMeta::new(feed, false),
+            ],
+        );
+        setup.fx.send(ix, &[&admin]).unwrap();
+        let acc = setup.fx.svm.get_account(&feed).unwrap();
+        let pushed = PriceFeed::try_deserialize(&mut &acc.data[..]).unwrap();
+        assert_eq!((pushed.price, pushed.expo), (101, -2));
+
+        //a third asset can't be valued with A's feed, a Pyth-style account records no mint
+        let mint_c = CreateMint::new(&mut setup.fx.svm, &setup.fx.mint_authority)
+            .decimals(6)
+            .authority(&setup.fx.mint_authority.pubkey())
+            .send()
+            .unwrap();
+        let ix = setup.add_asset_ix(&mint_c, &feed);
+        assert_rejected(setup.fx.send(ix, &[&admin]), Errors::InvalidPriceFeed);
+        let pyth = Keypair::new().pubkey();
+        setup.fx.set_pyth_price(&pyth, 100_000_000, 0, -8, 0);
+        let ix = setup.add_asset_ix(&mint_c, &pyth);
+        setup.fx.send(ix, &[&admin]).unwrap();
+    }
+}
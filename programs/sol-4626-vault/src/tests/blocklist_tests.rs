@@ -0,0 +1,196 @@
+#[cfg(test)]
+mod test_blocklist {
+    use crate::tests::helper::{assert_rejected, ata, to_address, VaultFixture};
+    use crate::util::Errors;
+    use anchor_lang::error::ErrorCode;
+    use anchor_lang::system_program;
+    use litesvm_token::spl_token::error::TokenError;
+    use litesvm_token::spl_token::state::{Account as TokenAccount, AccountState};
+    use litesvm_token::{get_spl_account, CreateAssociatedTokenAccount, Transfer};
+    use solana_sdk::instruction::InstructionError;
+    use solana_sdk::message::{AccountMeta, Address, Instruction};
+    use solana_sdk::signature::{Keypair, Signer};
+    use solana_sdk::transaction::TransactionError;
+
+    fn block_ix(fx: &VaultFixture, authority: &Address, wallet: &Address) -> Instruction {
+        fx.instruction(
+            "block_address",
+            wallet.as_ref(),
+            vec![
+                AccountMeta::new(*authority, true),
+                AccountMeta::new_readonly(fx.vault, false),
+                AccountMeta::new(fx.blocked_address(wallet), false),
+                AccountMeta::new_readonly(to_address(&system_program::ID), false),
+            ],
+        )
+    }
+
+    fn unblock_ix(fx: &VaultFixture, wallet: &Address) -> Instruction {
+        fx.instruction(
+            "unblock_address",
+            &[],
+            vec![
+                AccountMeta::new(fx.admin.pubkey(), true),
+                AccountMeta::new_readonly(fx.vault, false),
+                AccountMeta::new(fx.blocked_address(wallet), false),
+            ],
+        )
+    }
+
+    fn set_frozen_ix(fx: &VaultFixture, name: &str, holder: &Address) -> Instruction {
+        fx.instruction(
+            name,
+            &[],
+            vec![
+                AccountMeta::new_readonly(fx.admin.pubkey(), true),
+                AccountMeta::new_readonly(fx.vault, false),
+                AccountMeta::new_readonly(fx.vault_authority, false),
+                AccountMeta::new_readonly(fx.shares_mint, false),
+                AccountMeta::new(ata(holder, &fx.shares_mint), false),
+                AccountMeta::new_readonly(fx.blocked_address(holder), false),
+                AccountMeta::new_readonly(fx.shares_token_program, false),
+            ],
+        )
+    }
+
+    #[test]
+    pub fn test_blocked_wallet_is_refused() {
+        let mut fx = VaultFixture::new();
+        let admin = fx.admin.insecure_clone();
+        let user = fx.create_depositor(1_000_000);
+        let user_key = user.pubkey();
+
+        let ix = fx.deposit_ix(&user_key, 500_000);
+        fx.send(ix, &[&user]).unwrap();
+
+        //only the compliance authority can block
+        let stranger = Keypair::new();
+        fx.svm.airdrop(&stranger.pubkey(), 1_000_000_000).unwrap();
+        let ix = block_ix(&fx, &stranger.pubkey(), &user_key);
+        assert_rejected(fx.send(ix, &[&stranger]), ErrorCode::ConstraintHasOne);
+
+        let ix = block_ix(&fx, &admin.pubkey(), &user_key);
+        fx.send(ix, &[&admin]).unwrap();
+
+        //every entry and exit path rejects the blocked wallet
+        let ix = fx.deposit_ix(&user_key, 100_000);
+        assert_rejected(fx.send(ix, &[&user]), Errors::AddressBlocked);
+        let ix = fx.enter_ix("mint", &user_key, 100_000);
+        assert_rejected(fx.send(ix, &[&user]), Errors::AddressBlocked);
+        let ix = fx.exit_ix("withdraw", &user_key, 100_000);
+        assert_rejected(fx.send(ix, &[&user]), Errors::AddressBlocked);
+        let ix = fx.exit_ix("redeem", &user_key, 100_000);
+        assert_rejected(fx.send(ix, &[&user]), Errors::AddressBlocked);
+
+        //freeze the blocked holder's shares, transfers are now rejected
+        let ix = set_frozen_ix(&fx, "freeze_holder", &user_key);
+        fx.send(ix, &[&admin]).unwrap();
+        let shares_ata = ata(&user_key, &fx.shares_mint);
+        let acc = get_spl_account::<TokenAccount>(&fx.svm, &shares_ata).unwrap();
+        assert_eq!(acc.state, AccountState::Frozen);
+        let shares_mint = fx.shares_mint;
+        let admin_shares = CreateAssociatedTokenAccount::new(&mut fx.svm, &admin, &shares_mint)
+            .send()
+            .unwrap();
+        let frozen = Transfer::new(&mut fx.svm, &user, &shares_mint, &admin_shares, 1)
+            .owner(&user)
+            .source(&shares_ata)
+            .send()
+            .unwrap_err();
+        assert_eq!(
+            frozen.err,
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(TokenError::AccountFrozen as u32)
+            )
+        );
+
+        //thaw requires the block to be lifted first
+        let ix = set_frozen_ix(&fx, "thaw_holder", &user_key);
+        assert_rejected(fx.send(ix, &[&admin]), Errors::AddressBlocked);
+
+        let ix = unblock_ix(&fx, &user_key);
+        fx.send(ix, &[&admin]).unwrap();
+        let ix = set_frozen_ix(&fx, "thaw_holder", &user_key);
+        fx.send(ix, &[&admin]).unwrap();
+
+        let ix = fx.exit_ix("redeem", &user_key, 500_000);
+        fx.send(ix, &[&user]).unwrap();
+        assert_eq!(
+            fx.token_balance(&ata(&user_key, &fx.base_asset_mint)),
+            1_000_000
+        );
+    }
+
+    #[test]
+    pub fn test_freeze_requires_block() {
+        let mut fx = VaultFixture::new();
+        let admin = fx.admin.insecure_clone();
+        let user = fx.create_depositor(1_000_000);
+
+        let ix = fx.deposit_ix(&user.pubkey(), 500_000);
+        fx.send(ix, &[&user]).unwrap();
+
+        let ix = set_frozen_ix(&fx, "freeze_holder", &user.pubkey());
+        assert_rejected(fx.send(ix, &[&admin]), Errors::AddressNotBlocked);
+    }
+
+    #[test]
+    pub fn test_blocked_owner_cannot_claim_redeem() {
+        let mut fx = VaultFixture::new();
+        let admin = fx.admin.insecure_clone();
+        let user = fx.create_depositor(1_000_000);
+        let user_key = user.pubkey();
+
+        let ix = fx.deposit_ix(&user_key, 1_000_000);
+        fx.send(ix, &[&user]).unwrap();
+        let ix = fx.request_redeem_ix(&user_key, 400_000);
+        fx.send(ix, &[&user]).unwrap();
+        let ix = fx.fulfill_redeem_ix(&user_key);
+        fx.send(ix, &[&admin]).unwrap();
+
+        //blocked after the request
+        let ix = block_ix(&fx, &admin.pubkey(), &user_key);
+        fx.send(ix, &[&admin]).unwrap();
+        let ix = fx.claim_redeem_ix(&user_key);
+        assert_rejected(fx.send(ix, &[&user]), Errors::AddressBlocked);
+
+        let ix = unblock_ix(&fx, &user_key);
+        fx.send(ix, &[&admin]).unwrap();
+        let ix = fx.claim_redeem_ix(&user_key);
+        fx.send(ix, &[&user]).unwrap();
+        assert_eq!(
+            fx.token_balance(&ata(&user_key, &fx.base_asset_mint)),
+            400_000
+        );
+    }
+
+    #[test]
+    pub fn test_blocked_owner_cannot_claim_epoch() {
+        let mut fx = VaultFixture::new();
+        fx.set_unix_timestamp(1_000);
+        let admin = fx.admin.insecure_clone();
+        fx.send_admin("set_epoch_duration", &3_600i64.to_le_bytes())
+            .unwrap();
+
+        let user = fx.create_depositor(500_000);
+        let user_key = user.pubkey();
+        let ix = fx.queue_deposit_ix(&user_key, 500_000);
+        fx.send(ix, &[&user]).unwrap();
+        fx.set_unix_timestamp(4_600);
+        let ix = fx.close_epoch_ix(&admin.pubkey());
+        fx.send(ix, &[&admin]).unwrap();
+
+        //blocked after queueing
+        let ix = block_ix(&fx, &admin.pubkey(), &user_key);
+        fx.send(ix, &[&admin]).unwrap();
+        let ix = fx.claim_epoch_ix(&user_key, 0);
+        assert_rejected(fx.send(ix, &[&user]), Errors::AddressBlocked);
+
+        let ix = unblock_ix(&fx, &user_key);
+        fx.send(ix, &[&admin]).unwrap();
+        let ix = fx.claim_epoch_ix(&user_key, 0);
+        fx.send(ix, &[&user]).unwrap();
+        assert_eq!(fx.token_balance(&ata(&user_key, &fx.shares_mint)), 500_000);
+    }
+}
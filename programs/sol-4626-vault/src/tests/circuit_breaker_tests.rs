@@ -0,0 +1,113 @@
+#[cfg(test)]
+mod test_circuit_breaker {
+    use crate::tests::helper::{assert_rejected, VaultFixture};
+    use crate::util::Errors;
+    use anchor_lang::error::ErrorCode;
+    use litesvm::types::{FailedTransactionMetadata, TransactionMetadata};
+    use solana_sdk::signature::{Keypair, Signer};
+
+    fn send_report(
+        fx: &mut VaultFixture,
+        valuation: &Keypair,
+        submitter: &Keypair,
+        nav: u64,
+        timestamp: i64,
+        sequence: u64,
+    ) -> Result<TransactionMetadata, Box<FailedTransactionMetadata>> {
+        let report = fx.nav_report(nav, timestamp, sequence);
+        let ixs = fx.update_nav_ixs(valuation, &submitter.pubkey(), &report);
+        fx.send_all(&ixs, &[submitter])
+    }
+
+    fn set_circuit_breaker(
+        fx: &mut VaultFixture,
+        report_bps: u16,
+        window_bps: u16,
+        window: i64,
+        pause: bool,
+    ) {
+        let mut args = report_bps.to_le_bytes().to_vec();
+        args.extend_from_slice(&window_bps.to_le_bytes());
+        args.extend_from_slice(&window.to_le_bytes());
+        args.push(pause as u8);
+        fx.send_admin("set_circuit_breaker", &args).unwrap();
+    }
+
+    fn setup() -> (VaultFixture, Keypair, Keypair) {
+        let mut fx = VaultFixture::new();
+        fx.set_unix_timestamp(1_000);
+
+        let user = fx.create_depositor(2_000_000);
+        let ix = fx.deposit_ix(&user.pubkey(), 1_000_000);
+        fx.send(ix, &[&user]).unwrap();
+
+        let valuation = Keypair::new();
+        fx.set_valuation_signer(&valuation, 0, 86_400).unwrap();
+
+        (fx, user, valuation)
+    }
+
+    #[test]
+    pub fn test_breach_reverts_report() {
+        let (mut fx, user, valuation) = setup();
+        //5% per report, 8% per hour
+        set_circuit_breaker(&mut fx, 500, 800, 3_600, false);
+
+        assert_rejected(
+            send_report(&mut fx, &valuation, &user, 1_060_000, 1_000, 1),
+            Errors::CircuitBreakerTripped,
+        );
+        send_report(&mut fx, &valuation, &user, 1_050_000, 1_000, 1).unwrap();
+
+        //within 5% of the last report but 8.15% over the window
+        fx.set_unix_timestamp(2_000);
+        assert_rejected(
+            send_report(&mut fx, &valuation, &user, 1_081_500, 2_000, 2),
+            Errors::CircuitBreakerTripped,
+        );
+        send_report(&mut fx, &valuation, &user, 1_080_000, 2_000, 2).unwrap();
+
+        //next window starts at 1.08
+        fx.set_unix_timestamp(4_600);
+        send_report(&mut fx, &valuation, &user, 1_130_000, 4_600, 3).unwrap();
+        assert_eq!(fx.vault_state().total_base_assets, 1_130_000);
+        assert!(!fx.vault_state().breaker_tripped.is_true());
+    }
+
+    #[test]
+    pub fn test_breach_pauses_until_guardian_reset() {
+        let (mut fx, user, valuation) = setup();
+        let guardian = Keypair::new();
+        fx.send_admin("set_guardian", guardian.pubkey().as_ref())
+            .unwrap();
+        set_circuit_breaker(&mut fx, 500, 0, 0, true);
+
+        //applied, then deposits and withdrawals paused
+        send_report(&mut fx, &valuation, &user, 800_000, 1_000, 1).unwrap();
+        let vlt = fx.vault_state();
+        assert_eq!(vlt.total_base_assets, 800_000);
+        assert!(vlt.breaker_tripped.is_true());
+
+        let ix = fx.deposit_ix(&user.pubkey(), 100_000);
+        assert_rejected(fx.send(ix, &[&user]), Errors::CircuitBreakerTripped);
+        let ix = fx.exit_ix("redeem", &user.pubkey(), 100_000);
+        assert_rejected(fx.send(ix, &[&user]), Errors::CircuitBreakerTripped);
+
+        //only the guardian resets
+        let admin = fx.admin.insecure_clone();
+        let ix = fx.reset_circuit_breaker_ix(&admin.pubkey());
+        assert_rejected(fx.send(ix, &[&admin]), ErrorCode::ConstraintHasOne);
+        let ix = fx.reset_circuit_breaker_ix(&guardian.pubkey());
+        fx.send(ix, &[&guardian]).unwrap();
+        assert!(!fx.vault_state().breaker_tripped.is_true());
+
+        let ix = fx.exit_ix("redeem", &user.pubkey(), 100_000);
+        fx.send(ix, &[&user]).unwrap();
+        let ix = fx.deposit_ix(&user.pubkey(), 100_000);
+        fx.send(ix, &[&user]).unwrap();
+
+        //nothing to reset
+        let ix = fx.reset_circuit_breaker_ix(&guardian.pubkey());
+        assert_rejected(fx.send(ix, &[&guardian]), Errors::CircuitBreakerNotTripped);
+    }
+}
@@ -0,0 +1,67 @@
+#[cfg(test)]
+mod test_deposit_caps {
+    use crate::state::UserPosition;
+    use crate::tests::helper::{assert_rejected, ata, VaultFixture};
+    use crate::util::Errors;
+    use anchor_lang::AccountDeserialize;
+    use solana_sdk::signature::Signer;
+
+    fn caps_args(deposit_cap: u64, user_deposit_cap: u64) -> Vec<u8> {
+        [deposit_cap.to_le_bytes(), user_deposit_cap.to_le_bytes()].concat()
+    }
+
+    #[test]
+    pub fn test_user_deposit_cap() {
+        let mut fx = VaultFixture::new();
+        fx.send_admin("set_deposit_caps", &caps_args(0, 1_000_000))
+            .unwrap();
+
+        let user = fx.create_depositor(2_000_000);
+        let user_key = user.pubkey();
+
+        let ix = fx.deposit_ix(&user_key, 800_000);
+        fx.send(ix, &[&user]).unwrap();
+
+        //above per-user cap
+        let ix = fx.deposit_ix(&user_key, 300_000);
+        assert_rejected(fx.send(ix, &[&user]), Errors::UserDepositCapExceeded);
+
+        //mint path is capped as well
+        let ix = fx.enter_ix("mint", &user_key, 300_000);
+        assert_rejected(fx.send(ix, &[&user]), Errors::UserDepositCapExceeded);
+
+        //withdrawing frees headroom
+        let ix = fx.exit_ix("withdraw", &user_key, 200_000);
+        fx.send(ix, &[&user]).unwrap();
+        let ix = fx.enter_ix("mint", &user_key, 400_000);
+        fx.send(ix, &[&user]).unwrap();
+
+        let position_acc = fx.svm.get_account(&fx.user_position(&user_key)).unwrap();
+        let position = UserPosition::try_deserialize(&mut position_acc.data.as_slice()).unwrap();
+        assert_eq!(position.deposited_assets, 1_000_000);
+        assert_eq!(
+            fx.token_balance(&ata(&user_key, &fx.shares_mint)),
+            1_000_000
+        );
+    }
+
+    #[test]
+    pub fn test_vault_deposit_cap() {
+        let mut fx = VaultFixture::new();
+        fx.send_admin("set_deposit_caps", &caps_args(1_500_000, 0))
+            .unwrap();
+
+        let alice = fx.create_depositor(1_000_000);
+        let bob = fx.create_depositor(1_000_000);
+
+        let ix = fx.deposit_ix(&alice.pubkey(), 1_000_000);
+        fx.send(ix, &[&alice]).unwrap();
+
+        let ix = fx.deposit_ix(&bob.pubkey(), 600_000);
+        assert_rejected(fx.send(ix, &[&bob]), Errors::DepositCapExceeded);
+
+        let ix = fx.deposit_ix(&bob.pubkey(), 500_000);
+        fx.send(ix, &[&bob]).unwrap();
+        assert_eq!(fx.vault_state().total_base_assets, 1_500_000);
+    }
+}
@@ -0,0 +1,154 @@
+#[cfg(test)]
+mod test_epoch {
+    use crate::tests::helper::{assert_rejected, ata, VaultFixture};
+    use crate::util::Errors;
+    use anchor_lang::error::ErrorCode;
+    use solana_sdk::signature::{Keypair, Signer};
+
+    #[test]
+    pub fn test_epoch_settles_at_single_price() {
+        let mut fx = VaultFixture::new();
+        fx.set_unix_timestamp(1_000);
+
+        //existing holder seeds the vault before epochs are enabled
+        let holder = fx.create_depositor(1_000_000);
+        let holder_key = holder.pubkey();
+        let ix = fx.deposit_ix(&holder_key, 1_000_000);
+        fx.send(ix, &[&holder]).unwrap();
+        fx.send_admin("set_epoch_duration", &3_600i64.to_le_bytes())
+            .unwrap();
+
+        //two depositors and the holder queue into epoch 0
+        let alice = fx.create_depositor(500_000);
+        let bob = fx.create_depositor(300_000);
+        let ix = fx.queue_deposit_ix(&alice.pubkey(), 500_000);
+        fx.send(ix, &[&alice]).unwrap();
+        let ix = fx.queue_deposit_ix(&bob.pubkey(), 300_000);
+        fx.send(ix, &[&bob]).unwrap();
+        let ix = fx.queue_redeem_ix(&holder_key, 400_000);
+        fx.send(ix, &[&holder]).unwrap();
+
+        //queued assets stay out of the share price
+        let vlt = fx.vault_state();
+        assert_eq!(vlt.total_base_assets, 1_000_000);
+        assert_eq!(vlt.reserved_assets, 800_000);
+
+        //anyone can crank, but only once the epoch has run its duration
+        let cranker = Keypair::new();
+        fx.svm.airdrop(&cranker.pubkey(), 1_000_000_000).unwrap();
+        let ix = fx.close_epoch_ix(&cranker.pubkey());
+        assert_rejected(fx.send(ix, &[&cranker]), Errors::EpochNotEnded);
+
+        fx.set_unix_timestamp(4_600);
+        let ix = fx.close_epoch_ix(&cranker.pubkey());
+        fx.send(ix, &[&cranker]).unwrap();
+
+        let vlt = fx.vault_state();
+        assert_eq!(vlt.current_epoch, 1);
+        assert_eq!(vlt.epoch_started_at, 4_600);
+        assert_eq!(vlt.total_base_assets, 1_400_000);
+        assert_eq!(vlt.reserved_assets, 400_000);
+
+        //everyone claims at the 1:1 price of the close
+        for (user, shares, assets) in [
+            (&alice, 500_000, 0),
+            (&bob, 300_000, 0),
+            (&holder, 600_000, 400_000),
+        ] {
+            let ix = fx.claim_epoch_ix(&user.pubkey(), 0);
+            fx.send(ix, &[user]).unwrap();
+            assert_eq!(
+                fx.token_balance(&ata(&user.pubkey(), &fx.shares_mint)),
+                shares
+            );
+            assert_eq!(
+                fx.token_balance(&ata(&user.pubkey(), &fx.base_asset_mint)),
+                assets
+            );
+        }
+        assert_eq!(fx.vault_state().reserved_assets, 0);
+
+        //receipts are closed on claim
+        let ix = fx.claim_epoch_ix(&alice.pubkey(), 0);
+        assert_rejected(fx.send(ix, &[&alice]), ErrorCode::AccountNotInitialized);
+    }
+
+    #[test]
+    pub fn test_queue_requires_epochs() {
+        let mut fx = VaultFixture::new();
+        let user = fx.create_depositor(500_000);
+
+        let ix = fx.queue_deposit_ix(&user.pubkey(), 500_000);
+        assert_rejected(fx.send(ix, &[&user]), Errors::EpochsDisabled);
+    }
+
+    #[test]
+    pub fn test_epochs_reject_sync_entry_and_exit() {
+        let mut fx = VaultFixture::new();
+        let user = fx.create_depositor(1_000_000);
+        let ix = fx.deposit_ix(&user.pubkey(), 500_000);
+        fx.send(ix, &[&user]).unwrap();
+
+        fx.send_admin("set_epoch_duration", &3_600i64.to_le_bytes())
+            .unwrap();
+
+        //no live-price flows beside the epoch queue
+        let ix = fx.deposit_ix(&user.pubkey(), 100_000);
+        assert_rejected(fx.send(ix, &[&user]), Errors::EpochsEnabled);
+        let ix = fx.exit_ix("redeem", &user.pubkey(), 100_000);
+        assert_rejected(fx.send(ix, &[&user]), Errors::EpochsEnabled);
+
+        let ix = fx.queue_deposit_ix(&user.pubkey(), 100_000);
+        fx.send(ix, &[&user]).unwrap();
+        let ix = fx.queue_redeem_ix(&user.pubkey(), 100_000);
+        fx.send(ix, &[&user]).unwrap();
+
+        //back to sync flows once disabled
+        fx.send_admin("set_epoch_duration", &0i64.to_le_bytes())
+            .unwrap();
+        let ix = fx.exit_ix("redeem", &user.pubkey(), 100_000);
+        fx.send(ix, &[&user]).unwrap();
+    }
+
+    #[test]
+    pub fn test_epoch_closes_with_zero_assets() {
+        let mut fx = VaultFixture::new();
+        fx.set_unix_timestamp(1_000);
+        let holder = fx.create_depositor(1_000_000);
+        let ix = fx.deposit_ix(&holder.pubkey(), 1_000_000);
+        fx.send(ix, &[&holder]).unwrap();
+        fx.send_admin("set_epoch_duration", &3_600i64.to_le_bytes())
+            .unwrap();
+
+        let alice = fx.create_depositor(500_000);
+        let ix = fx.queue_deposit_ix(&alice.pubkey(), 500_000);
+        fx.send(ix, &[&alice]).unwrap();
+        let ix = fx.queue_redeem_ix(&holder.pubkey(), 400_000);
+        fx.send(ix, &[&holder]).unwrap();
+
+        //the whole vault is reported lost
+        let valuation = Keypair::new();
+        fx.set_valuation_signer(&valuation, 0, 3_600).unwrap();
+        let report = fx.nav_report(0, 1_000, 1);
+        let ixs = fx.update_nav_ixs(&valuation, &holder.pubkey(), &report);
+        fx.send_all(&ixs, &[&holder]).unwrap();
+
+        //redeems settle at 0, deposits are refunded
+        fx.set_unix_timestamp(4_600);
+        let ix = fx.close_epoch_ix(&holder.pubkey());
+        fx.send(ix, &[&holder]).unwrap();
+        let vlt = fx.vault_state();
+        assert_eq!(vlt.current_epoch, 1);
+        assert_eq!(vlt.total_base_assets, 0);
+        assert_eq!(vlt.reserved_assets, 500_000);
+
+        let ix = fx.claim_epoch_ix(&alice.pubkey(), 0);
+        fx.send(ix, &[&alice]).unwrap();
+        assert_eq!(
+            fx.token_balance(&ata(&alice.pubkey(), &fx.base_asset_mint)),
+            500_000
+        );
+        assert_eq!(fx.token_balance(&ata(&alice.pubkey(), &fx.shares_mint)), 0);
+        assert_eq!(fx.vault_state().reserved_assets, 0);
+    }
+}
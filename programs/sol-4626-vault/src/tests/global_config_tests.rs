@@ -0,0 +1,126 @@
+#[cfg(test)]
+mod test_global_config {
+    use crate::tests::helper::{
+        add_upgradeable_program, assert_rejected, initialize_global_config_ix, VaultFixture,
+    };
+    use crate::util::Errors;
+    use anchor_lang::error::ErrorCode;
+    use litesvm::LiteSVM;
+    use solana_sdk::native_token::LAMPORTS_PER_SOL;
+    use solana_sdk::signature::{Keypair, Signer};
+    use solana_sdk::transaction::Transaction;
+
+    fn send_protocol_admin(fx: &mut VaultFixture, name: &str, args: &[u8]) {
+        let protocol_admin = fx.protocol_admin.insecure_clone();
+        let ix = fx.protocol_admin_ix(&protocol_admin.pubkey(), name, args);
+        fx.send(ix, &[&protocol_admin]).unwrap();
+    }
+
+    #[test]
+    pub fn test_only_upgrade_authority_initializes() {
+        let mut svm = LiteSVM::new();
+        let deployer = Keypair::new();
+        let attacker = Keypair::new();
+        for signer in [&deployer, &attacker] {
+            svm.airdrop(&signer.pubkey(), LAMPORTS_PER_SOL).unwrap();
+        }
+        add_upgradeable_program(&mut svm, &deployer.pubkey());
+
+        //front-running the deployer is refused
+        for signer in [&attacker, &deployer] {
+            let tx = Transaction::new_signed_with_payer(
+                &[initialize_global_config_ix(
+                    &signer.pubkey(),
+                    &signer.pubkey(),
+                    0,
+                )],
+                Some(&signer.pubkey()),
+                &[signer],
+                svm.latest_blockhash(),
+            );
+            let result = svm.send_transaction(tx).map_err(Box::new);
+            if signer.pubkey() == attacker.pubkey() {
+                assert_rejected(result, ErrorCode::ConstraintRaw);
+            } else {
+                result.unwrap();
+            }
+        }
+    }
+
+    #[test]
+    pub fn test_global_pause_blocks_deposits_not_exits() {
+        let mut fx = VaultFixture::new();
+
+        let user = fx.create_depositor(1_000_000);
+        let user_key = user.pubkey();
+        let ix = fx.deposit_ix(&user_key, 500_000);
+        fx.send(ix, &[&user]).unwrap();
+
+        send_protocol_admin(&mut fx, "set_global_pause", &[1]);
+
+        let ix = fx.deposit_ix(&user_key, 100_000);
+        assert_rejected(fx.send(ix, &[&user]), Errors::ProtocolPaused);
+        let ix = fx.enter_ix("mint", &user_key, 100_000);
+        assert_rejected(fx.send(ix, &[&user]), Errors::ProtocolPaused);
+        let ix = fx.exit_ix("withdraw", &user_key, 200_000);
+        fx.send(ix, &[&user]).unwrap();
+
+        send_protocol_admin(&mut fx, "set_global_pause", &[0]);
+
+        let ix = fx.deposit_ix(&user_key, 100_000);
+        fx.send(ix, &[&user]).unwrap();
+        assert_eq!(fx.vault_state().total_base_assets, 400_000);
+    }
+
+    #[test]
+    pub fn test_only_protocol_admin_configures() {
+        let mut fx = VaultFixture::new();
+
+        //the vault admin has no say over the protocol
+        let admin = fx.admin.insecure_clone();
+        let ix = fx.protocol_admin_ix(&admin.pubkey(), "set_global_pause", &[1]);
+        assert_rejected(fx.send(ix, &[&admin]), ErrorCode::ConstraintHasOne);
+
+        let new_admin = Keypair::new();
+        send_protocol_admin(
+            &mut fx,
+            "set_protocol_admin",
+            &new_admin.pubkey().to_bytes(),
+        );
+        let ix = fx.protocol_admin_ix(&fx.protocol_admin.pubkey(), "set_global_pause", &[1]);
+        let protocol_admin = fx.protocol_admin.insecure_clone();
+        assert_rejected(fx.send(ix, &[&protocol_admin]), ErrorCode::ConstraintHasOne);
+    }
+
+    #[test]
+    pub fn test_protocol_takes_no_cut_of_exit_penalty() {
+        let mut fx = VaultFixture::with_soulbound_shares();
+        fx.set_unix_timestamp(1_000);
+
+        let mut args = fx.fee_recipient.pubkey().to_bytes().to_vec();
+        args.extend_from_slice(&2_000u16.to_le_bytes());
+        send_protocol_admin(&mut fx, "set_protocol_fee", &args);
+
+        let mut args = 1_000i64.to_le_bytes().to_vec();
+        args.extend_from_slice(&500u16.to_le_bytes());
+        fx.send_admin("set_lockup", &args).unwrap();
+
+        let leaver = fx.create_depositor(1_000_000);
+        let leaver_key = leaver.pubkey();
+        let ix = fx.deposit_ix(&leaver_key, 1_000_000);
+        fx.send(ix, &[&leaver]).unwrap();
+
+        //250 bps penalty half way through the lockup, all of it stays with the holders
+        fx.set_unix_timestamp(1_500);
+        let ix = fx.exit_ix("redeem", &leaver_key, 500_000);
+        fx.send(ix, &[&leaver]).unwrap();
+
+        let vault = fx.vault_state();
+        assert_eq!(vault.protocol_fees, 0);
+        assert_eq!(vault.total_base_assets, 1_000_000 - 487_500);
+
+        let cranker = fx.create_depositor(0);
+        let ix = fx.collect_protocol_fees_ix(&cranker.pubkey());
+        assert_rejected(fx.send(ix, &[&cranker]), Errors::NoProtocolFees);
+    }
+}
@@ -0,0 +1,1311 @@
+#![cfg(test)]
+
+use crate::constant::{
+    ALLOCATION_TARGET_SEED, ALLOWED_ADDRESS_SEED, BLOCKED_ADDRESS_SEED, EPOCH_RECEIPT_SEED,
+    EPOCH_SEED, GLOBAL_CONFIG_SEED, MINT_VAULTS_SEED, NAV_UPDATE_SEED, PRICE_HISTORY_SEED,
+    PYTH_ORACLE_PROGRAM_ID, REDEEM_REQUEST_SEED, SHARES_MINT_SEED, TOKEN_METADATA_PROGRAM_ID,
+    TOKEN_METADATA_SEED, USER_POSITION_SEED, VAULT_AUTHORITY_SEED, VAULT_SEED,
+};
+use crate::state::{PriceFeed, Vault};
+use crate::util::{NavReport, PythPrice};
+use crate::ID;
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::solana_program::bpf_loader_upgradeable;
+use anchor_lang::solana_program::instruction::Instruction as ProgramInstruction;
+use anchor_lang::solana_program::program_pack::Pack;
+use anchor_lang::{system_program, AnchorSerialize, Discriminator, Id};
+use anchor_spl::associated_token::{get_associated_token_address_with_program_id, AssociatedToken};
+use anchor_spl::token::spl_token;
+use anchor_spl::token_2022::spl_token_2022;
+use anchor_spl::token_2022::spl_token_2022::extension::{transfer_fee, ExtensionType};
+use litesvm::types::{FailedTransactionMetadata, TransactionMetadata};
+use litesvm::LiteSVM;
+use litesvm_token::{CreateAccount, CreateAssociatedTokenAccount, CreateMint, MintTo, TOKEN_ID};
+use sha2::{Digest, Sha256};
+use shares_transfer_hook::constant::{EXTRA_ACCOUNT_METAS_SEED, HOOK_CONFIG_SEED};
+use solana_sdk::account::Account;
+use solana_sdk::clock::Clock;
+use solana_sdk::instruction::InstructionError;
+use solana_sdk::message::{AccountMeta, Address, Instruction};
+use solana_sdk::native_token::LAMPORTS_PER_SOL;
+use solana_sdk::signature::{Keypair, Signer};
+use solana_sdk::transaction::{Transaction, TransactionError};
+
+pub const PROGRAM_PATH: &str = "../../target/deploy/sol_4626_vault.so";
+pub const HOOK_PROGRAM_PATH: &str = "../../target/deploy/shares_transfer_hook.so";
+/// Metaplex Token Metadata, dumped from mainnet by `scripts/dump-fixtures.sh`
+pub const TOKEN_METADATA_PROGRAM_PATH: &str = "../../tests/fixtures/mpl_token_metadata.so";
+pub const BASE_ASSET_DECIMALS: u8 = 6;
+pub const SHARES_NAME: &str = "Vault Shares";
+pub const SHARES_SYMBOL: &str = "vSHR";
+pub const SHARES_URI: &str = "https://example.com/vault.json";
+
+pub fn to_address(pubkey: &Pubkey) -> Address {
+    Address::new_from_array(pubkey.to_bytes())
+}
+
+pub fn to_pubkey(address: &Address) -> Pubkey {
+    Pubkey::new_from_array(address.to_bytes())
+}
+
+/// Anchor instruction data: sighash("global:<name>") followed by borsh encoded args.
+pub fn ix_data(name: &str, args: &[u8]) -> Vec<u8> {
+    let disc = Sha256::digest(format!("global:{name}").as_bytes());
+    let mut data = disc[..8].to_vec();
+    data.extend_from_slice(args);
+    data
+}
+
+pub fn ata(owner: &Address, mint: &Address) -> Address {
+    ata_with_program(owner, mint, &TOKEN_ID)
+}
+
+pub fn ata_with_program(owner: &Address, mint: &Address, token_program: &Address) -> Address {
+    to_address(&get_associated_token_address_with_program_id(
+        &to_pubkey(owner),
+        &to_pubkey(mint),
+        &to_pubkey(token_program),
+    ))
+}
+
+/// Converts an instruction built by the on-chain crates into a LiteSVM one.
+pub fn to_instruction(ix: ProgramInstruction) -> Instruction {
+    let accounts = ix
+        .accounts
+        .iter()
+        .map(|meta| {
+            let address = to_address(&meta.pubkey);
+            if meta.is_writable {
+                AccountMeta::new(address, meta.is_signer)
+            } else {
+                AccountMeta::new_readonly(address, meta.is_signer)
+            }
+        })
+        .collect();
+    Instruction::new_with_bytes(to_address(&ix.program_id), &ix.data, accounts)
+}
+
+/// Ed25519 program instruction carrying `signer`'s signature over `message`.
+pub fn ed25519_ix(signer: &Keypair, message: &[u8]) -> Instruction {
+    let pubkey_offset: u16 = 16;
+    let signature_offset: u16 = pubkey_offset + 32;
+    let message_offset: u16 = signature_offset + 64;
+
+    let mut data = vec![1, 0];
+    for value in [
+        signature_offset,
+        u16::MAX,
+        pubkey_offset,
+        u16::MAX,
+        message_offset,
+        message.len() as u16,
+        u16::MAX,
+    ] {
+        data.extend_from_slice(&value.to_le_bytes());
+    }
+    data.extend_from_slice(signer.pubkey().as_ref());
+    data.extend_from_slice(signer.sign_message(message).as_ref());
+    data.extend_from_slice(message);
+
+    Instruction::new_with_bytes(
+        to_address(&solana_sdk_ids::ed25519_program::ID),
+        &data,
+        vec![],
+    )
+}
+
+/// Asserts `result` failed with the custom program error `code`: an `Errors` variant,
+/// an Anchor `ErrorCode` or a token program error.
+pub fn assert_rejected(
+    result: Result<TransactionMetadata, Box<FailedTransactionMetadata>>,
+    code: impl Into<u32>,
+) {
+    let code = code.into();
+    let failed = result.expect_err("transaction should be rejected");
+    assert!(
+        matches!(
+            failed.err,
+            TransactionError::InstructionError(_, InstructionError::Custom(c)) if c == code
+        ),
+        "expected error {code}, got {:?}, logs {:?}",
+        failed.err,
+        failed.meta.logs
+    );
+}
+
+/// Protocol config singleton PDA.
+pub fn global_config_pda() -> Address {
+    let (global_config, _) =
+        Pubkey::find_program_address(&[GLOBAL_CONFIG_SEED.as_bytes()], &ID);
+    to_address(&global_config)
+}
+
+/// Program data PDA of the upgradeable program, holds its upgrade authority.
+pub fn program_data_pda() -> Address {
+    let (program_data, _) =
+        Pubkey::find_program_address(&[ID.as_ref()], &bpf_loader_upgradeable::ID);
+    to_address(&program_data)
+}
+
+/// Deploys the program from `PROGRAM_PATH` through the upgradeable loader, with
+/// `upgrade_authority` recorded in its program data account.
+pub fn add_upgradeable_program(svm: &mut LiteSVM, upgrade_authority: &Address) {
+    // bincode `UpgradeableLoaderState::ProgramData { slot: 0, upgrade_authority_address }`
+    let mut program_data = 3u32.to_le_bytes().to_vec();
+    program_data.extend_from_slice(&0u64.to_le_bytes());
+    program_data.push(1);
+    program_data.extend_from_slice(upgrade_authority.as_ref());
+    program_data.extend_from_slice(&std::fs::read(PROGRAM_PATH).unwrap());
+    // bincode `UpgradeableLoaderState::Program { programdata_address }`
+    let mut program = 2u32.to_le_bytes().to_vec();
+    program.extend_from_slice(program_data_pda().as_ref());
+
+    let loader = to_address(&bpf_loader_upgradeable::ID);
+    for (address, data, executable) in [
+        (program_data_pda(), program_data, false),
+        (to_address(&ID), program, true),
+    ] {
+        let account = Account {
+            lamports: svm.minimum_balance_for_rent_exemption(data.len()),
+            data,
+            owner: loader,
+            executable,
+            rent_epoch: 0,
+        };
+        svm.set_account(address, account).unwrap();
+    }
+}
+
+/// `initialize_global_config` signed by `protocol_admin`, the program upgrade authority.
+pub fn initialize_global_config_ix(
+    protocol_admin: &Address,
+    fee_recipient: &Address,
+    protocol_fee_bps: u16,
+) -> Instruction {
+    let mut args = fee_recipient.to_bytes().to_vec();
+    args.extend_from_slice(&protocol_fee_bps.to_le_bytes());
+
+    Instruction::new_with_bytes(
+        to_address(&ID),
+        &ix_data("initialize_global_config", &args),
+        vec![
+            AccountMeta::new(*protocol_admin, true),
+            AccountMeta::new_readonly(to_address(&ID), false),
+            AccountMeta::new_readonly(program_data_pda(), false),
+            AccountMeta::new(global_config_pda(), false),
+            AccountMeta::new_readonly(to_address(&system_program::ID), false),
+        ],
+    )
+}
+
+/// Borsh encoded `initialize` args.
+pub fn initialize_args(
+    vault_index: u64,
+    name: &str,
+    symbol: &str,
+    uri: &str,
+    allowed_mint_extensions: u16,
+    soulbound_shares: bool,
+    transfer_hook_mode: u8,
+) -> Vec<u8> {
+    let mut args = vault_index.to_le_bytes().to_vec();
+    for value in [name, symbol, uri] {
+        args.extend_from_slice(&(value.len() as u32).to_le_bytes());
+        args.extend_from_slice(value.as_bytes());
+    }
+    args.extend_from_slice(&allowed_mint_extensions.to_le_bytes());
+    args.push(soulbound_shares as u8);
+    args.push(transfer_hook_mode);
+    args
+}
+
+/// Metaplex metadata PDA of a classic SPL Token `shares_mint`.
+pub fn shares_metadata_pda(shares_mint: &Address) -> Address {
+    let (shares_metadata, _) = Pubkey::find_program_address(
+        &[
+            TOKEN_METADATA_SEED.as_bytes(),
+            TOKEN_METADATA_PROGRAM_ID.as_ref(),
+            shares_mint.as_ref(),
+        ],
+        &TOKEN_METADATA_PROGRAM_ID,
+    );
+    to_address(&shares_metadata)
+}
+
+/// Vault registry PDA of `base_asset_mint`.
+pub fn mint_vaults_pda(base_asset_mint: &Address) -> Address {
+    let (mint_vaults, _) = Pubkey::find_program_address(
+        &[MINT_VAULTS_SEED.as_bytes(), base_asset_mint.as_ref()],
+        &ID,
+    );
+    to_address(&mint_vaults)
+}
+
+/// Vault PDA number `index` of `base_asset_mint`.
+pub fn vault_pda(base_asset_mint: &Address, index: u64) -> Address {
+    let (vault, _) = Pubkey::find_program_address(
+        &[
+            VAULT_SEED.as_bytes(),
+            base_asset_mint.as_ref(),
+            &index.to_le_bytes(),
+        ],
+        &ID,
+    );
+    to_address(&vault)
+}
+
+/// Initialized vault over a fresh base asset mint (classic SPL by default), created
+/// through a fresh global config (no protocol fee).
+pub struct VaultFixture {
+    pub svm: LiteSVM,
+    pub program_id: Address,
+    pub protocol_admin: Keypair,
+    pub fee_recipient: Keypair,
+    pub global_config: Address,
+    pub admin: Keypair,
+    pub mint_authority: Keypair,
+    pub vault: Address,
+    pub vault_authority: Address,
+    pub shares_mint: Address,
+    pub shares_metadata: Address,
+    pub base_asset_mint: Address,
+    pub vault_base_asset_ata: Address,
+    pub token_program: Address,
+    pub shares_token_program: Address,
+}
+
+impl VaultFixture {
+    pub fn new() -> Self {
+        let mut fixture = Self::uninitialized();
+        fixture.initialize(0, false, 0).unwrap();
+        fixture
+    }
+
+    /// Fixture over a fresh classic SPL Token base asset mint, vault not initialized.
+    pub fn uninitialized() -> Self {
+        let (mut svm, mint_authority) = Self::setup_svm();
+        let base_asset_mint = CreateMint::new(&mut svm, &mint_authority)
+            .decimals(BASE_ASSET_DECIMALS)
+            .authority(&mint_authority.pubkey())
+            .send()
+            .unwrap();
+        Self::with_base_asset_mint(svm, mint_authority, base_asset_mint, TOKEN_ID)
+    }
+
+    /// Vault over a Token-2022 base asset mint charging `fee_bps` (capped at `max_fee`)
+    /// on every transfer.
+    pub fn with_transfer_fee(fee_bps: u16, max_fee: u64) -> Self {
+        let mut fixture =
+            Self::with_token_2022_mint(&[ExtensionType::TransferFeeConfig], |mint, authority| {
+                vec![transfer_fee::instruction::initialize_transfer_fee_config(
+                    &spl_token_2022::ID,
+                    mint,
+                    Some(authority),
+                    Some(authority),
+                    fee_bps,
+                    max_fee,
+                )
+                .unwrap()]
+            });
+        fixture.initialize(0, false, 0).unwrap();
+        fixture
+    }
+
+    /// Vault over the classic native mint (wSOL).
+    pub fn with_native_mint() -> Self {
+        let (mut svm, mint_authority) = Self::setup_svm();
+        let native_mint = to_address(&spl_token::native_mint::ID);
+        if svm.get_account(&native_mint).is_none() {
+            let mut data = vec![0; spl_token::state::Mint::LEN];
+            spl_token::state::Mint {
+                decimals: spl_token::native_mint::DECIMALS,
+                is_initialized: true,
+                ..Default::default()
+            }
+            .pack_into_slice(&mut data);
+            svm.set_account(
+                native_mint,
+                Account {
+                    lamports: svm.minimum_balance_for_rent_exemption(data.len()),
+                    data,
+                    owner: TOKEN_ID,
+                    executable: false,
+                    rent_epoch: 0,
+                },
+            )
+            .unwrap();
+        }
+        let mut fixture = Self::with_base_asset_mint(svm, mint_authority, native_mint, TOKEN_ID);
+        fixture.initialize(0, false, 0).unwrap();
+        fixture
+    }
+
+    /// Fixture over a classic SPL Token base asset with a Token-2022 shares mint.
+    /// The vault itself is not initialized yet.
+    pub fn with_token_2022_shares() -> Self {
+        let mut fixture = Self::uninitialized();
+        fixture.shares_token_program = to_address(&spl_token_2022::ID);
+        fixture.shares_metadata = fixture.program_id;
+        fixture
+    }
+
+    /// Vault with non-transferable Token-2022 shares over a classic SPL Token base asset.
+    pub fn with_soulbound_shares() -> Self {
+        let mut fixture = Self::with_token_2022_shares();
+        fixture.initialize(0, true, 0).unwrap();
+        fixture
+    }
+
+    /// Fixture over a fresh Token-2022 base asset mint sized for `extensions`, which are set
+    /// up by the instructions `init_extensions` builds for (mint, mint authority).
+    /// The vault itself is not initialized yet.
+    pub fn with_token_2022_mint(
+        extensions: &[ExtensionType],
+        init_extensions: impl FnOnce(&Pubkey, &Pubkey) -> Vec<ProgramInstruction>,
+    ) -> Self {
+        let (mut svm, mint_authority) = Self::setup_svm();
+        let token_program = to_address(&spl_token_2022::ID);
+        let base_asset_mint = Keypair::new().pubkey();
+        let mint_pubkey = to_pubkey(&base_asset_mint);
+        let authority = to_pubkey(&mint_authority.pubkey());
+
+        let len =
+            ExtensionType::try_calculate_account_len::<spl_token_2022::state::Mint>(extensions)
+                .unwrap();
+        svm.set_account(
+            base_asset_mint,
+            Account {
+                lamports: svm.minimum_balance_for_rent_exemption(len),
+                data: vec![0; len],
+                owner: token_program,
+                executable: false,
+                rent_epoch: 0,
+            },
+        )
+        .unwrap();
+
+        let mut ixs = init_extensions(&mint_pubkey, &authority);
+        ixs.push(
+            spl_token_2022::instruction::initialize_mint2(
+                &spl_token_2022::ID,
+                &mint_pubkey,
+                &authority,
+                None,
+                BASE_ASSET_DECIMALS,
+            )
+            .unwrap(),
+        );
+        let ixs: Vec<Instruction> = ixs.into_iter().map(to_instruction).collect();
+        let tx = Transaction::new_signed_with_payer(
+            &ixs,
+            Some(&mint_authority.pubkey()),
+            &[&mint_authority],
+            svm.latest_blockhash(),
+        );
+        svm.send_transaction(tx).unwrap();
+
+        Self::with_base_asset_mint(svm, mint_authority, base_asset_mint, token_program)
+    }
+
+    fn setup_svm() -> (LiteSVM, Keypair) {
+        let mut svm = LiteSVM::new();
+        svm.add_program_from_file(to_address(&shares_transfer_hook::ID), HOOK_PROGRAM_PATH)
+            .unwrap();
+        svm.add_program_from_file(
+            to_address(&TOKEN_METADATA_PROGRAM_ID),
+            TOKEN_METADATA_PROGRAM_PATH,
+        )
+        .expect("missing Metaplex fixture, run scripts/dump-fixtures.sh");
+
+        let mint_authority = Keypair::new();
+        svm.airdrop(&mint_authority.pubkey(), LAMPORTS_PER_SOL)
+            .unwrap();
+        (svm, mint_authority)
+    }
+
+    /// Fixture over `base_asset_mint` with the global config set up, vault not initialized.
+    fn with_base_asset_mint(
+        mut svm: LiteSVM,
+        mint_authority: Keypair,
+        base_asset_mint: Address,
+        token_program: Address,
+    ) -> Self {
+        let program_id = to_address(&ID);
+
+        let admin = Keypair::new();
+        svm.airdrop(&admin.pubkey(), LAMPORTS_PER_SOL * 2).unwrap();
+        let protocol_admin = Keypair::new();
+        svm.airdrop(&protocol_admin.pubkey(), LAMPORTS_PER_SOL)
+            .unwrap();
+        add_upgradeable_program(&mut svm, &protocol_admin.pubkey());
+        let fee_recipient = Keypair::new();
+        let global_config = global_config_pda();
+
+        let vault = vault_pda(&base_asset_mint, 0);
+
+        let (vault_authority, _) =
+            Pubkey::find_program_address(&[VAULT_AUTHORITY_SEED.as_bytes(), vault.as_ref()], &ID);
+        let vault_authority = to_address(&vault_authority);
+        let (shares_mint, _) = Pubkey::find_program_address(
+            &[SHARES_MINT_SEED.as_bytes(), vault_authority.as_ref()],
+            &ID,
+        );
+        let shares_mint = to_address(&shares_mint);
+        // Token-2022 shares mints carry their own metadata, no PDA (None)
+        let shares_metadata = if token_program == TOKEN_ID {
+            shares_metadata_pda(&shares_mint)
+        } else {
+            program_id
+        };
+        let vault_base_asset_ata =
+            ata_with_program(&vault_authority, &base_asset_mint, &token_program);
+
+        let mut fixture = Self {
+            svm,
+            program_id,
+            protocol_admin,
+            fee_recipient,
+            global_config,
+            admin,
+            mint_authority,
+            vault,
+            vault_authority,
+            shares_mint,
+            shares_metadata,
+            base_asset_mint,
+            vault_base_asset_ata,
+            token_program,
+            shares_token_program: token_program,
+        };
+
+        let ix = initialize_global_config_ix(
+            &fixture.protocol_admin.pubkey(),
+            &fixture.fee_recipient.pubkey(),
+            0,
+        );
+        let protocol_admin = fixture.protocol_admin.insecure_clone();
+        fixture.send(ix, &[&protocol_admin]).unwrap();
+
+        fixture
+    }
+
+    /// Initializes the fixture vault accepting the `allowed_mint_extensions` flags,
+    /// with non-transferable shares when `soulbound_shares` and a shares transfer hook
+    /// unless `transfer_hook_mode` is 0.
+    pub fn initialize(
+        &mut self,
+        allowed_mint_extensions: u16,
+        soulbound_shares: bool,
+        transfer_hook_mode: u8,
+    ) -> Result<TransactionMetadata, Box<FailedTransactionMetadata>> {
+        // Optional hook accounts are passed as the program id (None) without a hook
+        let (extra_account_meta_list, hook_config, hook_program) = if transfer_hook_mode == 0 {
+            (self.program_id, self.program_id, self.program_id)
+        } else {
+            (
+                self.extra_account_meta_list(),
+                self.hook_config(),
+                to_address(&shares_transfer_hook::ID),
+            )
+        };
+        let ix = self.instruction(
+            "initialize",
+            &initialize_args(
+                0,
+                SHARES_NAME,
+                SHARES_SYMBOL,
+                SHARES_URI,
+                allowed_mint_extensions,
+                soulbound_shares,
+                transfer_hook_mode,
+            ),
+            vec![
+                AccountMeta::new(self.admin.pubkey(), true),
+                AccountMeta::new_readonly(self.base_asset_mint, false),
+                AccountMeta::new(self.global_config, false),
+                AccountMeta::new(mint_vaults_pda(&self.base_asset_mint), false),
+                AccountMeta::new(self.vault, false),
+                AccountMeta::new(self.price_history(), false),
+                AccountMeta::new_readonly(self.vault_authority, false),
+                AccountMeta::new(self.vault_base_asset_ata, false),
+                AccountMeta::new(self.shares_mint, false),
+                AccountMeta::new(self.shares_metadata, false),
+                AccountMeta::new_readonly(self.token_program, false),
+                AccountMeta::new_readonly(self.shares_token_program, false),
+                AccountMeta::new_readonly(to_address(&AssociatedToken::id()), false),
+                AccountMeta::new_readonly(to_address(&system_program::ID), false),
+                AccountMeta::new_readonly(self.token_metadata_program(), false),
+                AccountMeta::new(extra_account_meta_list, false),
+                AccountMeta::new(hook_config, false),
+                AccountMeta::new_readonly(hook_program, false),
+            ],
+        );
+        let admin = self.admin.insecure_clone();
+        self.send(ix, &[&admin])
+    }
+
+    pub fn instruction(&self, name: &str, args: &[u8], accounts: Vec<AccountMeta>) -> Instruction {
+        Instruction::new_with_bytes(self.program_id, &ix_data(name, args), accounts)
+    }
+
+    /// Signs and sends `ix`, the first signer pays the fees.
+    pub fn send(
+        &mut self,
+        ix: Instruction,
+        signers: &[&Keypair],
+    ) -> Result<TransactionMetadata, Box<FailedTransactionMetadata>> {
+        self.send_all(&[ix], signers)
+    }
+
+    /// Signs and sends all `ixs` in one transaction, the first signer pays the fees.
+    pub fn send_all(
+        &mut self,
+        ixs: &[Instruction],
+        signers: &[&Keypair],
+    ) -> Result<TransactionMetadata, Box<FailedTransactionMetadata>> {
+        self.svm.expire_blockhash();
+        let tx = Transaction::new_signed_with_payer(
+            ixs,
+            Some(&signers[0].pubkey()),
+            signers,
+            self.svm.latest_blockhash(),
+        );
+        self.svm.send_transaction(tx).map_err(Box::new)
+    }
+
+    /// Creates a funded user holding `amount` base assets in its ATA.
+    pub fn create_depositor(&mut self, amount: u64) -> Keypair {
+        let user = Keypair::new();
+        self.svm.airdrop(&user.pubkey(), LAMPORTS_PER_SOL).unwrap();
+        let user_ata =
+            CreateAssociatedTokenAccount::new(&mut self.svm, &user, &self.base_asset_mint)
+                .token_program_id(&self.token_program)
+                .send()
+                .unwrap();
+        MintTo::new(
+            &mut self.svm,
+            &self.mint_authority,
+            &self.base_asset_mint,
+            &user_ata,
+            amount,
+        )
+        .token_program_id(&self.token_program)
+        .send()
+        .unwrap();
+        user
+    }
+
+    /// Creates a strategy escrow paid by `payer`: a base asset token account owned by the
+    /// vault authority, as `allocate` requires.
+    pub fn create_strategy(&mut self, payer: &Keypair) -> Address {
+        CreateAccount::new(&mut self.svm, payer, &self.base_asset_mint)
+            .owner(&self.vault_authority)
+            .token_program_id(&self.token_program)
+            .send()
+            .unwrap()
+    }
+
+    pub fn set_unix_timestamp(&mut self, unix_timestamp: i64) {
+        let mut clock = self.svm.get_sysvar::<Clock>();
+        clock.unix_timestamp = unix_timestamp;
+        self.svm.set_sysvar(&clock);
+    }
+
+    /// Writes a mock `PriceFeed` for `mint` at `address`, published now.
+    pub fn set_price_feed(
+        &mut self,
+        address: &Address,
+        mint: &Address,
+        price: i64,
+        conf: u64,
+        expo: i32,
+    ) {
+        let feed = PriceFeed {
+            authority: to_pubkey(&self.admin.pubkey()),
+            mint: to_pubkey(mint),
+            price,
+            conf,
+            expo,
+            publish_time: self.svm.get_sysvar::<Clock>().unix_timestamp,
+            bump: 0,
+        };
+        let mut data = PriceFeed::DISCRIMINATOR.to_vec();
+        feed.serialize(&mut data).unwrap();
+        self.set_oracle_account(address, data, self.program_id);
+    }
+
+    /// Writes a mock Pyth-style price account at `address`, trading at `price` +- `conf`
+    /// (`expo` exponent) since `publish_time`.
+    pub fn set_pyth_price(
+        &mut self,
+        address: &Address,
+        price: i64,
+        conf: u64,
+        expo: i32,
+        publish_time: i64,
+    ) {
+        let mut data = vec![0u8; PythPrice::LEN];
+        data[0..4].copy_from_slice(&PythPrice::MAGIC.to_le_bytes());
+        data[4..8].copy_from_slice(&PythPrice::VERSION.to_le_bytes());
+        data[8..12].copy_from_slice(&PythPrice::ACCOUNT_TYPE_PRICE.to_le_bytes());
+        data[20..24].copy_from_slice(&expo.to_le_bytes());
+        data[96..104].copy_from_slice(&publish_time.to_le_bytes());
+        data[208..216].copy_from_slice(&price.to_le_bytes());
+        data[216..224].copy_from_slice(&conf.to_le_bytes());
+        data[224..228].copy_from_slice(&PythPrice::STATUS_TRADING.to_le_bytes());
+        self.set_oracle_account(address, data, to_address(&PYTH_ORACLE_PROGRAM_ID));
+    }
+
+    fn set_oracle_account(&mut self, address: &Address, data: Vec<u8>, owner: Address) {
+        self.svm
+            .set_account(
+                *address,
+                Account {
+                    lamports: self.svm.minimum_balance_for_rent_exemption(data.len()),
+                    data,
+                    owner,
+                    executable: false,
+                    rent_epoch: 0,
+                },
+            )
+            .unwrap();
+    }
+
+    /// Amount held by a token account of either token program, 0 if it doesn't exist.
+    pub fn token_balance(&self, account: &Address) -> u64 {
+        // mint (32) and owner (32) precede the amount, extensions come after the base layout
+        self.svm
+            .get_account(account)
+            .filter(|acc| acc.data.len() >= 72)
+            .map(|acc| u64::from_le_bytes(acc.data[64..72].try_into().unwrap()))
+            .unwrap_or(0)
+    }
+
+    /// Associated token account of `owner` for `mint` under the vault's token program of
+    /// that mint (shares or base asset).
+    pub fn ata(&self, owner: &Address, mint: &Address) -> Address {
+        if *mint == self.shares_mint {
+            ata_with_program(owner, mint, &self.shares_token_program)
+        } else {
+            ata_with_program(owner, mint, &self.token_program)
+        }
+    }
+
+    pub fn vault_state(&self) -> Vault {
+        let acc = self.svm.get_account(&self.vault).unwrap();
+        bytemuck::pod_read_unaligned::<Vault>(&acc.data[8..8 + Vault::MAX_SIZE])
+    }
+
+    pub fn user_position(&self, user: &Address) -> Address {
+        let (position, _) = Pubkey::find_program_address(
+            &[
+                USER_POSITION_SEED.as_bytes(),
+                self.vault.as_ref(),
+                user.as_ref(),
+            ],
+            &ID,
+        );
+        to_address(&position)
+    }
+
+    pub fn blocked_address(&self, wallet: &Address) -> Address {
+        let (blocked, _) = Pubkey::find_program_address(
+            &[
+                BLOCKED_ADDRESS_SEED.as_bytes(),
+                self.vault.as_ref(),
+                wallet.as_ref(),
+            ],
+            &ID,
+        );
+        to_address(&blocked)
+    }
+
+    pub fn allowed_address(&self, wallet: &Address) -> Address {
+        let (allowed, _) = Pubkey::find_program_address(
+            &[
+                ALLOWED_ADDRESS_SEED.as_bytes(),
+                self.vault.as_ref(),
+                wallet.as_ref(),
+            ],
+            &ID,
+        );
+        to_address(&allowed)
+    }
+
+    /// Validation account of the shares transfer hook.
+    pub fn extra_account_meta_list(&self) -> Address {
+        let (list, _) = Pubkey::find_program_address(
+            &[
+                EXTRA_ACCOUNT_METAS_SEED.as_bytes(),
+                self.shares_mint.as_ref(),
+            ],
+            &shares_transfer_hook::ID,
+        );
+        to_address(&list)
+    }
+
+    pub fn hook_config(&self) -> Address {
+        let (config, _) = Pubkey::find_program_address(
+            &[HOOK_CONFIG_SEED.as_bytes(), self.shares_mint.as_ref()],
+            &shares_transfer_hook::ID,
+        );
+        to_address(&config)
+    }
+
+    pub fn redeem_request(&self, owner: &Address) -> Address {
+        let (request, _) = Pubkey::find_program_address(
+            &[
+                REDEEM_REQUEST_SEED.as_bytes(),
+                self.vault.as_ref(),
+                owner.as_ref(),
+            ],
+            &ID,
+        );
+        to_address(&request)
+    }
+
+    pub fn epoch(&self, id: u64) -> Address {
+        let (epoch, _) = Pubkey::find_program_address(
+            &[
+                EPOCH_SEED.as_bytes(),
+                self.vault.as_ref(),
+                &id.to_le_bytes(),
+            ],
+            &ID,
+        );
+        to_address(&epoch)
+    }
+
+    pub fn epoch_receipt(&self, epoch: &Address, owner: &Address) -> Address {
+        let (receipt, _) = Pubkey::find_program_address(
+            &[
+                EPOCH_RECEIPT_SEED.as_bytes(),
+                epoch.as_ref(),
+                owner.as_ref(),
+            ],
+            &ID,
+        );
+        to_address(&receipt)
+    }
+
+    pub fn price_history(&self) -> Address {
+        let (history, _) = Pubkey::find_program_address(
+            &[PRICE_HISTORY_SEED.as_bytes(), self.vault.as_ref()],
+            &ID,
+        );
+        to_address(&history)
+    }
+
+    pub fn nav_update(&self, sequence: u64) -> Address {
+        let (nav_update, _) = Pubkey::find_program_address(
+            &[
+                NAV_UPDATE_SEED.as_bytes(),
+                self.vault.as_ref(),
+                &sequence.to_le_bytes(),
+            ],
+            &ID,
+        );
+        to_address(&nav_update)
+    }
+
+    pub fn allocation_target(&self, target_ata: &Address) -> Address {
+        let (target, _) = Pubkey::find_program_address(
+            &[
+                ALLOCATION_TARGET_SEED.as_bytes(),
+                self.vault.as_ref(),
+                target_ata.as_ref(),
+            ],
+            &ID,
+        );
+        to_address(&target)
+    }
+
+    /// `deposit` (exact assets) or `mint` (exact shares), both use the `Deposit` accounts.
+    pub fn enter_ix(&self, name: &str, user: &Address, amount: u64) -> Instruction {
+        self.enter_ix_with_proof(name, user, amount, &[])
+    }
+
+    /// Entry instruction carrying an allowlist merkle proof.
+    pub fn enter_ix_with_proof(
+        &self,
+        name: &str,
+        user: &Address,
+        amount: u64,
+        proof: &[[u8; 32]],
+    ) -> Instruction {
+        let mut args = amount.to_le_bytes().to_vec();
+        args.extend_from_slice(&(proof.len() as u32).to_le_bytes());
+        proof.iter().for_each(|node| args.extend_from_slice(node));
+
+        self.instruction(
+            name,
+            &args,
+            vec![
+                AccountMeta::new(*user, true),
+                AccountMeta::new(self.shares_mint, false),
+                AccountMeta::new(self.vault, false),
+                AccountMeta::new(self.price_history(), false),
+                AccountMeta::new(self.vault_authority, false),
+                AccountMeta::new_readonly(self.global_config, false),
+                AccountMeta::new(self.ata(user, &self.shares_mint), false),
+                AccountMeta::new_readonly(self.base_asset_mint, false),
+                AccountMeta::new(self.ata(user, &self.base_asset_mint), false),
+                AccountMeta::new(self.vault_base_asset_ata, false),
+                AccountMeta::new(self.user_position(user), false),
+                AccountMeta::new_readonly(self.blocked_address(user), false),
+                AccountMeta::new_readonly(
+                    to_address(&solana_sdk_ids::sysvar::instructions::ID),
+                    false,
+                ),
+                AccountMeta::new_readonly(self.token_program, false),
+                AccountMeta::new_readonly(self.shares_token_program, false),
+                AccountMeta::new_readonly(to_address(&AssociatedToken::id()), false),
+                AccountMeta::new_readonly(to_address(&system_program::ID), false),
+            ],
+        )
+    }
+
+    /// `withdraw` (exact assets) or `redeem` (exact shares), both use the `Withdraw` accounts.
+    pub fn exit_ix(&self, name: &str, user: &Address, amount: u64) -> Instruction {
+        self.instruction(
+            name,
+            &amount.to_le_bytes(),
+            vec![
+                AccountMeta::new(*user, true),
+                AccountMeta::new(self.shares_mint, false),
+                AccountMeta::new(self.vault, false),
+                AccountMeta::new(self.price_history(), false),
+                AccountMeta::new_readonly(self.vault_authority, false),
+                AccountMeta::new(self.ata(user, &self.shares_mint), false),
+                AccountMeta::new_readonly(self.base_asset_mint, false),
+                AccountMeta::new(self.ata(user, &self.base_asset_mint), false),
+                AccountMeta::new(self.vault_base_asset_ata, false),
+                AccountMeta::new(self.user_position(user), false),
+                AccountMeta::new_readonly(self.blocked_address(user), false),
+                AccountMeta::new_readonly(self.token_program, false),
+                AccountMeta::new_readonly(self.shares_token_program, false),
+                AccountMeta::new_readonly(to_address(&AssociatedToken::id()), false),
+                AccountMeta::new_readonly(to_address(&system_program::ID), false),
+            ],
+        )
+    }
+
+    pub fn allocate_ix(&self, target_ata: &Address, amount: u64) -> Instruction {
+        self.instruction(
+            "allocate",
+            &amount.to_le_bytes(),
+            vec![
+                AccountMeta::new(self.admin.pubkey(), true),
+                AccountMeta::new(self.vault, false),
+                AccountMeta::new(self.price_history(), false),
+                AccountMeta::new_readonly(self.shares_mint, false),
+                AccountMeta::new(self.vault_authority, false),
+                AccountMeta::new_readonly(self.global_config, false),
+                AccountMeta::new_readonly(self.base_asset_mint, false),
+                AccountMeta::new(self.vault_base_asset_ata, false),
+                AccountMeta::new(*target_ata, false),
+                AccountMeta::new(self.allocation_target(target_ata), false),
+                AccountMeta::new_readonly(self.token_program, false),
+                AccountMeta::new_readonly(to_address(&system_program::ID), false),
+            ],
+        )
+    }
+
+    /// Admin pulls `amount` back from `source_ata`, signed by the vault authority.
+    pub fn deallocate_ix(&self, source_ata: &Address, amount: u64) -> Instruction {
+        self.instruction(
+            "deallocate",
+            &amount.to_le_bytes(),
+            vec![
+                AccountMeta::new_readonly(self.admin.pubkey(), true),
+                AccountMeta::new(self.vault, false),
+                AccountMeta::new_readonly(self.vault_authority, false),
+                AccountMeta::new(self.price_history(), false),
+                AccountMeta::new_readonly(self.shares_mint, false),
+                AccountMeta::new_readonly(self.base_asset_mint, false),
+                AccountMeta::new(self.vault_base_asset_ata, false),
+                AccountMeta::new(*source_ata, false),
+                AccountMeta::new(self.allocation_target(source_ata), false),
+                AccountMeta::new_readonly(self.token_program, false),
+            ],
+        )
+    }
+
+    pub fn write_down_ix(
+        &self,
+        signer: &Address,
+        target_ata: &Address,
+        amount: u64,
+    ) -> Instruction {
+        self.instruction(
+            "write_down",
+            &amount.to_le_bytes(),
+            vec![
+                AccountMeta::new_readonly(*signer, true),
+                AccountMeta::new(self.vault, false),
+                AccountMeta::new(self.price_history(), false),
+                AccountMeta::new_readonly(self.shares_mint, false),
+                AccountMeta::new(self.allocation_target(target_ata), false),
+            ],
+        )
+    }
+
+    /// Guardian (the admin by default) approves `target_ata` for allocations.
+    pub fn set_allocation_target_ix(
+        &self,
+        target_ata: &Address,
+        period_limit: u64,
+        period_seconds: i64,
+        active: bool,
+    ) -> Instruction {
+        let mut args = period_limit.to_le_bytes().to_vec();
+        args.extend_from_slice(&period_seconds.to_le_bytes());
+        args.push(active as u8);
+
+        self.instruction(
+            "set_allocation_target",
+            &args,
+            vec![
+                AccountMeta::new(self.admin.pubkey(), true),
+                AccountMeta::new_readonly(self.vault, false),
+                AccountMeta::new_readonly(self.base_asset_mint, false),
+                AccountMeta::new_readonly(*target_ata, false),
+                AccountMeta::new(self.allocation_target(target_ata), false),
+                AccountMeta::new_readonly(self.token_program, false),
+                AccountMeta::new_readonly(to_address(&system_program::ID), false),
+            ],
+        )
+    }
+
+    pub fn deposit_ix(&self, user: &Address, amount: u64) -> Instruction {
+        self.enter_ix("deposit", user, amount)
+    }
+
+    pub fn admin_ix(&self, name: &str, args: &[u8]) -> Instruction {
+        self.instruction(
+            name,
+            args,
+            vec![
+                AccountMeta::new_readonly(self.admin.pubkey(), true),
+                AccountMeta::new(self.vault, false),
+            ],
+        )
+    }
+
+    pub fn send_admin(
+        &mut self,
+        name: &str,
+        args: &[u8],
+    ) -> Result<TransactionMetadata, Box<FailedTransactionMetadata>> {
+        let ix = self.admin_ix(name, args);
+        let admin = self.admin.insecure_clone();
+        self.send(ix, &[&admin])
+    }
+
+    pub fn update_shares_uri_ix(&self, signer: &Address, uri: &str) -> Instruction {
+        let mut args = (uri.len() as u32).to_le_bytes().to_vec();
+        args.extend_from_slice(uri.as_bytes());
+
+        self.instruction(
+            "update_shares_uri",
+            &args,
+            vec![
+                AccountMeta::new(*signer, true),
+                AccountMeta::new_readonly(self.vault, false),
+                AccountMeta::new_readonly(self.vault_authority, false),
+                AccountMeta::new(self.shares_mint, false),
+                AccountMeta::new(self.shares_metadata, false),
+                AccountMeta::new_readonly(self.shares_token_program, false),
+                AccountMeta::new_readonly(to_address(&system_program::ID), false),
+                AccountMeta::new_readonly(self.token_metadata_program(), false),
+            ],
+        )
+    }
+
+    /// Metaplex program for classic SPL Token shares, program id (None) otherwise.
+    pub fn token_metadata_program(&self) -> Address {
+        if self.shares_metadata == self.program_id {
+            self.program_id
+        } else {
+            to_address(&TOKEN_METADATA_PROGRAM_ID)
+        }
+    }
+
+    pub fn protocol_admin_ix(&self, signer: &Address, name: &str, args: &[u8]) -> Instruction {
+        self.instruction(
+            name,
+            args,
+            vec![
+                AccountMeta::new_readonly(*signer, true),
+                AccountMeta::new(self.global_config, false),
+            ],
+        )
+    }
+
+    pub fn collect_protocol_fees_ix(&self, cranker: &Address) -> Instruction {
+        let fee_recipient = self.fee_recipient.pubkey();
+
+        self.instruction(
+            "collect_protocol_fees",
+            &[],
+            vec![
+                AccountMeta::new(*cranker, true),
+                AccountMeta::new_readonly(self.global_config, false),
+                AccountMeta::new(self.vault, false),
+                AccountMeta::new_readonly(self.vault_authority, false),
+                AccountMeta::new_readonly(self.base_asset_mint, false),
+                AccountMeta::new(self.vault_base_asset_ata, false),
+                AccountMeta::new_readonly(fee_recipient, false),
+                AccountMeta::new(self.ata(&fee_recipient, &self.base_asset_mint), false),
+                AccountMeta::new_readonly(self.token_program, false),
+                AccountMeta::new_readonly(to_address(&AssociatedToken::id()), false),
+                AccountMeta::new_readonly(to_address(&system_program::ID), false),
+            ],
+        )
+    }
+
+    /// Sets `signer` as the valuation signer, `max_nav_age` in seconds.
+    pub fn set_valuation_signer(
+        &mut self,
+        signer: &Keypair,
+        max_nav_change_bps: u16,
+        max_nav_age: i64,
+    ) -> Result<TransactionMetadata, Box<FailedTransactionMetadata>> {
+        let mut args = signer.pubkey().as_ref().to_vec();
+        args.extend_from_slice(&max_nav_change_bps.to_le_bytes());
+        args.extend_from_slice(&max_nav_age.to_le_bytes());
+        self.send_admin("set_valuation_signer", &args)
+    }
+
+    /// NAV report replacing the current total assets of the vault.
+    pub fn nav_report(&self, nav: u64, timestamp: i64, sequence: u64) -> NavReport {
+        NavReport {
+            vault: to_pubkey(&self.vault),
+            nav,
+            previous_nav: self.vault_state().total_base_assets,
+            timestamp,
+            sequence,
+        }
+    }
+
+    /// `report` signed by `valuation`, relayed by `submitter`.
+    pub fn update_nav_ixs(
+        &self,
+        valuation: &Keypair,
+        submitter: &Address,
+        report: &NavReport,
+    ) -> [Instruction; 2] {
+        [
+            ed25519_ix(valuation, &report.try_to_vec().unwrap()),
+            self.update_nav_ix(submitter, report),
+        ]
+    }
+
+    pub fn update_nav_ix(&self, submitter: &Address, report: &NavReport) -> Instruction {
+        let sequence = report.sequence;
+        let mut args = report.nav.to_le_bytes().to_vec();
+        args.extend_from_slice(&report.previous_nav.to_le_bytes());
+        args.extend_from_slice(&report.timestamp.to_le_bytes());
+        args.extend_from_slice(&sequence.to_le_bytes());
+
+        self.instruction(
+            "update_nav",
+            &args,
+            vec![
+                AccountMeta::new(*submitter, true),
+                AccountMeta::new(self.vault, false),
+                AccountMeta::new(self.price_history(), false),
+                AccountMeta::new_readonly(self.shares_mint, false),
+                AccountMeta::new(self.nav_update(sequence), false),
+                AccountMeta::new_readonly(
+                    to_address(&solana_sdk_ids::sysvar::instructions::ID),
+                    false,
+                ),
+                AccountMeta::new_readonly(to_address(&system_program::ID), false),
+            ],
+        )
+    }
+
+    pub fn shutdown_ix(&self, authority: &Address) -> Instruction {
+        self.instruction(
+            "shutdown",
+            &[],
+            vec![
+                AccountMeta::new_readonly(*authority, true),
+                AccountMeta::new(self.vault, false),
+            ],
+        )
+    }
+
+    pub fn reset_circuit_breaker_ix(&self, guardian: &Address) -> Instruction {
+        self.instruction(
+            "reset_circuit_breaker",
+            &[],
+            vec![
+                AccountMeta::new_readonly(*guardian, true),
+                AccountMeta::new(self.vault, false),
+            ],
+        )
+    }
+
+    pub fn request_redeem_ix(&self, owner: &Address, shares: u64) -> Instruction {
+        self.instruction(
+            "request_redeem",
+            &shares.to_le_bytes(),
+            vec![
+                AccountMeta::new(*owner, true),
+                AccountMeta::new_readonly(self.shares_mint, false),
+                AccountMeta::new(self.vault, false),
+                AccountMeta::new_readonly(self.vault_authority, false),
+                AccountMeta::new(self.ata(owner, &self.shares_mint), false),
+                AccountMeta::new(self.ata(&self.vault_authority, &self.shares_mint), false),
+                AccountMeta::new(self.redeem_request(owner), false),
+                AccountMeta::new(self.user_position(owner), false),
+                AccountMeta::new_readonly(self.blocked_address(owner), false),
+                AccountMeta::new_readonly(self.shares_token_program, false),
+                AccountMeta::new_readonly(to_address(&AssociatedToken::id()), false),
+                AccountMeta::new_readonly(to_address(&system_program::ID), false),
+            ],
+        )
+    }
+
+    pub fn fulfill_redeem_ix(&self, owner: &Address) -> Instruction {
+        self.instruction(
+            "fulfill_redeem",
+            &[],
+            vec![
+                AccountMeta::new_readonly(self.admin.pubkey(), true),
+                AccountMeta::new(self.vault, false),
+                AccountMeta::new(self.price_history(), false),
+                AccountMeta::new_readonly(self.vault_authority, false),
+                AccountMeta::new(self.shares_mint, false),
+                AccountMeta::new(self.ata(&self.vault_authority, &self.shares_mint), false),
+                AccountMeta::new_readonly(self.vault_base_asset_ata, false),
+                AccountMeta::new(self.redeem_request(owner), false),
+                AccountMeta::new(self.user_position(owner), false),
+                AccountMeta::new_readonly(self.shares_token_program, false),
+            ],
+        )
+    }
+
+    pub fn claim_redeem_ix(&self, owner: &Address) -> Instruction {
+        self.instruction(
+            "claim_redeem",
+            &[],
+            vec![
+                AccountMeta::new(*owner, true),
+                AccountMeta::new(self.vault, false),
+                AccountMeta::new_readonly(self.vault_authority, false),
+                AccountMeta::new_readonly(self.base_asset_mint, false),
+                AccountMeta::new(self.ata(owner, &self.base_asset_mint), false),
+                AccountMeta::new(self.vault_base_asset_ata, false),
+                AccountMeta::new(self.redeem_request(owner), false),
+                AccountMeta::new_readonly(self.blocked_address(owner), false),
+                AccountMeta::new_readonly(self.token_program, false),
+                AccountMeta::new_readonly(to_address(&AssociatedToken::id()), false),
+                AccountMeta::new_readonly(to_address(&system_program::ID), false),
+            ],
+        )
+    }
+
+    pub fn queue_deposit_ix(&self, user: &Address, amount: u64) -> Instruction {
+        let mut args = amount.to_le_bytes().to_vec();
+        args.extend_from_slice(&0u32.to_le_bytes());
+        let epoch = self.epoch(self.vault_state().current_epoch);
+
+        self.instruction(
+            "queue_deposit",
+            &args,
+            vec![
+                AccountMeta::new(*user, true),
+                AccountMeta::new(self.vault, false),
+                AccountMeta::new_readonly(self.global_config, false),
+                AccountMeta::new_readonly(self.base_asset_mint, false),
+                AccountMeta::new(self.ata(user, &self.base_asset_mint), false),
+                AccountMeta::new(self.vault_base_asset_ata, false),
+                AccountMeta::new(epoch, false),
+                AccountMeta::new(self.epoch_receipt(&epoch, user), false),
+                AccountMeta::new(self.user_position(user), false),
+                AccountMeta::new_readonly(self.blocked_address(user), false),
+                AccountMeta::new_readonly(
+                    to_address(&solana_sdk_ids::sysvar::instructions::ID),
+                    false,
+                ),
+                AccountMeta::new_readonly(self.token_program, false),
+                AccountMeta::new_readonly(to_address(&system_program::ID), false),
+            ],
+        )
+    }
+
+    pub fn queue_redeem_ix(&self, owner: &Address, shares: u64) -> Instruction {
+        let epoch = self.epoch(self.vault_state().current_epoch);
+
+        self.instruction(
+            "queue_redeem",
+            &shares.to_le_bytes(),
+            vec![
+                AccountMeta::new(*owner, true),
+                AccountMeta::new_readonly(self.vault, false),
+                AccountMeta::new_readonly(self.vault_authority, false),
+                AccountMeta::new_readonly(self.shares_mint, false),
+                AccountMeta::new(self.ata(owner, &self.shares_mint), false),
+                AccountMeta::new(self.ata(&self.vault_authority, &self.shares_mint), false),
+                AccountMeta::new(epoch, false),
+                AccountMeta::new(self.epoch_receipt(&epoch, owner), false),
+                AccountMeta::new(self.user_position(owner), false),
+                AccountMeta::new_readonly(self.blocked_address(owner), false),
+                AccountMeta::new_readonly(self.shares_token_program, false),
+                AccountMeta::new_readonly(to_address(&AssociatedToken::id()), false),
+                AccountMeta::new_readonly(to_address(&system_program::ID), false),
+            ],
+        )
+    }
+
+    pub fn close_epoch_ix(&self, cranker: &Address) -> Instruction {
+        self.instruction(
+            "close_epoch",
+            &[],
+            vec![
+                AccountMeta::new(*cranker, true),
+                AccountMeta::new(self.vault, false),
+                AccountMeta::new(self.price_history(), false),
+                AccountMeta::new_readonly(self.vault_authority, false),
+                AccountMeta::new(self.shares_mint, false),
+                AccountMeta::new(self.ata(&self.vault_authority, &self.shares_mint), false),
+                AccountMeta::new_readonly(self.vault_base_asset_ata, false),
+                AccountMeta::new(self.epoch(self.vault_state().current_epoch), false),
+                AccountMeta::new_readonly(self.shares_token_program, false),
+                AccountMeta::new_readonly(to_address(&AssociatedToken::id()), false),
+                AccountMeta::new_readonly(to_address(&system_program::ID), false),
+            ],
+        )
+    }
+
+    pub fn claim_epoch_ix(&self, owner: &Address, epoch_id: u64) -> Instruction {
+        let epoch = self.epoch(epoch_id);
+
+        self.instruction(
+            "claim_epoch",
+            &[],
+            vec![
+                AccountMeta::new(*owner, true),
+                AccountMeta::new(self.vault, false),
+                AccountMeta::new_readonly(self.vault_authority, false),
+                AccountMeta::new_readonly(self.shares_mint, false),
+                AccountMeta::new(self.ata(&self.vault_authority, &self.shares_mint), false),
+                AccountMeta::new(self.ata(owner, &self.shares_mint), false),
+                AccountMeta::new_readonly(self.base_asset_mint, false),
+                AccountMeta::new(self.ata(owner, &self.base_asset_mint), false),
+                AccountMeta::new(self.vault_base_asset_ata, false),
+                AccountMeta::new_readonly(epoch, false),
+                AccountMeta::new(self.epoch_receipt(&epoch, owner), false),
+                AccountMeta::new(self.user_position(owner), false),
+                AccountMeta::new_readonly(self.blocked_address(owner), false),
+                AccountMeta::new_readonly(self.token_program, false),
+                AccountMeta::new_readonly(self.shares_token_program, false),
+                AccountMeta::new_readonly(to_address(&AssociatedToken::id()), false),
+                AccountMeta::new_readonly(to_address(&system_program::ID), false),
+            ],
+        )
+    }
+}
@@ -1,15 +1,23 @@
 #[cfg(test)]
 mod test_initialize {
-    use crate::constant::{SHARES_MINT_SEED, VAULT_AUTHORITY_SEED};
-    use crate::state::Vault;
+    use crate::constant::{
+        MINT_VAULTS_SEED, PRICE_HISTORY_SEED, SHARES_MINT_SEED, TOKEN_METADATA_PROGRAM_ID,
+        VAULT_AUTHORITY_SEED, VAULT_SEED,
+    };
+    use crate::state::{GlobalConfig, MintVaults, PriceHistory, Vault};
+    use crate::tests::helper::{
+        add_upgradeable_program, global_config_pda, initialize_args, initialize_global_config_ix,
+        shares_metadata_pda, to_address, to_pubkey, VaultFixture, SHARES_NAME, SHARES_SYMBOL,
+        SHARES_URI, TOKEN_METADATA_PROGRAM_PATH,
+    };
+    use crate::util::{SharesMetadata, PRICE_SCALE};
     use crate::ID;
     use anchor_lang::prelude::Pubkey;
-    use anchor_lang::{system_program, Id};
+    use anchor_lang::{system_program, AccountDeserialize, Id};
     use anchor_spl::associated_token::{get_associated_token_address, AssociatedToken};
     use litesvm::LiteSVM;
     use litesvm_token::{CreateMint, TOKEN_ID};
     use sha2::{Digest, Sha256};
-    use solana_sdk::account::Account;
     use solana_sdk::message::{AccountMeta, Address, Instruction};
     use solana_sdk::native_token::LAMPORTS_PER_SOL;
     use solana_sdk::signature::{Keypair, Signer};
@@ -19,18 +27,19 @@ mod test_initialize {
     pub fn test_initialize_success() {
         let mut svm = LiteSVM::new();
 
-        //adding our program to svm
-        let program_id = ID;
-        svm.add_program_from_file(
-            program_id.to_bytes(),
-            "../../target/deploy/sol_4626_vault.so",
-        )
-        .unwrap();
-
         //airdrop payer
         let payer = Keypair::new();
         svm.airdrop(&payer.pubkey(), LAMPORTS_PER_SOL).unwrap();
 
+        //adding our program to svm, deployed by the payer
+        let program_id = ID;
+        add_upgradeable_program(&mut svm, &payer.pubkey());
+        svm.add_program_from_file(
+            to_address(&TOKEN_METADATA_PROGRAM_ID),
+            TOKEN_METADATA_PROGRAM_PATH,
+        )
+        .expect("missing Metaplex fixture, run scripts/dump-fixtures.sh");
+
         //creating mint
         let base_asset_mint = CreateMint::new(&mut svm, &payer)
             .decimals(9)
@@ -40,9 +49,26 @@ mod test_initialize {
 
         //preparing accounts
         let admin_kp = Keypair::new();
-        let vlt_kp = Keypair::new();
         let admin = admin_kp.pubkey();
-        let vlt = vlt_kp.pubkey();
+
+        //mint vaults registry
+        let mint_vaults_seeds = &[MINT_VAULTS_SEED.as_bytes(), base_asset_mint.as_ref()];
+        let (mint_vaults, _) = Pubkey::find_program_address(mint_vaults_seeds, &program_id);
+
+        //first vault of the base asset mint
+        let vault_index: u64 = 0;
+        let vlt_seeds = &[
+            VAULT_SEED.as_bytes(),
+            base_asset_mint.as_ref(),
+            &vault_index.to_le_bytes(),
+        ];
+        let (vlt, vault_bump) = Pubkey::find_program_address(vlt_seeds, &program_id);
+
+        //share price history
+        let (price_history, _) = Pubkey::find_program_address(
+            &[PRICE_HISTORY_SEED.as_bytes(), vlt.as_ref()],
+            &program_id,
+        );
 
         //vlt authority
         let vlt_authority_seeds = &[VAULT_AUTHORITY_SEED.as_bytes(), vlt.as_ref()];
@@ -63,33 +89,51 @@ mod test_initialize {
         //airdrop admin
         svm.airdrop(&admin, LAMPORTS_PER_SOL * 2).unwrap();
 
-        //init vault data (zero)
-        let fn_disc = Sha256::digest(b"global:initialize");
-        let data = vec![0; 8 + Vault::MAX_SIZE];
-        let rent = svm.minimum_balance_for_rent_exemption(8 + Vault::MAX_SIZE);
+        //protocol config, vaults are created through it
+        let global_config = global_config_pda();
+        let tx = Transaction::new_signed_with_payer(
+            &[initialize_global_config_ix(
+                &payer.pubkey(),
+                &payer.pubkey(),
+                0,
+            )],
+            Some(&payer.pubkey()),
+            &[&payer],
+            svm.latest_blockhash(),
+        );
+        svm.send_transaction(tx).unwrap();
 
-        let vlt_acc = Account {
-            lamports: rent,
-            data,
-            owner: Address::new_from_array(program_id.to_bytes()),
-            executable: false,
-            rent_epoch: 0,
-        };
+        //classic SPL Token shares mints keep their metadata in a Metaplex account
+        let shares_metadata = shares_metadata_pda(&to_address(&shares_mint));
 
-        //create zeroed vlt account
-        svm.set_account(vlt, vlt_acc).unwrap();
+        let fn_disc = Sha256::digest(b"global:initialize");
+        let mut data = fn_disc[..8].to_vec();
+        data.extend_from_slice(&initialize_args(
+            vault_index,
+            SHARES_NAME,
+            SHARES_SYMBOL,
+            SHARES_URI,
+            0,
+            false,
+            0,
+        ));
 
         //define accounts for ix
         let accs = vec![
             AccountMeta::new(admin, true),
-            AccountMeta::new(vlt, false),
-            AccountMeta::new_readonly(Address::from(vlt_authority.to_bytes()), false),
             AccountMeta::new_readonly(base_asset_mint, false),
+            AccountMeta::new(global_config, false),
+            AccountMeta::new(Address::from(mint_vaults.to_bytes()), false),
+            AccountMeta::new(Address::from(vlt.to_bytes()), false),
+            AccountMeta::new(Address::from(price_history.to_bytes()), false),
+            AccountMeta::new_readonly(Address::from(vlt_authority.to_bytes()), false),
             AccountMeta::new(
                 Address::new_from_array(vault_base_asset_ata.to_bytes()),
                 false,
             ),
             AccountMeta::new(Address::from(shares_mint.to_bytes()), false),
+            AccountMeta::new(shares_metadata, false),
+            AccountMeta::new_readonly(TOKEN_ID, false),
             AccountMeta::new_readonly(TOKEN_ID, false),
             AccountMeta::new_readonly(
                 Address::new_from_array(AssociatedToken::id().to_bytes()),
@@ -99,12 +143,17 @@ mod test_initialize {
                 Address::new_from_array(system_program::ID.to_bytes()),
                 false,
             ),
+            AccountMeta::new_readonly(to_address(&TOKEN_METADATA_PROGRAM_ID), false),
+            //no shares transfer hook: extra_account_meta_list, hook_config, hook program (None)
+            AccountMeta::new(Address::from(program_id.to_bytes()), false),
+            AccountMeta::new(Address::from(program_id.to_bytes()), false),
+            AccountMeta::new_readonly(Address::from(program_id.to_bytes()), false),
         ];
 
         //build ix
         let ix = Instruction::new_with_bytes(
             Address::new_from_array(program_id.to_bytes()),
-            &fn_disc[..8],
+            &data,
             accs,
         );
 
@@ -126,8 +175,91 @@ mod test_initialize {
         }
 
         //load vlt account
-        let vlt_acc = svm.get_account(&vlt).expect("vault account not found");
+        let vlt_acc = svm
+            .get_account(&Address::from(vlt.to_bytes()))
+            .expect("vault account not found");
 
         assert_eq!(vlt_acc.owner.to_bytes()[..], program_id.to_bytes()[..]);
+
+        let vlt_state =
+            bytemuck::pod_read_unaligned::<Vault>(&vlt_acc.data[8..8 + Vault::MAX_SIZE]);
+        assert_eq!(vlt_state.admin.to_bytes(), admin.to_bytes());
+        assert_eq!(vlt_state.guardian.to_bytes(), admin.to_bytes());
+        assert_eq!(vlt_state.token_program.to_bytes(), TOKEN_ID.to_bytes());
+        assert_eq!(
+            vlt_state.shares_token_program.to_bytes(),
+            TOKEN_ID.to_bytes()
+        );
+        assert_eq!(vlt_state.vault_base_asset_ata, vault_base_asset_ata);
+        assert_eq!(vlt_state.vault_authority_bump, vlt_bump);
+        assert_eq!(vlt_state.mint_shares_bump, shares_mint_bump);
+        assert_eq!(vlt_state.vault_index, vault_index);
+        assert_eq!(vlt_state.vault_bump, vault_bump);
+        assert_eq!(vlt_state.allowed_mint_extensions, 0);
+        assert!(!vlt_state.soulbound_shares.is_true());
+        assert_eq!(vlt_state.transfer_hook_mode, 0);
+        assert!(!vlt_state.shutdown.is_true());
+
+        //price history starts at 1:1
+        let history_acc = svm
+            .get_account(&Address::from(price_history.to_bytes()))
+            .expect("price history account not found");
+        let history = bytemuck::pod_read_unaligned::<PriceHistory>(
+            &history_acc.data[8..8 + PriceHistory::MAX_SIZE],
+        );
+        assert_eq!(history.vault, vlt);
+        assert_eq!(history.count, 1);
+        assert_eq!(history.observations[0].price, PRICE_SCALE);
+
+        //registry counts the new vault
+        let registry_acc = svm
+            .get_account(&Address::from(mint_vaults.to_bytes()))
+            .expect("mint vaults account not found");
+        let registry = MintVaults::try_deserialize(&mut &registry_acc.data[..]).unwrap();
+        assert_eq!(
+            registry.base_asset_mint.to_bytes(),
+            base_asset_mint.to_bytes()
+        );
+        assert_eq!(registry.vault_count, 1);
+
+        //and the factory counts it protocol-wide
+        let config_acc = svm
+            .get_account(&global_config)
+            .expect("global config account not found");
+        let config = GlobalConfig::try_deserialize(&mut &config_acc.data[..]).unwrap();
+        assert_eq!(config.vault_count, 1);
+
+        //shares token metadata
+        let metadata_acc = svm
+            .get_account(&shares_metadata)
+            .expect("shares metadata account not found");
+        assert_eq!(metadata_acc.owner, to_address(&TOKEN_METADATA_PROGRAM_ID));
+        //update authority, then the mint
+        assert_eq!(&metadata_acc.data[1..33], vlt_authority.as_ref());
+        assert_eq!(&metadata_acc.data[33..65], shares_mint.as_ref());
+        let (name, symbol, uri) = SharesMetadata::read(&metadata_acc.data).unwrap();
+        assert_eq!(name, SHARES_NAME);
+        assert_eq!(symbol, SHARES_SYMBOL);
+        assert_eq!(uri, SHARES_URI);
+    }
+    #[test]
+    pub fn test_initialize_prefunded_shares_mint() {
+        for mut fx in [
+            VaultFixture::uninitialized(),
+            VaultFixture::with_token_2022_shares(),
+        ] {
+            //anyone can fund the predictable shares mint address before initialize
+            let shares_mint = fx.shares_mint;
+            fx.svm.airdrop(&shares_mint, 1_000_000).unwrap();
+
+            fx.initialize(0, false, 0).unwrap();
+            let mint_acc = fx.svm.get_account(&shares_mint).unwrap();
+            assert_eq!(mint_acc.owner, fx.shares_token_program);
+            let rent = fx
+                .svm
+                .minimum_balance_for_rent_exemption(mint_acc.data.len());
+            assert!(mint_acc.lamports >= rent);
+            assert_eq!(fx.vault_state().shares_mint, to_pubkey(&shares_mint));
+        }
     }
 }
@@ -0,0 +1,84 @@
+#[cfg(test)]
+mod test_lockup {
+    use crate::tests::helper::{assert_rejected, ata, VaultFixture};
+    use crate::util::Errors;
+    use litesvm::types::{FailedTransactionMetadata, TransactionMetadata};
+    use solana_sdk::signature::Signer;
+
+    fn set_lockup(
+        fx: &mut VaultFixture,
+        lockup_seconds: i64,
+        penalty_bps: u16,
+    ) -> Result<TransactionMetadata, Box<FailedTransactionMetadata>> {
+        let mut args = lockup_seconds.to_le_bytes().to_vec();
+        args.extend_from_slice(&penalty_bps.to_le_bytes());
+        fx.send_admin("set_lockup", &args)
+    }
+
+    #[test]
+    pub fn test_lockup_requires_soulbound_shares() {
+        //transferable shares would carry no lockup to a fresh wallet
+        let mut fx = VaultFixture::new();
+        assert_rejected(
+            set_lockup(&mut fx, 86_400, 0),
+            Errors::LockupRequiresSoulboundShares,
+        );
+        set_lockup(&mut fx, 0, 0).unwrap();
+        assert_eq!(fx.vault_state().lockup_seconds, 0);
+
+        let mut fx = VaultFixture::with_soulbound_shares();
+        set_lockup(&mut fx, 86_400, 0).unwrap();
+        assert_eq!(fx.vault_state().lockup_seconds, 86_400);
+    }
+
+    #[test]
+    pub fn test_locked_exits_are_rejected() {
+        let mut fx = VaultFixture::with_soulbound_shares();
+        fx.set_unix_timestamp(1_000);
+        set_lockup(&mut fx, 86_400, 0).unwrap();
+
+        let user = fx.create_depositor(1_000_000);
+        let user_key = user.pubkey();
+        let ix = fx.deposit_ix(&user_key, 1_000_000);
+        fx.send(ix, &[&user]).unwrap();
+
+        let ix = fx.exit_ix("redeem", &user_key, 500_000);
+        assert_rejected(fx.send(ix, &[&user]), Errors::PositionLocked);
+
+        //unlocked once the holding period is over
+        fx.set_unix_timestamp(1_000 + 86_400);
+        let ix = fx.exit_ix("redeem", &user_key, 500_000);
+        fx.send(ix, &[&user]).unwrap();
+        assert_eq!(
+            fx.token_balance(&ata(&user_key, &fx.base_asset_mint)),
+            500_000
+        );
+    }
+
+    #[test]
+    pub fn test_early_exit_penalty_stays_in_vault() {
+        let mut fx = VaultFixture::with_soulbound_shares();
+        fx.set_unix_timestamp(1_000);
+        set_lockup(&mut fx, 1_000, 500).unwrap();
+
+        let stayer = fx.create_depositor(1_000_000);
+        let ix = fx.deposit_ix(&stayer.pubkey(), 1_000_000);
+        fx.send(ix, &[&stayer]).unwrap();
+
+        let leaver = fx.create_depositor(1_000_000);
+        let leaver_key = leaver.pubkey();
+        let ix = fx.deposit_ix(&leaver_key, 1_000_000);
+        fx.send(ix, &[&leaver]).unwrap();
+
+        //half way through the lockup the penalty has decayed to 250 bps
+        fx.set_unix_timestamp(1_500);
+        let ix = fx.exit_ix("redeem", &leaver_key, 1_000_000);
+        fx.send(ix, &[&leaver]).unwrap();
+
+        assert_eq!(
+            fx.token_balance(&ata(&leaver_key, &fx.base_asset_mint)),
+            975_000
+        );
+        assert_eq!(fx.vault_state().total_base_assets, 1_025_000);
+    }
+}
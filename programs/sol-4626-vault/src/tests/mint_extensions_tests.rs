@@ -0,0 +1,86 @@
+#[cfg(test)]
+mod test_mint_extensions {
+    use crate::tests::helper::{assert_rejected, VaultFixture};
+    use crate::util::{Errors, MintExtensions};
+    use anchor_lang::prelude::Pubkey;
+    use anchor_spl::token_2022::spl_token_2022;
+    use anchor_spl::token_2022::spl_token_2022::extension::{transfer_hook, ExtensionType};
+
+    #[test]
+    pub fn test_permanent_delegate_requires_opt_in() {
+        let mut fx = VaultFixture::with_token_2022_mint(
+            &[ExtensionType::PermanentDelegate],
+            |mint, auth| {
+                vec![spl_token_2022::instruction::initialize_permanent_delegate(
+                    &spl_token_2022::ID,
+                    mint,
+                    auth,
+                )
+                .unwrap()]
+            },
+        );
+
+        assert_rejected(
+            fx.initialize(0, false, 0),
+            Errors::PermanentDelegateNotAllowed,
+        );
+        //opting into something else doesn't help
+        assert_rejected(
+            fx.initialize(MintExtensions::TRANSFER_HOOK, false, 0),
+            Errors::PermanentDelegateNotAllowed,
+        );
+        assert_rejected(
+            fx.initialize(1 << 15, false, 0),
+            Errors::InvalidMintExtensions,
+        );
+
+        fx.initialize(MintExtensions::PERMANENT_DELEGATE, false, 0)
+            .unwrap();
+        assert_eq!(
+            fx.vault_state().allowed_mint_extensions,
+            MintExtensions::PERMANENT_DELEGATE
+        );
+    }
+
+    #[test]
+    pub fn test_transfer_hook_requires_opt_in() {
+        //a deployed hook program as much as an arbitrary id
+        for hook_program in [shares_transfer_hook::ID, Pubkey::new_unique()] {
+            let mut fx =
+                VaultFixture::with_token_2022_mint(&[ExtensionType::TransferHook], |mint, auth| {
+                    vec![transfer_hook::instruction::initialize(
+                        &spl_token_2022::ID,
+                        mint,
+                        Some(*auth),
+                        Some(hook_program),
+                    )
+                    .unwrap()]
+                });
+
+            assert_rejected(fx.initialize(0, false, 0), Errors::TransferHookNotAllowed);
+            fx.initialize(MintExtensions::TRANSFER_HOOK, false, 0)
+                .unwrap();
+            assert_eq!(
+                fx.vault_state().allowed_mint_extensions,
+                MintExtensions::TRANSFER_HOOK
+            );
+        }
+    }
+
+    #[test]
+    pub fn test_harmless_extensions_are_accepted() {
+        //no hook program set, nothing to opt into
+        let mut fx =
+            VaultFixture::with_token_2022_mint(&[ExtensionType::TransferHook], |mint, auth| {
+                vec![transfer_hook::instruction::initialize(
+                    &spl_token_2022::ID,
+                    mint,
+                    Some(*auth),
+                    None,
+                )
+                .unwrap()]
+            });
+        fx.initialize(0, false, 0).unwrap();
+        assert_eq!(fx.vault_state().allowed_mint_extensions, 0);
+    }
+}
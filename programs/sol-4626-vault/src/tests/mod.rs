@@ -1,2 +1,24 @@
+mod allocation_target_tests;
+mod allowlist_tests;
+mod basket_tests;
+mod blocklist_tests;
+mod circuit_breaker_tests;
+mod deposit_caps_tests;
+mod epoch_tests;
+mod global_config_tests;
 mod initialize_tests;
+mod lockup_tests;
+mod mint_extensions_tests;
+mod native_sol_tests;
+mod nav_report_tests;
+mod price_history_tests;
+mod rate_limit_tests;
+mod redeem_request_tests;
+mod shares_metadata_tests;
+mod shutdown_tests;
+mod soulbound_tests;
+mod transfer_fee_tests;
+mod transfer_hook_tests;
+mod voucher_tests;
+mod write_down_tests;
 mod helper;
\ No newline at end of file
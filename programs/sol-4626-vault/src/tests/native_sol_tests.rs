@@ -0,0 +1,68 @@
+#[cfg(test)]
+mod test_native_sol {
+    use crate::tests::helper::{assert_rejected, VaultFixture};
+    use crate::util::Errors;
+    use solana_sdk::native_token::LAMPORTS_PER_SOL;
+    use solana_sdk::signature::{Keypair, Signer};
+
+    //upper bound of the signature fees paid by the tests' transactions
+    const MAX_FEES: u64 = 100_000;
+
+    #[test]
+    pub fn test_deposit_and_withdraw_sol() {
+        let mut fx = VaultFixture::with_native_mint();
+        let user = Keypair::new();
+        let user_key = user.pubkey();
+        fx.svm.airdrop(&user_key, 10 * LAMPORTS_PER_SOL).unwrap();
+        let user_wsol = fx.ata(&user_key, &fx.base_asset_mint);
+        let user_shares = fx.ata(&user_key, &fx.shares_mint);
+
+        //lamports are wrapped, deposited and the wSOL ATA closed again
+        let before = fx.svm.get_balance(&user_key).unwrap();
+        let ix = fx.enter_ix_with_proof("deposit_sol", &user_key, 2 * LAMPORTS_PER_SOL, &[]);
+        fx.send(ix, &[&user]).unwrap();
+        assert_eq!(fx.token_balance(&user_shares), 2 * LAMPORTS_PER_SOL);
+        assert_eq!(fx.vault_state().total_base_assets, 2 * LAMPORTS_PER_SOL);
+        assert_eq!(
+            fx.token_balance(&fx.vault_base_asset_ata),
+            2 * LAMPORTS_PER_SOL
+        );
+        assert!(fx
+            .svm
+            .get_account(&user_wsol)
+            .is_none_or(|acc| acc.lamports == 0));
+        //only the deposit, the shares ATA and position rent and the fees left the wallet
+        let spent = before - fx.svm.get_balance(&user_key).unwrap();
+        assert!(
+            spent > 2 * LAMPORTS_PER_SOL && spent < 2 * LAMPORTS_PER_SOL + LAMPORTS_PER_SOL / 100
+        );
+
+        //exits are paid out in lamports
+        let before = fx.svm.get_balance(&user_key).unwrap();
+        let ix = fx.exit_ix("withdraw_sol", &user_key, LAMPORTS_PER_SOL);
+        fx.send(ix, &[&user]).unwrap();
+        assert_eq!(fx.token_balance(&user_shares), LAMPORTS_PER_SOL);
+        assert_eq!(fx.vault_state().total_base_assets, LAMPORTS_PER_SOL);
+        assert!(fx
+            .svm
+            .get_account(&user_wsol)
+            .is_none_or(|acc| acc.lamports == 0));
+        let received = fx.svm.get_balance(&user_key).unwrap() - before;
+        assert!(received <= LAMPORTS_PER_SOL && received > LAMPORTS_PER_SOL - MAX_FEES);
+    }
+
+    #[test]
+    pub fn test_sol_paths_require_native_mint() {
+        let mut fx = VaultFixture::new();
+        let user = fx.create_depositor(1_000_000);
+        let user_key = user.pubkey();
+
+        let ix = fx.enter_ix_with_proof("deposit_sol", &user_key, 1_000_000, &[]);
+        assert_rejected(fx.send(ix, &[&user]), Errors::NotNativeMint);
+
+        let ix = fx.deposit_ix(&user_key, 1_000_000);
+        fx.send(ix, &[&user]).unwrap();
+        let ix = fx.exit_ix("withdraw_sol", &user_key, 500_000);
+        assert_rejected(fx.send(ix, &[&user]), Errors::NotNativeMint);
+    }
+}
@@ -0,0 +1,108 @@
+#[cfg(test)]
+mod test_nav_report {
+    use crate::state::NavUpdate;
+    use crate::tests::helper::{assert_rejected, VaultFixture};
+    use crate::util::Errors;
+    use anchor_lang::AccountDeserialize;
+    use solana_sdk::signature::{Keypair, Signer};
+
+    #[test]
+    pub fn test_update_nav_from_signed_reports() {
+        let mut fx = VaultFixture::new();
+        fx.set_unix_timestamp(1_000);
+
+        let user = fx.create_depositor(1_000_000);
+        let ix = fx.deposit_ix(&user.pubkey(), 1_000_000);
+        fx.send(ix, &[&user]).unwrap();
+
+        //reports disabled
+        let valuation = Keypair::new();
+        let report = fx.nav_report(1_010_000, 900, 1);
+        let ixs = fx.update_nav_ixs(&valuation, &user.pubkey(), &report);
+        assert_rejected(fx.send_all(&ixs, &[&user]), Errors::NavReportsDisabled);
+
+        fx.set_valuation_signer(&valuation, 500, 3_600).unwrap();
+
+        //valid report, relayed by anyone
+        fx.send_all(&ixs, &[&user]).unwrap();
+        let vlt = fx.vault_state();
+        assert_eq!(vlt.total_base_assets, 1_010_000);
+        assert_eq!(vlt.nav_sequence, 1);
+        assert_eq!(vlt.nav_updated_at, 900);
+
+        let acc = fx.svm.get_account(&fx.nav_update(1)).unwrap();
+        let update = NavUpdate::try_deserialize(&mut &acc.data[..]).unwrap();
+        assert_eq!(update.nav, 1_010_000);
+        assert_eq!(update.previous_nav, 1_000_000);
+        assert_eq!(update.submitted_at, 1_000);
+
+        //signed by another key
+        let report = fx.nav_report(1_020_000, 950, 2);
+        let ixs = fx.update_nav_ixs(&Keypair::new(), &user.pubkey(), &report);
+        assert_rejected(fx.send_all(&ixs, &[&user]), Errors::InvalidEd25519Signer);
+
+        //arguments differ from the signed report
+        let [signature, _] = fx.update_nav_ixs(&valuation, &user.pubkey(), &report);
+        let tampered = fx.nav_report(1_030_000, 950, 2);
+        let ixs = [signature, fx.update_nav_ix(&user.pubkey(), &tampered)];
+        assert_rejected(fx.send_all(&ixs, &[&user]), Errors::InvalidNavReport);
+
+        //above the 5% max change
+        let report = fx.nav_report(1_070_000, 950, 2);
+        let ixs = fx.update_nav_ixs(&valuation, &user.pubkey(), &report);
+        assert_rejected(fx.send_all(&ixs, &[&user]), Errors::NavChangeTooLarge);
+
+        //older than the last report
+        let report = fx.nav_report(1_020_000, 899, 2);
+        let ixs = fx.update_nav_ixs(&valuation, &user.pubkey(), &report);
+        assert_rejected(fx.send_all(&ixs, &[&user]), Errors::InvalidNavTimestamp);
+
+        //sequences may skip but never go back
+        let report = fx.nav_report(960_000, 950, 5);
+        let ixs = fx.update_nav_ixs(&valuation, &user.pubkey(), &report);
+        fx.send_all(&ixs, &[&user]).unwrap();
+        let report = fx.nav_report(970_000, 960, 4);
+        let ixs = fx.update_nav_ixs(&valuation, &user.pubkey(), &report);
+        assert_rejected(fx.send_all(&ixs, &[&user]), Errors::NavSequenceReused);
+
+        //the loss is shared through the share price
+        assert_eq!(fx.vault_state().total_base_assets, 960_000);
+        let ix = fx.exit_ix("redeem", &user.pubkey(), 500_000);
+        fx.send(ix, &[&user]).unwrap();
+        assert_eq!(fx.vault_state().total_base_assets, 480_000);
+    }
+
+    #[test]
+    pub fn test_stale_reports_are_rejected() {
+        let mut fx = VaultFixture::new();
+        fx.set_unix_timestamp(1_000);
+        let user = fx.create_depositor(2_000_000);
+        let ix = fx.deposit_ix(&user.pubkey(), 1_000_000);
+        fx.send(ix, &[&user]).unwrap();
+
+        //reports must expire
+        let valuation = Keypair::new();
+        assert_rejected(
+            fx.set_valuation_signer(&valuation, 0, 0),
+            Errors::InvalidNavMaxAge,
+        );
+        fx.set_valuation_signer(&valuation, 0, 300).unwrap();
+
+        //a deposit lands after signing, the report would erase it
+        let report = fx.nav_report(1_010_000, 1_000, 1);
+        let ix = fx.deposit_ix(&user.pubkey(), 500_000);
+        fx.send(ix, &[&user]).unwrap();
+        let ixs = fx.update_nav_ixs(&valuation, &user.pubkey(), &report);
+        assert_rejected(fx.send_all(&ixs, &[&user]), Errors::NavReportOutdated);
+
+        //relayed past the max age
+        let report = fx.nav_report(1_515_000, 1_000, 1);
+        let ixs = fx.update_nav_ixs(&valuation, &user.pubkey(), &report);
+        fx.set_unix_timestamp(1_301);
+        assert_rejected(fx.send_all(&ixs, &[&user]), Errors::NavReportTooOld);
+
+        fx.set_unix_timestamp(1_300);
+        fx.send_all(&ixs, &[&user]).unwrap();
+        assert_eq!(fx.vault_state().total_base_assets, 1_515_000);
+    }
+}
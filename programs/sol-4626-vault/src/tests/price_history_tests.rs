@@ -0,0 +1,72 @@
+#[cfg(test)]
+mod test_price_history {
+    use crate::state::PriceHistory;
+    use crate::tests::helper::{assert_rejected, to_pubkey, VaultFixture};
+    use crate::util::{Errors, PRICE_SCALE};
+    use solana_sdk::message::{AccountMeta, Instruction};
+    use solana_sdk::signature::{Keypair, Signer};
+
+    fn twap_ix(fx: &VaultFixture, window: i64) -> Instruction {
+        fx.instruction(
+            "share_price_twap",
+            &window.to_le_bytes(),
+            vec![AccountMeta::new_readonly(fx.price_history(), false)],
+        )
+    }
+
+    fn price_history(fx: &VaultFixture) -> PriceHistory {
+        let acc = fx.svm.get_account(&fx.price_history()).unwrap();
+        bytemuck::pod_read_unaligned::<PriceHistory>(&acc.data[8..8 + PriceHistory::MAX_SIZE])
+    }
+
+    #[test]
+    pub fn test_share_price_twap() {
+        let mut fx = VaultFixture::new();
+        let user = fx.create_depositor(1_000_000);
+
+        fx.set_unix_timestamp(1_000);
+        let ix = fx.deposit_ix(&user.pubkey(), 1_000_000);
+        fx.send(ix, &[&user]).unwrap();
+
+        //5% yield reported at 1_100
+        let valuation = Keypair::new();
+        fx.set_valuation_signer(&valuation, 0, 3_600).unwrap();
+
+        fx.set_unix_timestamp(1_100);
+        let report = fx.nav_report(1_050_000, 1_100, 1);
+        let ixs = fx.update_nav_ixs(&valuation, &user.pubkey(), &report);
+        fx.send_all(&ixs, &[&user]).unwrap();
+
+        let history = price_history(&fx);
+        assert_eq!(history.vault, to_pubkey(&fx.vault));
+        assert_eq!(history.latest.timestamp, 1_100);
+        assert_eq!(history.latest.price, 1_050_000_000);
+
+        //a round trip within one second leaves a single observation
+        fx.set_unix_timestamp(1_200);
+        let ix = fx.exit_ix("redeem", &user.pubkey(), 100_000);
+        fx.send(ix, &[&user]).unwrap();
+        let ix = fx.deposit_ix(&user.pubkey(), 105_000);
+        fx.send(ix, &[&user]).unwrap();
+        assert_eq!(price_history(&fx).count, history.count + 1);
+
+        //100s at 1.00 then 100s at 1.05
+        let meta = fx.send(twap_ix(&fx, 200), &[&user]).unwrap();
+        let twap = u64::from_le_bytes(meta.return_data.data[..8].try_into().unwrap());
+        assert_eq!(twap, 1_025 * PRICE_SCALE / 1_000);
+
+        let meta = fx.send(twap_ix(&fx, 100), &[&user]).unwrap();
+        let twap = u64::from_le_bytes(meta.return_data.data[..8].try_into().unwrap());
+        assert_eq!(twap, 1_050 * PRICE_SCALE / 1_000);
+
+        //window beyond the recorded history
+        assert_rejected(
+            fx.send(twap_ix(&fx, 10_000), &[&user]),
+            Errors::InsufficientPriceHistory,
+        );
+        assert_rejected(
+            fx.send(twap_ix(&fx, 0), &[&user]),
+            Errors::InvalidTwapWindow,
+        );
+    }
+}
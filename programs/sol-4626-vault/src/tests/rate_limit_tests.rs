@@ -0,0 +1,43 @@
+#[cfg(test)]
+mod test_rate_limit {
+    use crate::tests::helper::{assert_rejected, ata, VaultFixture};
+    use crate::util::Errors;
+    use solana_sdk::signature::Signer;
+
+    #[test]
+    pub fn test_outflow_limit_decays_over_window() {
+        let mut fx = VaultFixture::new();
+        fx.set_unix_timestamp(1_000);
+
+        //at most 10% of total assets per day
+        let mut args = 1_000u16.to_le_bytes().to_vec();
+        args.extend_from_slice(&86_400i64.to_le_bytes());
+        fx.send_admin("set_outflow_limit", &args).unwrap();
+
+        let whale = fx.create_depositor(1_000_000);
+        let whale_key = whale.pubkey();
+        let ix = fx.deposit_ix(&whale_key, 1_000_000);
+        fx.send(ix, &[&whale]).unwrap();
+
+        //beyond the limit the whale has to queue an async redemption
+        let ix = fx.exit_ix("withdraw", &whale_key, 100_001);
+        assert_rejected(fx.send(ix, &[&whale]), Errors::WithdrawalRateLimited);
+        let ix = fx.exit_ix("withdraw", &whale_key, 100_000);
+        fx.send(ix, &[&whale]).unwrap();
+        assert_eq!(fx.vault_state().outflow_counter, 100_000);
+
+        let ix = fx.exit_ix("withdraw", &whale_key, 1);
+        assert_rejected(fx.send(ix, &[&whale]), Errors::WithdrawalRateLimited);
+        let ix = fx.request_redeem_ix(&whale_key, 500_000);
+        fx.send(ix, &[&whale]).unwrap();
+
+        //capacity comes back as the counter decays
+        fx.set_unix_timestamp(1_000 + 43_200);
+        let ix = fx.exit_ix("withdraw", &whale_key, 30_000);
+        fx.send(ix, &[&whale]).unwrap();
+        assert_eq!(
+            fx.token_balance(&ata(&whale_key, &fx.base_asset_mint)),
+            130_000
+        );
+    }
+}
@@ -0,0 +1,86 @@
+#[cfg(test)]
+mod test_redeem_request {
+    use crate::state::RedeemRequest;
+    use crate::tests::helper::{assert_rejected, ata, VaultFixture};
+    use crate::util::Errors;
+    use anchor_lang::AccountDeserialize;
+    use solana_sdk::signature::Signer;
+
+    fn request(fx: &VaultFixture, owner: &solana_sdk::message::Address) -> RedeemRequest {
+        let acc = fx.svm.get_account(&fx.redeem_request(owner)).unwrap();
+        RedeemRequest::try_deserialize(&mut acc.data.as_slice()).unwrap()
+    }
+
+    #[test]
+    pub fn test_fulfill_requires_idle_liquidity() {
+        let mut fx = VaultFixture::new();
+        let user = fx.create_depositor(1_000_000);
+        let user_key = user.pubkey();
+        let admin = fx.admin.insecure_clone();
+
+        let ix = fx.deposit_ix(&user_key, 1_000_000);
+        fx.send(ix, &[&user]).unwrap();
+
+        //most of the liquidity is allocated away
+        let target = fx.create_strategy(&admin);
+        let ix = fx.set_allocation_target_ix(&target, u64::MAX, 86_400, true);
+        fx.send(ix, &[&admin]).unwrap();
+        let ix = fx.allocate_ix(&target, 900_000);
+        fx.send(ix, &[&admin]).unwrap();
+
+        //shares are escrowed while the request is pending
+        let ix = fx.request_redeem_ix(&user_key, 500_000);
+        fx.send(ix, &[&user]).unwrap();
+        assert_eq!(fx.token_balance(&ata(&user_key, &fx.shares_mint)), 500_000);
+        assert_eq!(request(&fx, &user_key).pending_shares, 500_000);
+        assert_eq!(fx.vault_state().pending_redeem_shares, 500_000);
+
+        //not enough idle liquidity to fulfill
+        let ix = fx.fulfill_redeem_ix(&user_key);
+        assert_rejected(fx.send(ix, &[&admin]), Errors::InsufficientVaultLiquidity);
+
+        //nothing to claim yet
+        let ix = fx.claim_redeem_ix(&user_key);
+        assert_rejected(fx.send(ix, &[&user]), Errors::NoClaimableRedeemRequest);
+    }
+
+    #[test]
+    pub fn test_fulfilled_assets_are_reserved() {
+        let mut fx = VaultFixture::new();
+        let user = fx.create_depositor(1_000_000);
+        let user_key = user.pubkey();
+        let admin = fx.admin.insecure_clone();
+
+        let ix = fx.deposit_ix(&user_key, 1_000_000);
+        fx.send(ix, &[&user]).unwrap();
+
+        let ix = fx.request_redeem_ix(&user_key, 400_000);
+        fx.send(ix, &[&user]).unwrap();
+        let ix = fx.fulfill_redeem_ix(&user_key);
+        fx.send(ix, &[&admin]).unwrap();
+
+        let req = request(&fx, &user_key);
+        assert_eq!(req.pending_shares, 0);
+        assert_eq!(req.claimable_assets, 400_000);
+        let vlt = fx.vault_state();
+        assert_eq!(vlt.total_base_assets, 600_000);
+        assert_eq!(vlt.reserved_assets, 400_000);
+        assert_eq!(vlt.pending_redeem_shares, 0);
+
+        //reserved assets cannot be allocated
+        let target = fx.create_strategy(&admin);
+        let ix = fx.set_allocation_target_ix(&target, u64::MAX, 86_400, true);
+        fx.send(ix, &[&admin]).unwrap();
+        let ix = fx.allocate_ix(&target, 700_000);
+        assert_rejected(fx.send(ix, &[&admin]), Errors::InsufficientBaseAssetBalance);
+
+        let ix = fx.claim_redeem_ix(&user_key);
+        fx.send(ix, &[&user]).unwrap();
+        assert_eq!(
+            fx.token_balance(&ata(&user_key, &fx.base_asset_mint)),
+            400_000
+        );
+        assert_eq!(request(&fx, &user_key).claimable_assets, 0);
+        assert_eq!(fx.vault_state().reserved_assets, 0);
+    }
+}
@@ -0,0 +1,39 @@
+#[cfg(test)]
+mod test_shares_metadata {
+    use crate::tests::helper::{assert_rejected, VaultFixture, SHARES_NAME, SHARES_SYMBOL};
+    use crate::util::{Errors, SharesMetadata};
+    use anchor_lang::error::ErrorCode;
+    use solana_sdk::signature::Signer;
+
+    fn shares_uri(fx: &VaultFixture) -> String {
+        let acc = fx.svm.get_account(&fx.shares_metadata).unwrap();
+        SharesMetadata::read(&acc.data).unwrap().2
+    }
+
+    #[test]
+    pub fn test_admin_updates_shares_uri() {
+        let mut fx = VaultFixture::new();
+
+        let admin = fx.admin.insecure_clone();
+        let ix = fx.update_shares_uri_ix(&admin.pubkey(), "https://example.com/v2.json");
+        fx.send(ix, &[&admin]).unwrap();
+        assert_eq!(shares_uri(&fx), "https://example.com/v2.json");
+        //name and symbol are kept
+        let acc = fx.svm.get_account(&fx.shares_metadata).unwrap();
+        let (name, symbol, _) = SharesMetadata::read(&acc.data).unwrap();
+        assert_eq!(
+            (name.as_str(), symbol.as_str()),
+            (SHARES_NAME, SHARES_SYMBOL)
+        );
+
+        //only the vault admin
+        let user = fx.create_depositor(0);
+        let ix = fx.update_shares_uri_ix(&user.pubkey(), "https://evil.com");
+        assert_rejected(fx.send(ix, &[&user]), ErrorCode::ConstraintHasOne);
+
+        //bounded by the metadata account size
+        let ix = fx.update_shares_uri_ix(&admin.pubkey(), &"u".repeat(201));
+        assert_rejected(fx.send(ix, &[&admin]), Errors::InvalidSharesMetadata);
+        assert_eq!(shares_uri(&fx), "https://example.com/v2.json");
+    }
+}
@@ -0,0 +1,147 @@
+#[cfg(test)]
+mod test_shutdown {
+    use crate::state::AllocationTarget;
+    use crate::tests::helper::{assert_rejected, ata, VaultFixture};
+    use crate::util::Errors;
+    use anchor_lang::AccountDeserialize;
+    use litesvm_token::MintTo;
+    use solana_sdk::signature::{Keypair, Signer};
+
+    #[test]
+    pub fn test_shutdown_is_withdraw_only() {
+        let mut fx = VaultFixture::new();
+        let user = fx.create_depositor(2_000_000);
+        let user_key = user.pubkey();
+
+        //deposit before shutdown
+        let ix = fx.deposit_ix(&user_key, 1_000_000);
+        fx.send(ix, &[&user]).unwrap();
+        assert_eq!(
+            fx.token_balance(&ata(&user_key, &fx.shares_mint)),
+            1_000_000
+        );
+
+        //admin shuts the vault down
+        let admin = fx.admin.insecure_clone();
+        let ix = fx.shutdown_ix(&admin.pubkey());
+        fx.send(ix, &[&admin]).unwrap();
+        assert!(fx.vault_state().shutdown.is_true());
+
+        //deposits are rejected
+        let ix = fx.deposit_ix(&user_key, 1_000_000);
+        assert_rejected(fx.send(ix, &[&user]), Errors::VaultShutdown);
+
+        //allocations are rejected
+        let target = fx.create_strategy(&admin);
+        let ix = fx.allocate_ix(&target, 100_000);
+        assert_rejected(fx.send(ix, &[&admin]), Errors::VaultShutdown);
+
+        //withdraw and redeem keep working
+        let ix = fx.exit_ix("withdraw", &user_key, 400_000);
+        fx.send(ix, &[&user]).unwrap();
+        let ix = fx.exit_ix("redeem", &user_key, 600_000);
+        fx.send(ix, &[&user]).unwrap();
+
+        assert_eq!(fx.token_balance(&ata(&user_key, &fx.shares_mint)), 0);
+        assert_eq!(
+            fx.token_balance(&ata(&user_key, &fx.base_asset_mint)),
+            2_000_000
+        );
+        assert_eq!(fx.vault_state().total_base_assets, 0);
+
+        //shutdown cannot be triggered twice
+        let ix = fx.shutdown_ix(&admin.pubkey());
+        assert_rejected(fx.send(ix, &[&admin]), Errors::AlreadyShutdown);
+    }
+
+    #[test]
+    pub fn test_admin_force_deallocates() {
+        let mut fx = VaultFixture::new();
+        let admin = fx.admin.insecure_clone();
+        let user = fx.create_depositor(1_000_000);
+        let user_key = user.pubkey();
+        let ix = fx.deposit_ix(&user_key, 1_000_000);
+        fx.send(ix, &[&user]).unwrap();
+
+        let strategy = Keypair::new();
+        fx.svm.airdrop(&strategy.pubkey(), 1_000_000_000).unwrap();
+        let target = fx.create_strategy(&strategy);
+        let ix = fx.set_allocation_target_ix(&target, u64::MAX, 86_400, true);
+        fx.send(ix, &[&admin]).unwrap();
+        let ix = fx.allocate_ix(&target, 600_000);
+        fx.send(ix, &[&admin]).unwrap();
+
+        let ix = fx.shutdown_ix(&admin.pubkey());
+        fx.send(ix, &[&admin]).unwrap();
+
+        //the strategy does not sign
+        let ix = fx.deallocate_ix(&target, 600_000);
+        fx.send(ix, &[&admin]).unwrap();
+        assert_eq!(fx.token_balance(&target), 0);
+        assert_eq!(fx.token_balance(&fx.vault_base_asset_ata), 1_000_000);
+
+        let ix = fx.exit_ix("redeem", &user_key, 1_000_000);
+        fx.send(ix, &[&user]).unwrap();
+        assert_eq!(
+            fx.token_balance(&ata(&user_key, &fx.base_asset_mint)),
+            1_000_000
+        );
+    }
+
+    #[test]
+    pub fn test_deallocated_profit_is_credited() {
+        let mut fx = VaultFixture::new();
+        let admin = fx.admin.insecure_clone();
+        let user = fx.create_depositor(1_000_000);
+        let ix = fx.deposit_ix(&user.pubkey(), 1_000_000);
+        fx.send(ix, &[&user]).unwrap();
+
+        let target = fx.create_strategy(&admin);
+        let ix = fx.set_allocation_target_ix(&target, u64::MAX, 86_400, true);
+        fx.send(ix, &[&admin]).unwrap();
+        let ix = fx.allocate_ix(&target, 600_000);
+        fx.send(ix, &[&admin]).unwrap();
+
+        //the strategy earns 60_000 into the escrow
+        MintTo::new(
+            &mut fx.svm,
+            &fx.mint_authority,
+            &fx.base_asset_mint,
+            &target,
+            60_000,
+        )
+        .token_program_id(&fx.token_program)
+        .send()
+        .unwrap();
+
+        //repaying part of the debt is no profit
+        let ix = fx.deallocate_ix(&target, 400_000);
+        fx.send(ix, &[&admin]).unwrap();
+        assert_eq!(fx.vault_state().total_base_assets, 1_000_000);
+
+        let ix = fx.deallocate_ix(&target, 260_000);
+        fx.send(ix, &[&admin]).unwrap();
+        assert_eq!(fx.vault_state().total_base_assets, 1_060_000);
+        let acc = fx.svm.get_account(&fx.allocation_target(&target)).unwrap();
+        let strategy = AllocationTarget::try_deserialize(&mut &acc.data[..]).unwrap();
+        assert_eq!(strategy.allocated_assets, 0);
+
+        let ix = fx.exit_ix("redeem", &user.pubkey(), 1_000_000);
+        fx.send(ix, &[&user]).unwrap();
+        assert_eq!(
+            fx.token_balance(&ata(&user.pubkey(), &fx.base_asset_mint)),
+            1_060_000
+        );
+    }
+
+    #[test]
+    pub fn test_shutdown_unauthorized() {
+        let mut fx = VaultFixture::new();
+        let stranger = Keypair::new();
+        fx.svm.airdrop(&stranger.pubkey(), 1_000_000_000).unwrap();
+
+        let ix = fx.shutdown_ix(&stranger.pubkey());
+        assert_rejected(fx.send(ix, &[&stranger]), Errors::Unauthorized);
+        assert!(!fx.vault_state().shutdown.is_true());
+    }
+}
@@ -0,0 +1,95 @@
+#[cfg(test)]
+mod test_soulbound {
+    use crate::tests::helper::{
+        assert_rejected, to_instruction, to_pubkey, VaultFixture, BASE_ASSET_DECIMALS,
+    };
+    use crate::util::Errors;
+    use anchor_spl::token_2022::spl_token_2022;
+    use anchor_spl::token_2022::spl_token_2022::error::TokenError;
+    use litesvm_token::{CreateAssociatedTokenAccount, TOKEN_ID};
+    use solana_sdk::signature::{Keypair, Signer};
+
+    fn soulbound_fixture() -> VaultFixture {
+        let mut fx = VaultFixture::with_token_2022_mint(&[], |_, _| vec![]);
+        fx.initialize(0, true, 0).unwrap();
+        fx
+    }
+
+    #[test]
+    pub fn test_soulbound_shares_over_spl_base_asset() {
+        let mut fx = VaultFixture::with_token_2022_shares();
+        fx.initialize(0, true, 0).unwrap();
+        let vlt = fx.vault_state();
+        assert!(vlt.soulbound_shares.is_true());
+        assert_eq!(vlt.token_program.to_bytes(), TOKEN_ID.to_bytes());
+        assert_eq!(
+            vlt.shares_token_program.to_bytes(),
+            spl_token_2022::ID.to_bytes()
+        );
+
+        //base assets move under SPL Token, shares under Token-2022
+        let user = fx.create_depositor(1_000_000);
+        let user_key = user.pubkey();
+        let ix = fx.deposit_ix(&user_key, 1_000_000);
+        fx.send(ix, &[&user]).unwrap();
+        let user_shares = fx.ata(&user_key, &fx.shares_mint);
+        assert_eq!(fx.token_balance(&user_shares), 1_000_000);
+
+        let ix = fx.exit_ix("redeem", &user_key, 1_000_000);
+        fx.send(ix, &[&user]).unwrap();
+        assert_eq!(fx.token_balance(&user_shares), 0);
+        assert_eq!(
+            fx.token_balance(&fx.ata(&user_key, &fx.base_asset_mint)),
+            1_000_000
+        );
+    }
+
+    #[test]
+    pub fn test_shares_cannot_be_transferred() {
+        let mut fx = soulbound_fixture();
+        assert!(fx.vault_state().soulbound_shares.is_true());
+
+        let user = fx.create_depositor(1_000_000);
+        let user_key = user.pubkey();
+        let user_shares = fx.ata(&user_key, &fx.shares_mint);
+        let ix = fx.deposit_ix(&user_key, 1_000_000);
+        fx.send(ix, &[&user]).unwrap();
+        assert_eq!(fx.token_balance(&user_shares), 1_000_000);
+
+        //a plain token transfer to another wallet is refused by the shares mint
+        let friend = Keypair::new();
+        let friend_shares = CreateAssociatedTokenAccount::new(&mut fx.svm, &user, &fx.shares_mint)
+            .owner(&friend.pubkey())
+            .token_program_id(&fx.shares_token_program)
+            .send()
+            .unwrap();
+        let ix = spl_token_2022::instruction::transfer_checked(
+            &spl_token_2022::ID,
+            &to_pubkey(&user_shares),
+            &to_pubkey(&fx.shares_mint),
+            &to_pubkey(&friend_shares),
+            &to_pubkey(&user_key),
+            &[],
+            100_000,
+            BASE_ASSET_DECIMALS,
+        )
+        .unwrap();
+        assert_rejected(
+            fx.send(to_instruction(ix), &[&user]),
+            TokenError::NonTransferable as u32,
+        );
+        assert_eq!(fx.token_balance(&friend_shares), 0);
+
+        //so is the redeem request escrow
+        let ix = fx.request_redeem_ix(&user_key, 100_000);
+        assert_rejected(fx.send(ix, &[&user]), Errors::SharesNonTransferable);
+
+        //while exits still burn from the holder's ATA
+        let ix = fx.exit_ix("withdraw", &user_key, 400_000);
+        fx.send(ix, &[&user]).unwrap();
+        let ix = fx.exit_ix("redeem", &user_key, 600_000);
+        fx.send(ix, &[&user]).unwrap();
+        assert_eq!(fx.token_balance(&user_shares), 0);
+        assert_eq!(fx.vault_state().total_base_assets, 0);
+    }
+}
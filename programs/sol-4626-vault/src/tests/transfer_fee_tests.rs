@@ -0,0 +1,66 @@
+#[cfg(test)]
+mod test_transfer_fee {
+    use crate::tests::helper::VaultFixture;
+    use solana_sdk::signature::Signer;
+
+    //1% on every base asset transfer
+    const FEE_BPS: u16 = 100;
+    const MAX_FEE: u64 = 1_000_000;
+
+    fn assert_accounting(fx: &VaultFixture) {
+        let vault_balance = fx.token_balance(&fx.vault_base_asset_ata);
+        assert_eq!(fx.vault_state().total_base_assets, vault_balance);
+    }
+
+    #[test]
+    pub fn test_shares_minted_on_received_amount() {
+        let mut fx = VaultFixture::with_transfer_fee(FEE_BPS, MAX_FEE);
+
+        let user = fx.create_depositor(2_000_000);
+        let user_key = user.pubkey();
+        let user_shares = fx.ata(&user_key, &fx.shares_mint);
+        let user_base = fx.ata(&user_key, &fx.base_asset_mint);
+
+        //the vault only gets 990k out of the 1M pulled
+        let ix = fx.deposit_ix(&user_key, 1_000_000);
+        fx.send(ix, &[&user]).unwrap();
+        assert_eq!(fx.token_balance(&user_shares), 990_000);
+        assert_eq!(fx.vault_state().total_base_assets, 990_000);
+        assert_accounting(&fx);
+
+        //mint pulls enough to net the shares' worth
+        let before = fx.token_balance(&user_base);
+        let ix = fx.enter_ix("mint", &user_key, 99_000);
+        fx.send(ix, &[&user]).unwrap();
+        assert_eq!(fx.token_balance(&user_shares), 1_089_000);
+        assert!(before - fx.token_balance(&user_base) > 99_000);
+        assert!(fx.vault_state().total_base_assets >= 1_089_000);
+        assert_accounting(&fx);
+    }
+
+    #[test]
+    pub fn test_withdraw_delivers_net_assets() {
+        let mut fx = VaultFixture::with_transfer_fee(FEE_BPS, MAX_FEE);
+
+        let user = fx.create_depositor(1_000_000);
+        let user_key = user.pubkey();
+        let user_base = fx.ata(&user_key, &fx.base_asset_mint);
+        let ix = fx.deposit_ix(&user_key, 1_000_000);
+        fx.send(ix, &[&user]).unwrap();
+
+        //withdraw is exact on the receiving side, the vault pays the fee on top
+        let before = fx.token_balance(&user_base);
+        let ix = fx.exit_ix("withdraw", &user_key, 99_000);
+        fx.send(ix, &[&user]).unwrap();
+        assert!(fx.token_balance(&user_base) - before >= 99_000);
+        assert!(fx.vault_state().total_base_assets < 990_000 - 99_000);
+        assert_accounting(&fx);
+
+        //redeem is exact on the shares side, the depositor eats the fee
+        let before = fx.token_balance(&user_base);
+        let ix = fx.exit_ix("redeem", &user_key, 100_000);
+        fx.send(ix, &[&user]).unwrap();
+        assert_eq!(fx.token_balance(&user_base) - before, 99_000);
+        assert_accounting(&fx);
+    }
+}
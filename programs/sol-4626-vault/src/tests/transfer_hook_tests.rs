@@ -0,0 +1,175 @@
+#[cfg(test)]
+mod test_transfer_hook {
+    use crate::tests::helper::{
+        assert_rejected, to_address, to_instruction, to_pubkey, VaultFixture, BASE_ASSET_DECIMALS,
+    };
+    use crate::util::Errors;
+    use crate::ID;
+    use anchor_lang::system_program;
+    use anchor_spl::token_2022::spl_token_2022;
+    use litesvm_token::CreateAssociatedTokenAccount;
+    use shares_transfer_hook::state::HookConfig;
+    use shares_transfer_hook::util::Errors as HookErrors;
+    use solana_sdk::message::{AccountMeta, Address, Instruction};
+    use solana_sdk::signature::{Keypair, Signer};
+
+    fn hook_fixture(mode: u8) -> VaultFixture {
+        let mut fx = VaultFixture::with_token_2022_mint(&[], |_, _| vec![]);
+        fx.initialize(0, false, mode).unwrap();
+        fx
+    }
+
+    fn list_ix(fx: &VaultFixture, name: &str, wallet: &Address) -> Instruction {
+        let entry = if name == "allow_address" {
+            fx.allowed_address(wallet)
+        } else {
+            fx.blocked_address(wallet)
+        };
+        fx.instruction(
+            name,
+            wallet.as_ref(),
+            vec![
+                AccountMeta::new(fx.admin.pubkey(), true),
+                AccountMeta::new_readonly(fx.vault, false),
+                AccountMeta::new(entry, false),
+                AccountMeta::new_readonly(to_address(&system_program::ID), false),
+            ],
+        )
+    }
+
+    /// Extra accounts the hook resolves for a transfer between `source` and `destination` owners.
+    fn hook_accounts(
+        fx: &VaultFixture,
+        source: &Address,
+        destination: &Address,
+    ) -> Vec<AccountMeta> {
+        let entry = |wallet: &Address| {
+            if fx.vault_state().transfer_hook_mode == HookConfig::ALLOWLIST {
+                fx.allowed_address(wallet)
+            } else {
+                fx.blocked_address(wallet)
+            }
+        };
+        vec![
+            AccountMeta::new_readonly(fx.extra_account_meta_list(), false),
+            AccountMeta::new_readonly(fx.hook_config(), false),
+            AccountMeta::new_readonly(to_address(&ID), false),
+            AccountMeta::new_readonly(fx.vault, false),
+            AccountMeta::new_readonly(entry(source), false),
+            AccountMeta::new_readonly(entry(destination), false),
+            AccountMeta::new_readonly(to_address(&shares_transfer_hook::ID), false),
+        ]
+    }
+
+    fn transfer_shares_ix(
+        fx: &VaultFixture,
+        from: &Address,
+        to: &Address,
+        shares: u64,
+    ) -> Instruction {
+        let mut ix = to_instruction(
+            spl_token_2022::instruction::transfer_checked(
+                &spl_token_2022::ID,
+                &to_pubkey(&fx.ata(from, &fx.shares_mint)),
+                &to_pubkey(&fx.shares_mint),
+                &to_pubkey(&fx.ata(to, &fx.shares_mint)),
+                &to_pubkey(from),
+                &[],
+                shares,
+                BASE_ASSET_DECIMALS,
+            )
+            .unwrap(),
+        );
+        ix.accounts.extend(hook_accounts(fx, from, to));
+        ix
+    }
+
+    fn create_shares_ata(fx: &mut VaultFixture, payer: &Keypair, owner: &Address) -> Address {
+        let shares_mint = fx.shares_mint;
+        let token_program = fx.shares_token_program;
+        CreateAssociatedTokenAccount::new(&mut fx.svm, payer, &shares_mint)
+            .owner(owner)
+            .token_program_id(&token_program)
+            .send()
+            .unwrap()
+    }
+
+    #[test]
+    pub fn test_allowlist_transfers() {
+        let mut fx = hook_fixture(HookConfig::ALLOWLIST);
+        assert_eq!(fx.vault_state().transfer_hook_mode, HookConfig::ALLOWLIST);
+
+        let user = fx.create_depositor(1_000_000);
+        let user_key = user.pubkey();
+        let ix = fx.deposit_ix(&user_key, 1_000_000);
+        fx.send(ix, &[&user]).unwrap();
+
+        let friend = Keypair::new().pubkey();
+        let friend_shares = create_shares_ata(&mut fx, &user, &friend);
+
+        //neither side listed
+        let ix = transfer_shares_ix(&fx, &user_key, &friend, 100_000);
+        assert_rejected(fx.send(ix, &[&user]), HookErrors::SourceNotAllowed);
+
+        //only the sender listed
+        let admin = fx.admin.insecure_clone();
+        let ix = list_ix(&fx, "allow_address", &user_key);
+        fx.send(ix, &[&admin]).unwrap();
+        let ix = transfer_shares_ix(&fx, &user_key, &friend, 100_000);
+        assert_rejected(fx.send(ix, &[&user]), HookErrors::DestinationNotAllowed);
+
+        //both listed
+        let ix = list_ix(&fx, "allow_address", &friend);
+        fx.send(ix, &[&admin]).unwrap();
+        let ix = transfer_shares_ix(&fx, &user_key, &friend, 100_000);
+        fx.send(ix, &[&user]).unwrap();
+        assert_eq!(fx.token_balance(&friend_shares), 100_000);
+
+        //the vault authority escrow is exempt
+        let mut ix = fx.request_redeem_ix(&user_key, 200_000);
+        ix.accounts
+            .extend(hook_accounts(&fx, &user_key, &fx.vault_authority));
+        fx.send(ix, &[&user]).unwrap();
+        assert_eq!(
+            fx.token_balance(&fx.ata(&fx.vault_authority, &fx.shares_mint)),
+            200_000
+        );
+    }
+
+    #[test]
+    pub fn test_blocklist_transfers() {
+        let mut fx = hook_fixture(HookConfig::BLOCKLIST);
+
+        let user = fx.create_depositor(1_000_000);
+        let user_key = user.pubkey();
+        let ix = fx.deposit_ix(&user_key, 1_000_000);
+        fx.send(ix, &[&user]).unwrap();
+
+        let friend = Keypair::new().pubkey();
+        let friend_shares = create_shares_ata(&mut fx, &user, &friend);
+        let ix = transfer_shares_ix(&fx, &user_key, &friend, 100_000);
+        fx.send(ix, &[&user]).unwrap();
+        assert_eq!(fx.token_balance(&friend_shares), 100_000);
+
+        //blocked recipient
+        let admin = fx.admin.insecure_clone();
+        let ix = list_ix(&fx, "block_address", &friend);
+        fx.send(ix, &[&admin]).unwrap();
+        let ix = transfer_shares_ix(&fx, &user_key, &friend, 100_000);
+        assert_rejected(fx.send(ix, &[&user]), HookErrors::DestinationBlocked);
+        assert_eq!(fx.token_balance(&friend_shares), 100_000);
+    }
+
+    #[test]
+    pub fn test_invalid_hook_setup() {
+        //unknown mode
+        let mut fx = VaultFixture::with_token_2022_mint(&[], |_, _| vec![]);
+        assert_rejected(fx.initialize(0, false, 3), Errors::InvalidTransferHook);
+
+        //soulbound shares never transfer, a hook would be dead weight
+        assert_rejected(
+            fx.initialize(0, true, HookConfig::ALLOWLIST),
+            Errors::InvalidTransferHook,
+        );
+    }
+}
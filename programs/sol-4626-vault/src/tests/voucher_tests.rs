@@ -0,0 +1,96 @@
+#[cfg(test)]
+mod test_deposit_voucher {
+    use crate::tests::helper::{assert_rejected, ed25519_ix, to_pubkey, VaultFixture};
+    use crate::util::{DepositVoucher, Errors};
+    use anchor_lang::AnchorSerialize;
+    use solana_sdk::clock::Clock;
+    use solana_sdk::signature::{Keypair, Signer};
+
+    fn voucher_message(
+        fx: &VaultFixture,
+        depositor: &Keypair,
+        max_amount: u64,
+        expiry: i64,
+        nonce: u64,
+    ) -> Vec<u8> {
+        DepositVoucher {
+            vault: to_pubkey(&fx.vault),
+            depositor: to_pubkey(&depositor.pubkey()),
+            max_amount,
+            expiry,
+            nonce,
+        }
+        .try_to_vec()
+        .unwrap()
+    }
+
+    #[test]
+    pub fn test_deposit_requires_signed_voucher() {
+        let mut fx = VaultFixture::new();
+        let compliance = Keypair::new();
+        fx.send_admin("set_compliance_signer", compliance.pubkey().as_ref())
+            .unwrap();
+
+        let mut clock = fx.svm.get_sysvar::<Clock>();
+        clock.unix_timestamp = 1_000;
+        fx.svm.set_sysvar(&clock);
+
+        let user = fx.create_depositor(2_000_000);
+
+        //no voucher
+        let ix = fx.deposit_ix(&user.pubkey(), 500_000);
+        assert_rejected(fx.send(ix, &[&user]), Errors::MissingEd25519Instruction);
+
+        //valid voucher
+        let message = voucher_message(&fx, &user, 500_000, 2_000, 1);
+        let ixs = [
+            ed25519_ix(&compliance, &message),
+            fx.deposit_ix(&user.pubkey(), 500_000),
+        ];
+        fx.send_all(&ixs, &[&user]).unwrap();
+
+        //replayed voucher
+        let ixs = [
+            ed25519_ix(&compliance, &message),
+            fx.deposit_ix(&user.pubkey(), 500_000),
+        ];
+        assert_rejected(fx.send_all(&ixs, &[&user]), Errors::VoucherNonceReused);
+
+        //amount above voucher
+        let message = voucher_message(&fx, &user, 500_000, 2_000, 2);
+        let ixs = [
+            ed25519_ix(&compliance, &message),
+            fx.deposit_ix(&user.pubkey(), 500_001),
+        ];
+        assert_rejected(fx.send_all(&ixs, &[&user]), Errors::VoucherAmountExceeded);
+
+        //expired voucher
+        let message = voucher_message(&fx, &user, 500_000, 999, 2);
+        let ixs = [
+            ed25519_ix(&compliance, &message),
+            fx.deposit_ix(&user.pubkey(), 500_000),
+        ];
+        assert_rejected(fx.send_all(&ixs, &[&user]), Errors::VoucherExpired);
+
+        //voucher signed by another key
+        let message = voucher_message(&fx, &user, 500_000, 2_000, 2);
+        let ixs = [
+            ed25519_ix(&Keypair::new(), &message),
+            fx.deposit_ix(&user.pubkey(), 500_000),
+        ];
+        assert_rejected(fx.send_all(&ixs, &[&user]), Errors::InvalidEd25519Signer);
+
+        //next nonce goes through
+        let ixs = [
+            ed25519_ix(&compliance, &message),
+            fx.deposit_ix(&user.pubkey(), 500_000),
+        ];
+        fx.send_all(&ixs, &[&user]).unwrap();
+
+        assert_eq!(fx.vault_state().total_base_assets, 1_000_000);
+        assert_eq!(
+            fx.vault_state().compliance_signer,
+            to_pubkey(&compliance.pubkey())
+        );
+    }
+}
@@ -0,0 +1,51 @@
+#[cfg(test)]
+mod test_write_down {
+    use crate::state::AllocationTarget;
+    use crate::tests::helper::{assert_rejected, VaultFixture};
+    use crate::util::Errors;
+    use anchor_lang::error::ErrorCode;
+    use anchor_lang::AccountDeserialize;
+    use solana_sdk::signature::Signer;
+
+    #[test]
+    pub fn test_write_down_socialises_loss() {
+        let mut fx = VaultFixture::new();
+        let admin = fx.admin.insecure_clone();
+        let alice = fx.create_depositor(600_000);
+        let bob = fx.create_depositor(400_000);
+        let ix = fx.deposit_ix(&alice.pubkey(), 600_000);
+        fx.send(ix, &[&alice]).unwrap();
+        let ix = fx.deposit_ix(&bob.pubkey(), 400_000);
+        fx.send(ix, &[&bob]).unwrap();
+
+        let target = fx.create_strategy(&admin);
+        let ix = fx.set_allocation_target_ix(&target, u64::MAX, 86_400, true);
+        fx.send(ix, &[&admin]).unwrap();
+        let ix = fx.allocate_ix(&target, 500_000);
+        fx.send(ix, &[&admin]).unwrap();
+
+        //admin only
+        let ix = fx.write_down_ix(&bob.pubkey(), &target, 200_000);
+        assert_rejected(fx.send(ix, &[&bob]), ErrorCode::ConstraintHasOne);
+
+        let ix = fx.write_down_ix(&admin.pubkey(), &target, 200_000);
+        fx.send(ix, &[&admin]).unwrap();
+        assert_eq!(fx.vault_state().total_base_assets, 800_000);
+        let acc = fx.svm.get_account(&fx.allocation_target(&target)).unwrap();
+        let strategy = AllocationTarget::try_deserialize(&mut &acc.data[..]).unwrap();
+        assert_eq!(strategy.allocated_assets, 300_000);
+
+        //beyond the remaining debt
+        let ix = fx.write_down_ix(&admin.pubkey(), &target, 300_001);
+        assert_rejected(fx.send(ix, &[&admin]), Errors::WriteDownExceedsDebt);
+
+        //both holders take 20%
+        let ix = fx.exit_ix("redeem", &bob.pubkey(), 400_000);
+        fx.send(ix, &[&bob]).unwrap();
+        assert_eq!(
+            fx.token_balance(&fx.ata(&bob.pubkey(), &fx.base_asset_mint)),
+            320_000
+        );
+        assert_eq!(fx.vault_state().total_base_assets, 480_000);
+    }
+}
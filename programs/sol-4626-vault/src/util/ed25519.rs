@@ -0,0 +1,127 @@
+use crate::util::Errors;
+use anchor_lang::prelude::*;
+use solana_instructions_sysvar::{load_current_index_checked, load_instruction_at_checked};
+use solana_sdk_ids::ed25519_program;
+
+const SIGNATURE_OFFSETS_START: usize = 2;
+const PUBKEY_SIZE: usize = 32;
+
+/// Returns the message signed by `signer` in the Ed25519 program instruction placed
+/// right before the current one.
+/// The runtime already verified the signature when it processed the precompile,
+/// so we only bind that instruction to the expected signer and extract the message.
+pub fn load_preceding_ed25519_message(
+    instructions_sysvar: &AccountInfo,
+    signer: &Pubkey,
+) -> Result<Vec<u8>> {
+    let current = load_current_index_checked(instructions_sysvar)?;
+    require!(current > 0, Errors::MissingEd25519Instruction);
+
+    let ix = load_instruction_at_checked(current as usize - 1, instructions_sysvar)?;
+    require_keys_eq!(
+        ix.program_id,
+        ed25519_program::ID,
+        Errors::MissingEd25519Instruction
+    );
+
+    parse_ed25519_message(&ix.data, signer)
+}
+
+/// Parses a single-signature Ed25519 instruction whose pubkey, signature and message
+/// all live in the instruction itself.
+pub fn parse_ed25519_message(data: &[u8], signer: &Pubkey) -> Result<Vec<u8>> {
+    require!(data.first() == Some(&1), Errors::InvalidEd25519Instruction);
+
+    // offsets: signature, signature ix, pubkey, pubkey ix, message, message size, message ix
+    let mut offsets = [0u16; 7];
+    for (i, offset) in offsets.iter_mut().enumerate() {
+        let at = SIGNATURE_OFFSETS_START + i * 2;
+        let bytes = data
+            .get(at..at + 2)
+            .ok_or(Errors::InvalidEd25519Instruction)?;
+        *offset = u16::from_le_bytes([bytes[0], bytes[1]]);
+    }
+    let [_, signature_ix, pubkey_offset, pubkey_ix, message_offset, message_size, message_ix] =
+        offsets;
+
+    require!(
+        signature_ix == u16::MAX && pubkey_ix == u16::MAX && message_ix == u16::MAX,
+        Errors::InvalidEd25519Instruction
+    );
+
+    let pubkey_offset = pubkey_offset as usize;
+    let pubkey = data
+        .get(pubkey_offset..pubkey_offset + PUBKEY_SIZE)
+        .ok_or(Errors::InvalidEd25519Instruction)?;
+    require!(pubkey == signer.as_ref(), Errors::InvalidEd25519Signer);
+
+    let message_offset = message_offset as usize;
+    let message = data
+        .get(message_offset..message_offset + message_size as usize)
+        .ok_or(Errors::InvalidEd25519Instruction)?;
+
+    Ok(message.to_vec())
+}
+
+#[cfg(test)]
+mod test_ed25519 {
+    use super::*;
+
+    /// Ed25519 instruction layout with a dummy signature, the precompile is not run here.
+    fn ed25519_data(pubkey: &Pubkey, message: &[u8]) -> Vec<u8> {
+        let pubkey_offset: u16 = 16;
+        let signature_offset: u16 = pubkey_offset + 32;
+        let message_offset: u16 = signature_offset + 64;
+
+        let mut data = vec![1, 0];
+        for value in [
+            signature_offset,
+            u16::MAX,
+            pubkey_offset,
+            u16::MAX,
+            message_offset,
+            message.len() as u16,
+            u16::MAX,
+        ] {
+            data.extend_from_slice(&value.to_le_bytes());
+        }
+        data.extend_from_slice(pubkey.as_ref());
+        data.extend_from_slice(&[7; 64]);
+        data.extend_from_slice(message);
+        data
+    }
+
+    #[test]
+    fn extracts_message_of_expected_signer() {
+        let signer = Pubkey::new_unique();
+        let data = ed25519_data(&signer, b"voucher");
+
+        assert_eq!(parse_ed25519_message(&data, &signer).unwrap(), b"voucher");
+    }
+
+    #[test]
+    fn rejects_other_signer() {
+        let data = ed25519_data(&Pubkey::new_unique(), b"voucher");
+
+        assert!(parse_ed25519_message(&data, &Pubkey::new_unique()).is_err());
+    }
+
+    #[test]
+    fn rejects_data_from_other_instructions() {
+        let signer = Pubkey::new_unique();
+        let mut data = ed25519_data(&signer, b"voucher");
+        // message instruction index
+        data[14..16].copy_from_slice(&0u16.to_le_bytes());
+
+        assert!(parse_ed25519_message(&data, &signer).is_err());
+    }
+
+    #[test]
+    fn rejects_truncated_data() {
+        let signer = Pubkey::new_unique();
+        let data = ed25519_data(&signer, b"voucher");
+
+        assert!(parse_ed25519_message(&data[..data.len() - 1], &signer).is_err());
+        assert!(parse_ed25519_message(&[], &signer).is_err());
+    }
+}
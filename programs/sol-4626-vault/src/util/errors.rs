@@ -20,4 +20,156 @@ pub enum Errors {
     AllocatePaused,
     #[msg("Invalid Ticker")]
     InvalidTicker,
+    #[msg("Vault has been shut down")]
+    VaultShutdown,
+    #[msg("Vault is already shut down")]
+    AlreadyShutdown,
+    #[msg("Signer is not authorized for this action")]
+    Unauthorized,
+    #[msg("Insufficient idle liquidity in the vault")]
+    InsufficientVaultLiquidity,
+    #[msg("Insufficient shares balance")]
+    InsufficientSharesBalance,
+    #[msg("Vault deposit cap exceeded")]
+    DepositCapExceeded,
+    #[msg("User deposit cap exceeded")]
+    UserDepositCapExceeded,
+    #[msg("Depositor is not in the allowlist")]
+    NotAllowlisted,
+    #[msg("Missing Ed25519 signature instruction")]
+    MissingEd25519Instruction,
+    #[msg("Invalid Ed25519 signature instruction")]
+    InvalidEd25519Instruction,
+    #[msg("Ed25519 instruction signed by an unexpected key")]
+    InvalidEd25519Signer,
+    #[msg("Invalid deposit voucher")]
+    InvalidVoucher,
+    #[msg("Deposit voucher issued for another vault or depositor")]
+    VoucherMismatch,
+    #[msg("Deposit voucher expired")]
+    VoucherExpired,
+    #[msg("Deposit exceeds voucher amount")]
+    VoucherAmountExceeded,
+    #[msg("Deposit voucher nonce already used")]
+    VoucherNonceReused,
+    #[msg("Address is blocked by the vault")]
+    AddressBlocked,
+    #[msg("Address is not blocked by the vault")]
+    AddressNotBlocked,
+    #[msg("No pending redeem request")]
+    NoPendingRedeemRequest,
+    #[msg("No claimable redeem request")]
+    NoClaimableRedeemRequest,
+    #[msg("Epochs are disabled for this vault")]
+    EpochsDisabled,
+    #[msg("Epoch has not reached its duration yet")]
+    EpochNotEnded,
+    #[msg("Epoch is not settled yet")]
+    EpochNotSettled,
+    #[msg("Invalid epoch duration")]
+    InvalidEpochDuration,
+    #[msg("Position is still in its lockup period")]
+    PositionLocked,
+    #[msg("Invalid lockup configuration")]
+    InvalidLockup,
+    #[msg("Withdrawal exceeds the vault outflow limit, use request_redeem")]
+    WithdrawalRateLimited,
+    #[msg("Invalid outflow limit configuration")]
+    InvalidOutflowLimit,
+    #[msg("Allocation destination is not approved")]
+    DestinationNotApproved,
+    #[msg("Allocation exceeds the destination period limit")]
+    AllocationLimitExceeded,
+    #[msg("Invalid allocation period")]
+    InvalidAllocationPeriod,
+    #[msg("Vault index must be the next index of the base asset mint")]
+    InvalidVaultIndex,
+    #[msg("Protocol is paused")]
+    ProtocolPaused,
+    #[msg("Invalid protocol fee")]
+    InvalidProtocolFee,
+    #[msg("No protocol fees to collect")]
+    NoProtocolFees,
+    #[msg("Invalid shares metadata")]
+    InvalidSharesMetadata,
+    #[msg("Unknown base asset mint extension flags")]
+    InvalidMintExtensions,
+    #[msg("Base asset mint has a freeze authority")]
+    FreezeAuthorityNotAllowed,
+    #[msg("Base asset mint has a permanent delegate")]
+    PermanentDelegateNotAllowed,
+    #[msg("Base asset mint has a transfer hook")]
+    TransferHookNotAllowed,
+    #[msg("Base asset mint creates frozen accounts by default")]
+    DefaultFrozenNotAllowed,
+    #[msg("Base asset mint is pausable")]
+    PausableNotAllowed,
+    #[msg("Base asset mint has a close authority")]
+    MintCloseAuthorityNotAllowed,
+    #[msg("Soulbound shares require a Token-2022 shares mint")]
+    SoulboundRequiresToken2022,
+    #[msg("Shares are non-transferable, escrow flows are unavailable")]
+    SharesNonTransferable,
+    #[msg("Invalid shares transfer hook configuration")]
+    InvalidTransferHook,
+    #[msg("Base asset is not the native SOL mint")]
+    NotNativeMint,
+    #[msg("Invalid price")]
+    InvalidPrice,
+    #[msg("Price feed does not price the asset")]
+    InvalidPriceFeed,
+    #[msg("Invalid basket exit fee")]
+    InvalidExitFee,
+    #[msg("Basket holds the maximum number of assets")]
+    BasketFull,
+    #[msg("Asset is already in the basket")]
+    AssetAlreadyInBasket,
+    #[msg("Asset is not in the basket")]
+    AssetNotInBasket,
+    #[msg("Basket accounts do not match the basket assets")]
+    InvalidBasketAccounts,
+    #[msg("Account is not a supported price oracle")]
+    InvalidOracleAccount,
+    #[msg("Oracle price is stale")]
+    StalePrice,
+    #[msg("Oracle price confidence interval is too wide")]
+    PriceConfidenceTooWide,
+    #[msg("Vault does not accept NAV reports")]
+    NavReportsDisabled,
+    #[msg("Invalid NAV report")]
+    InvalidNavReport,
+    #[msg("NAV report is for another vault")]
+    NavReportMismatch,
+    #[msg("NAV report sequence already used")]
+    NavSequenceReused,
+    #[msg("NAV report timestamp is out of order")]
+    InvalidNavTimestamp,
+    #[msg("NAV report changes total assets too much")]
+    NavChangeTooLarge,
+    #[msg("Invalid TWAP window")]
+    InvalidTwapWindow,
+    #[msg("Price history does not cover the TWAP window")]
+    InsufficientPriceHistory,
+    #[msg("Share price move trips the circuit breaker")]
+    CircuitBreakerTripped,
+    #[msg("Invalid circuit breaker configuration")]
+    InvalidCircuitBreaker,
+    #[msg("Circuit breaker is not tripped")]
+    CircuitBreakerNotTripped,
+    #[msg("Write-down exceeds the strategy's deployed assets")]
+    WriteDownExceedsDebt,
+    #[msg("Vault settles in epochs, use queue_deposit or queue_redeem")]
+    EpochsEnabled,
+    #[msg("Allocation target must be an escrow token account owned by the vault authority")]
+    TargetNotVaultOwned,
+    #[msg("NAV report is older than the vault's max report age")]
+    NavReportTooOld,
+    #[msg("NAV report was signed over other total assets")]
+    NavReportOutdated,
+    #[msg("Invalid NAV report max age")]
+    InvalidNavMaxAge,
+    #[msg("Lockups require soulbound shares, transferred shares would escape them")]
+    LockupRequiresSoulboundShares,
+    #[msg("Invalid shares symbol, must be 1 to 10 bytes")]
+    InvalidSharesSymbol,
 }
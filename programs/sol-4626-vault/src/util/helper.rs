@@ -25,6 +25,126 @@ pub fn convert_to_shares(deposit_amount: u64, total_assets: u64, total_shares: u
         .map_err(|_| Errors::MathOverflow)?)
 }
 
+/// Converts an amount of base assets into the shares that must be burned to withdraw it.
+/// Same assumptions as `convert_to_shares`, but rounded up so the vault never
+/// releases assets for fewer shares than they are worth.
+pub fn convert_to_shares_rounded_up(
+    assets: u64,
+    total_assets: u64,
+    total_shares: u64,
+) -> Result<u64> {
+    if total_shares == 0 {
+        return Ok(assets);
+    }
+
+    if total_assets == 0 {
+        return Err(Errors::DivideByZero.into());
+    }
+
+    let numerator = (assets as u128)
+        .checked_mul(total_shares as u128)
+        .ok_or(Errors::MathOverflow)?;
+
+    Ok(numerator
+        .checked_add(total_assets as u128 - 1)
+        .ok_or(Errors::MathOverflow)?
+        .checked_div(total_assets as u128)
+        .ok_or(Errors::MathOverflow)?
+        .try_into()
+        .map_err(|_| Errors::MathOverflow)?)
+}
+
+/// Converts an amount of vault shares into base assets.
+///  - empty vault → 1:1
+///  - rounded down
+pub fn convert_to_assets(shares: u64, total_assets: u64, total_shares: u64) -> Result<u64> {
+    if total_shares == 0 {
+        return Ok(shares);
+    }
+
+    Ok((shares as u128)
+        .checked_mul(total_assets as u128)
+        .ok_or(Errors::MathOverflow)?
+        .checked_div(total_shares as u128)
+        .ok_or(Errors::MathOverflow)?
+        .try_into()
+        .map_err(|_| Errors::MathOverflow)?)
+}
+
+/// Converts an amount of vault shares into the base assets that must be deposited to mint it.
+/// Same assumptions as `convert_to_assets`, but rounded up so minting never costs
+/// less than the shares are worth.
+pub fn convert_to_assets_rounded_up(
+    shares: u64,
+    total_assets: u64,
+    total_shares: u64,
+) -> Result<u64> {
+    if total_shares == 0 {
+        return Ok(shares);
+    }
+
+    let numerator = (shares as u128)
+        .checked_mul(total_assets as u128)
+        .ok_or(Errors::MathOverflow)?;
+
+    Ok(numerator
+        .checked_add(total_shares as u128 - 1)
+        .ok_or(Errors::MathOverflow)?
+        .checked_div(total_shares as u128)
+        .ok_or(Errors::MathOverflow)?
+        .try_into()
+        .map_err(|_| Errors::MathOverflow)?)
+}
+
+/// Basis points denominator (100% = 10_000 bps).
+pub const BPS_DENOMINATOR: u64 = 10_000;
+
+/// Fixed-point scale of prices expressed in base assets per share.
+pub const PRICE_SCALE: u64 = 1_000_000_000;
+
+/// Penalty withheld from a gross exit amount, rounded up in favour of the vault.
+pub fn exit_penalty(gross_assets: u64, penalty_bps: u64) -> Result<u64> {
+    Ok((gross_assets as u128)
+        .checked_mul(penalty_bps as u128)
+        .ok_or(Errors::MathOverflow)?
+        .checked_add(BPS_DENOMINATOR as u128 - 1)
+        .ok_or(Errors::MathOverflow)?
+        .checked_div(BPS_DENOMINATOR as u128)
+        .ok_or(Errors::MathOverflow)?
+        .try_into()
+        .map_err(|_| Errors::MathOverflow)?)
+}
+
+/// Gross exit amount whose net, after the penalty, covers `net_assets`. Rounded up.
+/// - penalty_bps: must be below `BPS_DENOMINATOR`
+pub fn gross_up_for_penalty(net_assets: u64, penalty_bps: u64) -> Result<u64> {
+    let kept_bps = BPS_DENOMINATOR
+        .checked_sub(penalty_bps)
+        .filter(|kept| *kept > 0)
+        .ok_or(Errors::MathOverflow)? as u128;
+
+    Ok((net_assets as u128)
+        .checked_mul(BPS_DENOMINATOR as u128)
+        .ok_or(Errors::MathOverflow)?
+        .checked_add(kept_bps - 1)
+        .ok_or(Errors::MathOverflow)?
+        .checked_div(kept_bps)
+        .ok_or(Errors::MathOverflow)?
+        .try_into()
+        .map_err(|_| Errors::MathOverflow)?)
+}
+
+/// Share price in base assets per share scaled by `PRICE_SCALE`, rounded down and
+/// saturating at `u64::MAX`. 1:1 while no shares exist.
+pub fn share_price(total_assets: u64, total_shares: u64) -> u64 {
+    if total_shares == 0 {
+        return PRICE_SCALE;
+    }
+
+    let price = total_assets as u128 * PRICE_SCALE as u128 / total_shares as u128;
+    price.min(u64::MAX as u128) as u64
+}
+
 #[cfg(test)]
 mod test_convert_to_shares {
     use super::*;
@@ -88,4 +208,102 @@ mod test_convert_to_shares {
         let res = convert_to_shares(deposit, total_assets, total_shares);
         assert!(res.is_err());
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod test_convert_to_assets {
+    use super::*;
+
+    #[test]
+    fn empty_vault_redeems_one_to_one() {
+        let assets = convert_to_assets(1_000_000, 0, 0).unwrap();
+        assert_eq!(assets, 1_000_000);
+    }
+
+    #[test]
+    fn vault_with_yield_redeems_more_assets() {
+        let assets = convert_to_assets(500_000, 4_000_000, 2_000_000).unwrap();
+        assert_eq!(assets, 1_000_000);
+    }
+
+    #[test]
+    fn redeem_rounds_down() {
+        let assets = convert_to_assets(3, 1, 10).unwrap();
+        assert_eq!(assets, 0);
+    }
+
+    #[test]
+    fn withdraw_shares_round_up() {
+        let shares = convert_to_shares_rounded_up(1, 3, 10).unwrap();
+        assert_eq!(shares, 4);
+
+        let exact = convert_to_shares_rounded_up(1_000_000, 4_000_000, 2_000_000).unwrap();
+        assert_eq!(exact, 500_000);
+    }
+
+    #[test]
+    fn withdraw_shares_error_when_total_assets_is_zero_but_shares_exist() {
+        let res = convert_to_shares_rounded_up(100, 0, 1_000);
+        assert!(res.is_err());
+    }
+
+    #[test]
+    fn mint_assets_round_up() {
+        let assets = convert_to_assets_rounded_up(4, 3, 10).unwrap();
+        assert_eq!(assets, 2);
+
+        let exact = convert_to_assets_rounded_up(500_000, 4_000_000, 2_000_000).unwrap();
+        assert_eq!(exact, 1_000_000);
+    }
+}
+
+#[cfg(test)]
+mod test_share_price {
+    use super::*;
+
+    #[test]
+    fn empty_vault_prices_one_to_one() {
+        assert_eq!(share_price(0, 0), PRICE_SCALE);
+        assert_eq!(share_price(1_000, 0), PRICE_SCALE);
+    }
+
+    #[test]
+    fn price_follows_assets_per_share() {
+        assert_eq!(share_price(1_500_000, 1_000_000), 1_500_000_000);
+        assert_eq!(share_price(1, 3), 333_333_333);
+        assert_eq!(share_price(0, 1_000), 0);
+        assert_eq!(share_price(u64::MAX, 1), u64::MAX);
+    }
+}
+
+#[cfg(test)]
+mod test_exit_penalty {
+    use super::*;
+
+    #[test]
+    fn no_penalty_is_identity() {
+        assert_eq!(exit_penalty(1_000_000, 0).unwrap(), 0);
+        assert_eq!(gross_up_for_penalty(1_000_000, 0).unwrap(), 1_000_000);
+    }
+
+    #[test]
+    fn penalty_rounds_up() {
+        assert_eq!(exit_penalty(1_000_000, 250).unwrap(), 25_000);
+        assert_eq!(exit_penalty(1, 1).unwrap(), 1);
+    }
+
+    #[test]
+    fn gross_up_covers_net_after_penalty() {
+        let gross = gross_up_for_penalty(975_000, 250).unwrap();
+        assert_eq!(gross, 1_000_000);
+        assert!(gross - exit_penalty(gross, 250).unwrap() >= 975_000);
+
+        let gross = gross_up_for_penalty(999_999, 333).unwrap();
+        assert!(gross - exit_penalty(gross, 333).unwrap() >= 999_999);
+    }
+
+    #[test]
+    fn full_penalty_cannot_be_grossed_up() {
+        assert!(gross_up_for_penalty(1, BPS_DENOMINATOR).is_err());
+    }
+}
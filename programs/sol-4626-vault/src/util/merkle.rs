@@ -0,0 +1,66 @@
+use anchor_lang::prelude::Pubkey;
+use solana_sha256_hasher::hashv;
+
+/// Leaf of the depositor allowlist tree: sha256(wallet).
+pub fn allowlist_leaf(wallet: &Pubkey) -> [u8; 32] {
+    hashv(&[wallet.as_ref()]).to_bytes()
+}
+
+/// Verifies a sha256 merkle proof where each pair is hashed in sorted order,
+/// so proofs don't need to carry left/right positions.
+pub fn verify_proof(proof: &[[u8; 32]], root: [u8; 32], leaf: [u8; 32]) -> bool {
+    let computed = proof
+        .iter()
+        .fold(leaf, |node, sibling| hash_pair(&node, sibling));
+    computed == root
+}
+
+pub fn hash_pair(a: &[u8; 32], b: &[u8; 32]) -> [u8; 32] {
+    if a <= b {
+        hashv(&[a, b]).to_bytes()
+    } else {
+        hashv(&[b, a]).to_bytes()
+    }
+}
+
+#[cfg(test)]
+mod test_merkle {
+    use super::*;
+
+    fn leaves(n: u8) -> Vec<[u8; 32]> {
+        (0..n)
+            .map(|i| allowlist_leaf(&Pubkey::new_from_array([i + 1; 32])))
+            .collect()
+    }
+
+    #[test]
+    fn single_leaf_tree_has_empty_proof() {
+        let leaf = leaves(1)[0];
+        assert!(verify_proof(&[], leaf, leaf));
+    }
+
+    #[test]
+    fn four_leaf_tree_proofs() {
+        let l = leaves(4);
+        let n01 = hash_pair(&l[0], &l[1]);
+        let n23 = hash_pair(&l[2], &l[3]);
+        let root = hash_pair(&n01, &n23);
+
+        assert!(verify_proof(&[l[1], n23], root, l[0]));
+        assert!(verify_proof(&[l[0], n23], root, l[1]));
+        assert!(verify_proof(&[l[3], n01], root, l[2]));
+        assert!(verify_proof(&[l[2], n01], root, l[3]));
+    }
+
+    #[test]
+    fn rejects_wallet_outside_the_set() {
+        let l = leaves(4);
+        let n01 = hash_pair(&l[0], &l[1]);
+        let n23 = hash_pair(&l[2], &l[3]);
+        let root = hash_pair(&n01, &n23);
+
+        let outsider = allowlist_leaf(&Pubkey::new_from_array([42; 32]));
+        assert!(!verify_proof(&[l[1], n23], root, outsider));
+        assert!(!verify_proof(&[n23], root, l[0]));
+    }
+}
@@ -0,0 +1,127 @@
+use crate::util::Errors;
+use anchor_lang::prelude::*;
+use anchor_spl::token_2022::spl_token_2022::extension::default_account_state::DefaultAccountState;
+use anchor_spl::token_2022::spl_token_2022::extension::mint_close_authority::MintCloseAuthority;
+use anchor_spl::token_2022::spl_token_2022::extension::pausable::PausableConfig;
+use anchor_spl::token_2022::spl_token_2022::extension::permanent_delegate::PermanentDelegate;
+use anchor_spl::token_2022::spl_token_2022::extension::transfer_hook::TransferHook;
+use anchor_spl::token_2022::spl_token_2022::extension::{
+    BaseStateWithExtensions, StateWithExtensions,
+};
+use anchor_spl::token_2022::spl_token_2022::state::{AccountState, Mint as MintState};
+
+/// Base asset mint features that can seize or freeze the vault's assets, as bit flags.
+/// Each is rejected at initialize unless the admin opts into it.
+pub struct MintExtensions;
+
+impl MintExtensions {
+    /// Freeze authority set (classic or Token-2022)
+    pub const FREEZE_AUTHORITY: u16 = 1 << 0;
+    /// PermanentDelegate with a delegate set
+    pub const PERMANENT_DELEGATE: u16 = 1 << 1;
+    /// TransferHook with a hook program set
+    pub const TRANSFER_HOOK: u16 = 1 << 2;
+    /// DefaultAccountState creating frozen accounts
+    pub const DEFAULT_FROZEN: u16 = 1 << 3;
+    /// Pausable, transfers can be halted by the pause authority
+    pub const PAUSABLE: u16 = 1 << 4;
+    /// MintCloseAuthority with a close authority set
+    pub const MINT_CLOSE_AUTHORITY: u16 = 1 << 5;
+
+    pub const ALL: u16 = Self::FREEZE_AUTHORITY
+        | Self::PERMANENT_DELEGATE
+        | Self::TRANSFER_HOOK
+        | Self::DEFAULT_FROZEN
+        | Self::PAUSABLE
+        | Self::MINT_CLOSE_AUTHORITY;
+
+    /// Flags of the dangerous features present on `mint`.
+    pub fn of(mint: &AccountInfo) -> Result<u16> {
+        let data = mint.try_borrow_data()?;
+        let mut flags = 0;
+
+        // Classic mints share the base layout and have no extensions
+        let mint = StateWithExtensions::<MintState>::unpack(&data)?;
+        if mint.base.freeze_authority.is_some() {
+            flags |= Self::FREEZE_AUTHORITY;
+        }
+        if let Ok(ext) = mint.get_extension::<PermanentDelegate>() {
+            if Option::<Pubkey>::from(ext.delegate).is_some() {
+                flags |= Self::PERMANENT_DELEGATE;
+            }
+        }
+        if let Ok(ext) = mint.get_extension::<TransferHook>() {
+            if Option::<Pubkey>::from(ext.program_id).is_some() {
+                flags |= Self::TRANSFER_HOOK;
+            }
+        }
+        if let Ok(ext) = mint.get_extension::<DefaultAccountState>() {
+            if ext.state == AccountState::Frozen as u8 {
+                flags |= Self::DEFAULT_FROZEN;
+            }
+        }
+        if mint.get_extension::<PausableConfig>().is_ok() {
+            flags |= Self::PAUSABLE;
+        }
+        if let Ok(ext) = mint.get_extension::<MintCloseAuthority>() {
+            if Option::<Pubkey>::from(ext.close_authority).is_some() {
+                flags |= Self::MINT_CLOSE_AUTHORITY;
+            }
+        }
+
+        Ok(flags)
+    }
+
+    /// Rejects the first feature in `present` the admin did not opt into with `allowed`.
+    pub fn check(present: u16, allowed: u16) -> Result<()> {
+        require!(allowed & !Self::ALL == 0, Errors::InvalidMintExtensions);
+
+        let denied = present & !allowed;
+        require!(
+            denied & Self::FREEZE_AUTHORITY == 0,
+            Errors::FreezeAuthorityNotAllowed
+        );
+        require!(
+            denied & Self::PERMANENT_DELEGATE == 0,
+            Errors::PermanentDelegateNotAllowed
+        );
+        require!(
+            denied & Self::TRANSFER_HOOK == 0,
+            Errors::TransferHookNotAllowed
+        );
+        require!(
+            denied & Self::DEFAULT_FROZEN == 0,
+            Errors::DefaultFrozenNotAllowed
+        );
+        require!(denied & Self::PAUSABLE == 0, Errors::PausableNotAllowed);
+        require!(
+            denied & Self::MINT_CLOSE_AUTHORITY == 0,
+            Errors::MintCloseAuthorityNotAllowed
+        );
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test_mint_extensions {
+    use super::*;
+
+    #[test]
+    fn check_rejects_features_not_opted_into() {
+        assert!(MintExtensions::check(0, 0).is_ok());
+        assert!(MintExtensions::check(MintExtensions::TRANSFER_HOOK, 0).is_err());
+        assert!(MintExtensions::check(
+            MintExtensions::TRANSFER_HOOK,
+            MintExtensions::TRANSFER_HOOK
+        )
+        .is_ok());
+        assert!(MintExtensions::check(
+            MintExtensions::TRANSFER_HOOK | MintExtensions::PAUSABLE,
+            MintExtensions::TRANSFER_HOOK
+        )
+        .is_err());
+        //opting into a feature the mint doesn't have is fine, unknown flags are not
+        assert!(MintExtensions::check(0, MintExtensions::ALL).is_ok());
+        assert!(MintExtensions::check(0, 1 << 15).is_err());
+    }
+}
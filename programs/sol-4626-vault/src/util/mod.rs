@@ -5,4 +5,34 @@ pub mod errors;
 pub use errors::*;
 
 pub mod bool_u8;
-pub use bool_u8::*;
\ No newline at end of file
+pub use bool_u8::*;
+
+pub mod merkle;
+pub use merkle::*;
+
+pub mod ed25519;
+pub use ed25519::*;
+
+pub mod voucher;
+pub use voucher::*;
+
+pub mod transfer_fee;
+pub use transfer_fee::*;
+
+pub mod mint_extensions;
+pub use mint_extensions::*;
+
+pub mod shares_transfer;
+pub use shares_transfer::*;
+
+pub mod native_sol;
+pub use native_sol::*;
+
+pub mod oracle;
+pub use oracle::*;
+
+pub mod nav_report;
+pub use nav_report::*;
+
+pub mod token_metadata;
+pub use token_metadata::*;
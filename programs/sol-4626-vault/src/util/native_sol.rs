@@ -0,0 +1,50 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+use anchor_spl::token::spl_token;
+use anchor_spl::token_2022::spl_token_2022;
+use anchor_spl::token_interface::{close_account, sync_native, CloseAccount, SyncNative};
+
+/// Whether `mint` is the native (wSOL) mint of the classic or Token-2022 program.
+pub fn is_native_mint(mint: &Pubkey) -> bool {
+    *mint == spl_token::native_mint::ID || *mint == spl_token_2022::native_mint::ID
+}
+
+/// Moves `lamports` from `owner` into its native token account and syncs the token balance.
+pub fn wrap_sol<'info>(
+    system_program: &AccountInfo<'info>,
+    token_program: &AccountInfo<'info>,
+    owner: &AccountInfo<'info>,
+    wsol_account: &AccountInfo<'info>,
+    lamports: u64,
+) -> Result<()> {
+    let transfer_accounts = Transfer {
+        from: owner.clone(),
+        to: wsol_account.clone(),
+    };
+    transfer(
+        CpiContext::new(system_program.clone(), transfer_accounts),
+        lamports,
+    )?;
+    sync_native(CpiContext::new(
+        token_program.clone(),
+        SyncNative {
+            account: wsol_account.clone(),
+        },
+    ))
+}
+
+/// Closes the native token account of `owner`, unwrapping its balance and rent to `owner`.
+pub fn unwrap_sol<'info>(
+    token_program: &AccountInfo<'info>,
+    owner: &AccountInfo<'info>,
+    wsol_account: &AccountInfo<'info>,
+) -> Result<()> {
+    close_account(CpiContext::new(
+        token_program.clone(),
+        CloseAccount {
+            account: wsol_account.clone(),
+            destination: owner.clone(),
+            authority: owner.clone(),
+        },
+    ))
+}
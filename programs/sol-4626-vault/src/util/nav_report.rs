@@ -0,0 +1,138 @@
+use crate::state::Vault;
+use crate::util::{Errors, BPS_DENOMINATOR};
+use anchor_lang::prelude::*;
+
+/// Net asset value report issued off chain by the vault's valuation signer.
+/// Signed message layout (borsh, 64 bytes): vault, nav, previous_nav, timestamp, sequence.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct NavReport {
+    pub vault: Pubkey,     // Vault the report values
+    pub nav: u64,          // Total base assets of the vault
+    pub previous_nav: u64, // Total base assets the report replaces, any flow since voids it
+    pub timestamp: i64,    // Unix timestamp the valuation was taken at
+    pub sequence: u64,     // Strictly increasing per vault, stops replay
+}
+
+impl NavReport {
+    /// Checks the report against the state of `vlt`, the vault at address `vault`: it must
+    /// follow the last applied report, be at most `nav_max_age` old, replace the current
+    /// total assets and stay within `max_nav_change_bps` of them (0 = unlimited).
+    pub fn verify(&self, vault: &Pubkey, vlt: &Vault, now: i64) -> Result<()> {
+        require_keys_eq!(self.vault, *vault, Errors::NavReportMismatch);
+        require!(self.sequence > vlt.nav_sequence, Errors::NavSequenceReused);
+        require!(
+            self.timestamp >= vlt.nav_updated_at && self.timestamp <= now,
+            Errors::InvalidNavTimestamp
+        );
+        require!(
+            now - self.timestamp <= vlt.nav_max_age,
+            Errors::NavReportTooOld
+        );
+        let total_assets = vlt.total_base_assets;
+        require!(self.previous_nav == total_assets, Errors::NavReportOutdated);
+
+        let max_change_bps = vlt.max_nav_change_bps;
+        if max_change_bps > 0 {
+            let change = self.nav.abs_diff(total_assets) as u128;
+            require!(
+                change * (BPS_DENOMINATOR as u128)
+                    <= (total_assets as u128) * (max_change_bps as u128),
+                Errors::NavChangeTooLarge
+            );
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test_nav_report {
+    use super::*;
+
+    fn report() -> NavReport {
+        NavReport {
+            vault: Pubkey::new_from_array([1; 32]),
+            nav: 1_050,
+            previous_nav: 1_000,
+            timestamp: 100,
+            sequence: 5,
+        }
+    }
+
+    /// Vault at 1_000 total assets, last report 4 at 90, 5% max change, 60s max age.
+    fn vault() -> Vault {
+        let mut vlt = Vault::empty();
+        vlt.total_base_assets = 1_000;
+        vlt.nav_sequence = 4;
+        vlt.nav_updated_at = 90;
+        vlt.max_nav_change_bps = 500;
+        vlt.nav_max_age = 60;
+        vlt
+    }
+
+    fn rejects(r: &NavReport, vlt: &Vault, now: i64, error: Errors) {
+        assert_eq!(r.verify(&r.vault, vlt, now), Err(error.into()));
+    }
+
+    #[test]
+    fn message_layout_is_64_bytes() {
+        let message = report().try_to_vec().unwrap();
+        assert_eq!(message.len(), 64);
+        assert_eq!(NavReport::try_from_slice(&message).unwrap(), report());
+    }
+
+    #[test]
+    fn accepts_next_report_within_limits() {
+        let r = report();
+        let mut vlt = vault();
+        assert!(r.verify(&r.vault, &vlt, 100).is_ok());
+        //up to the max age
+        assert!(r.verify(&r.vault, &vlt, 160).is_ok());
+        vlt.nav_updated_at = 100;
+        assert!(r.verify(&r.vault, &vlt, 100).is_ok());
+        //no change limit
+        vlt.max_nav_change_bps = 0;
+        let big = NavReport { nav: 1, ..r };
+        assert!(big.verify(&big.vault, &vlt, 100).is_ok());
+    }
+
+    #[test]
+    fn rejects_invalid_reports() {
+        let r = report();
+        let vlt = vault();
+
+        let other = Pubkey::new_from_array([3; 32]);
+        assert!(r.verify(&other, &vlt, 100).is_err());
+        //replayed sequence
+        rejects(
+            &r,
+            &Vault {
+                nav_sequence: 5,
+                ..vlt
+            },
+            100,
+            Errors::NavSequenceReused,
+        );
+        //older than the last report, or from the future
+        let last_at = Vault {
+            nav_updated_at: 101,
+            ..vlt
+        };
+        rejects(&r, &last_at, 200, Errors::InvalidNavTimestamp);
+        rejects(&r, &vlt, 99, Errors::InvalidNavTimestamp);
+        //relayed too late
+        rejects(&r, &vlt, 161, Errors::NavReportTooOld);
+        //flows since signing
+        let moved = Vault {
+            total_base_assets: 1_001,
+            ..vlt
+        };
+        rejects(&r, &moved, 100, Errors::NavReportOutdated);
+        //5% change above a 4.99% limit
+        let tight = Vault {
+            max_nav_change_bps: 499,
+            ..vlt
+        };
+        rejects(&r, &tight, 100, Errors::NavChangeTooLarge);
+    }
+}
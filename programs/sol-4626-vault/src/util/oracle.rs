@@ -0,0 +1,278 @@
+use crate::constant::PYTH_ORACLE_PROGRAM_ID;
+use crate::state::PriceFeed;
+use crate::util::{Errors, BPS_DENOMINATOR};
+use anchor_lang::prelude::*;
+
+/// Price of one whole token, `price * 10^expo` units of account, give or take `conf`
+/// (same exponent), as published at `publish_time`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct OraclePrice {
+    pub price: i64,
+    pub conf: u64,
+    pub expo: i32,
+    pub publish_time: i64,
+}
+
+/// Price account layout the vault can read a price from.
+pub trait PriceOracle: Sized {
+    /// Parses `account`, checking it is owned by the oracle program.
+    fn try_load(account: &AccountInfo) -> Result<Self>;
+
+    /// Latest published price.
+    fn oracle_price(&self) -> OraclePrice;
+
+    /// Mint the account prices, when the layout records it.
+    fn priced_mint(&self) -> Option<Pubkey> {
+        None
+    }
+}
+
+impl PriceOracle for PriceFeed {
+    fn try_load(account: &AccountInfo) -> Result<Self> {
+        require_keys_eq!(*account.owner, crate::ID, Errors::InvalidOracleAccount);
+        PriceFeed::try_deserialize(&mut &account.try_borrow_data()?[..])
+    }
+
+    fn oracle_price(&self) -> OraclePrice {
+        OraclePrice {
+            price: self.price,
+            conf: self.conf,
+            expo: self.expo,
+            publish_time: self.publish_time,
+        }
+    }
+
+    fn priced_mint(&self) -> Option<Pubkey> {
+        Some(self.mint)
+    }
+}
+
+/// Pyth-style (v2) price account: the aggregate price of a single product.
+pub struct PythPrice {
+    price: OraclePrice,
+}
+
+impl PythPrice {
+    /// Account magic number
+    pub const MAGIC: u32 = 0xa1b2_c3d4;
+    /// Supported layout version
+    pub const VERSION: u32 = 2;
+    /// Account type of price accounts
+    pub const ACCOUNT_TYPE_PRICE: u32 = 3;
+    /// Aggregate status of a price currently trading
+    pub const STATUS_TRADING: u32 = 1;
+    /// Bytes read, up to the end of the aggregate price
+    pub const LEN: usize = 240;
+
+    /// Parses the fields the vault reads out of a Pyth-style price account.
+    pub fn parse(data: &[u8]) -> Result<Self> {
+        require!(data.len() >= Self::LEN, Errors::InvalidOracleAccount);
+        let u32_at = |at: usize| u32::from_le_bytes(data[at..at + 4].try_into().unwrap());
+        let u64_at = |at: usize| u64::from_le_bytes(data[at..at + 8].try_into().unwrap());
+
+        require!(
+            u32_at(0) == Self::MAGIC
+                && u32_at(4) == Self::VERSION
+                && u32_at(8) == Self::ACCOUNT_TYPE_PRICE,
+            Errors::InvalidOracleAccount
+        );
+        // Halted or auction prices are not tradable
+        require!(u32_at(224) == Self::STATUS_TRADING, Errors::InvalidPrice);
+
+        Ok(Self {
+            price: OraclePrice {
+                price: u64_at(208) as i64,
+                conf: u64_at(216),
+                expo: u32_at(20) as i32,
+                publish_time: u64_at(96) as i64,
+            },
+        })
+    }
+}
+
+impl PriceOracle for PythPrice {
+    fn try_load(account: &AccountInfo) -> Result<Self> {
+        require_keys_eq!(
+            *account.owner,
+            PYTH_ORACLE_PROGRAM_ID,
+            Errors::InvalidOracleAccount
+        );
+        Self::parse(&account.try_borrow_data()?)
+    }
+
+    fn oracle_price(&self) -> OraclePrice {
+        self.price
+    }
+}
+
+/// Loads `account` with the adapter of its owner: a `PriceFeed` of this program or a
+/// Pyth-style price account. Also returns the priced mint, when the layout records it.
+pub fn load_oracle_price(account: &AccountInfo) -> Result<(OraclePrice, Option<Pubkey>)> {
+    fn load<T: PriceOracle>(account: &AccountInfo) -> Result<(OraclePrice, Option<Pubkey>)> {
+        let oracle = T::try_load(account)?;
+        Ok((oracle.oracle_price(), oracle.priced_mint()))
+    }
+
+    if *account.owner == crate::ID {
+        load::<PriceFeed>(account)
+    } else {
+        load::<PythPrice>(account)
+    }
+}
+
+impl OraclePrice {
+    /// Seconds a publish time may run ahead of the cluster clock
+    pub const MAX_CLOCK_SKEW: i64 = 5;
+
+    /// Rejects prices older than `max_age` seconds at `now` or published ahead of it
+    /// (beyond `MAX_CLOCK_SKEW`), non-positive prices, and confidence intervals wider
+    /// than `max_conf_bps` of the price.
+    pub fn checked(self, now: i64, max_age: u32, max_conf_bps: u16) -> Result<Self> {
+        require!(self.price > 0, Errors::InvalidPrice);
+        require!(
+            now.saturating_sub(self.publish_time) <= max_age as i64
+                && self.publish_time <= now.saturating_add(Self::MAX_CLOCK_SKEW),
+            Errors::StalePrice
+        );
+        require!(
+            (self.conf as u128) * (BPS_DENOMINATOR as u128)
+                <= (self.price as u128) * (max_conf_bps as u128),
+            Errors::PriceConfidenceTooWide
+        );
+        Ok(self)
+    }
+
+    /// Value of `amount` base units of a mint with `decimals`, in units of account
+    /// with `value_decimals` (rounded down).
+    pub fn value_of(&self, amount: u64, decimals: u8, value_decimals: u8) -> Result<u64> {
+        let (price, scale) = self.scaled_price(decimals, value_decimals)?;
+        let value = (amount as u128)
+            .checked_mul(price)
+            .ok_or(Errors::MathOverflow)?;
+        let value = if scale >= 0 {
+            value.checked_mul(pow10(scale)?)
+        } else {
+            value.checked_div(pow10(scale)?)
+        };
+        value
+            .ok_or(Errors::MathOverflow)?
+            .try_into()
+            .map_err(|_| Errors::MathOverflow.into())
+    }
+
+    /// Base units of a mint with `decimals` worth `value` units of account with
+    /// `value_decimals` (rounded down).
+    pub fn amount_for(&self, value: u64, decimals: u8, value_decimals: u8) -> Result<u64> {
+        let (price, scale) = self.scaled_price(decimals, value_decimals)?;
+        let amount = if scale >= 0 {
+            price
+                .checked_mul(pow10(scale)?)
+                .and_then(|divisor| (value as u128).checked_div(divisor))
+        } else {
+            (value as u128)
+                .checked_mul(pow10(scale)?)
+                .and_then(|scaled| scaled.checked_div(price))
+        };
+        amount
+            .ok_or(Errors::MathOverflow)?
+            .try_into()
+            .map_err(|_| Errors::MathOverflow.into())
+    }
+
+    /// Positive price mantissa and the power of ten turning `price * amount` into value units.
+    fn scaled_price(&self, decimals: u8, value_decimals: u8) -> Result<(u128, i32)> {
+        require!(self.price > 0, Errors::InvalidPrice);
+        Ok((
+            self.price as u128,
+            self.expo + value_decimals as i32 - decimals as i32,
+        ))
+    }
+}
+
+/// `10^|exponent|`, rejecting exponents beyond u128.
+fn pow10(exponent: i32) -> Result<u128> {
+    Ok(10u128
+        .checked_pow(exponent.unsigned_abs())
+        .ok_or(Errors::MathOverflow)?)
+}
+
+#[cfg(test)]
+mod test_oracle {
+    use super::*;
+
+    fn price(price: i64, conf: u64, expo: i32, publish_time: i64) -> OraclePrice {
+        OraclePrice {
+            price,
+            conf,
+            expo,
+            publish_time,
+        }
+    }
+
+    fn pyth_data(status: u32) -> Vec<u8> {
+        let mut data = vec![0u8; PythPrice::LEN];
+        data[0..4].copy_from_slice(&PythPrice::MAGIC.to_le_bytes());
+        data[4..8].copy_from_slice(&PythPrice::VERSION.to_le_bytes());
+        data[8..12].copy_from_slice(&PythPrice::ACCOUNT_TYPE_PRICE.to_le_bytes());
+        data[20..24].copy_from_slice(&(-8i32).to_le_bytes());
+        data[96..104].copy_from_slice(&1_700_000_000i64.to_le_bytes());
+        data[208..216].copy_from_slice(&6_500_000_000i64.to_le_bytes());
+        data[216..224].copy_from_slice(&3_000_000u64.to_le_bytes());
+        data[224..228].copy_from_slice(&status.to_le_bytes());
+        data
+    }
+
+    #[test]
+    fn parses_pyth_layout() {
+        let parsed = PythPrice::parse(&pyth_data(PythPrice::STATUS_TRADING)).unwrap();
+        assert_eq!(
+            parsed.oracle_price(),
+            price(6_500_000_000, 3_000_000, -8, 1_700_000_000)
+        );
+
+        //halted aggregate, wrong magic, truncated account
+        assert!(PythPrice::parse(&pyth_data(0)).is_err());
+        let mut data = pyth_data(PythPrice::STATUS_TRADING);
+        data[0] = 0;
+        assert!(PythPrice::parse(&data).is_err());
+        assert!(PythPrice::parse(&pyth_data(PythPrice::STATUS_TRADING)[..200]).is_err());
+    }
+
+    #[test]
+    fn checks_staleness_and_confidence() {
+        //65.00 +- 0.03 published at 1_000
+        let p = price(6_500, 3, -2, 1_000);
+        assert!(p.checked(1_060, 60, 10).is_ok());
+        assert!(p.checked(1_061, 60, 10).is_err());
+        //0.03 / 65.00 is ~4.6 bps
+        assert!(p.checked(1_000, 60, 4).is_err());
+        assert!(price(0, 0, 0, 1_000).checked(1_000, 60, 10).is_err());
+    }
+
+    #[test]
+    fn rejects_prices_published_ahead_of_the_clock() {
+        //published at 1_000, up to 5s ahead of the clock
+        let p = price(6_500, 3, -2, 1_000);
+        assert!(p.checked(995, 60, 10).is_ok());
+        assert!(p.checked(994, 60, 10).is_err());
+        assert!(p.checked(0, 60, 10).is_err());
+    }
+
+    #[test]
+    fn value_normalises_decimals() {
+        //1.5 units of account per token, 9 decimals token, 6 decimals value
+        let p = price(150, 0, -2, 0);
+        assert_eq!(p.value_of(2_000_000_000, 9, 6).unwrap(), 3_000_000);
+        assert_eq!(p.amount_for(3_000_000, 9, 6).unwrap(), 2_000_000_000);
+
+        //large price exponent scales up
+        let p = price(2, 0, 3, 0);
+        assert_eq!(p.value_of(1, 0, 6).unwrap(), 2_000_000_000);
+        assert_eq!(p.amount_for(2_000_000_000, 0, 6).unwrap(), 1);
+
+        //rounded down, absurd exponents overflow instead of panicking
+        assert_eq!(price(3, 0, 0, 0).amount_for(10, 6, 6).unwrap(), 3);
+        assert!(price(1, 0, 60, 0).value_of(1, 0, 6).is_err());
+        assert!(price(-1, 0, 0, 0).amount_for(1, 6, 6).is_err());
+    }
+}
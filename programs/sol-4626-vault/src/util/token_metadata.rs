@@ -0,0 +1,221 @@
+use crate::constant::TOKEN_METADATA_PROGRAM_ID;
+use crate::util::Errors;
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+use anchor_lang::solana_program::program::invoke_signed;
+
+/// Name, symbol and uri limits of the shares token metadata, those of Metaplex Token Metadata.
+/// Token-2022 shares mints keep the same limits in their token-metadata extension.
+pub struct SharesMetadata;
+
+impl SharesMetadata {
+    pub const MAX_NAME_LEN: usize = 32;
+    pub const MAX_SYMBOL_LEN: usize = 10;
+    pub const MAX_URI_LEN: usize = 200;
+
+    /// Checks the metadata fits the account, same limits for both token programs.
+    pub fn validate(name: &str, symbol: &str, uri: &str) -> Result<()> {
+        require!(
+            !name.is_empty() && name.len() <= Self::MAX_NAME_LEN,
+            Errors::InvalidSharesMetadata
+        );
+        require!(
+            !symbol.is_empty() && symbol.len() <= Self::MAX_SYMBOL_LEN,
+            Errors::InvalidSharesSymbol
+        );
+        Self::validate_uri(uri)
+    }
+
+    pub fn validate_uri(uri: &str) -> Result<()> {
+        require!(
+            uri.len() <= Self::MAX_URI_LEN,
+            Errors::InvalidSharesMetadata
+        );
+        Ok(())
+    }
+
+    /// Reads the name, symbol and uri of a Metaplex metadata account, without the
+    /// nul padding Metaplex stores them with.
+    pub fn read(data: &[u8]) -> Result<(String, String, String)> {
+        // key (u8), update authority and mint (Pubkey) precede the strings
+        let mut fields = data
+            .get(1 + 32 + 32..)
+            .ok_or(Errors::InvalidSharesMetadata)?;
+        let mut next = || -> Result<String> {
+            let field = String::deserialize(&mut fields)
+                .map_err(|_| error!(Errors::InvalidSharesMetadata))?;
+            Ok(field.trim_end_matches('\0').to_string())
+        };
+        Ok((next()?, next()?, next()?))
+    }
+}
+
+/// `DataV2` of the Metaplex instructions. Creators, collection and uses are never set,
+/// `None` serializes the same whatever their type.
+#[derive(AnchorSerialize)]
+struct DataV2 {
+    name: String,
+    symbol: String,
+    uri: String,
+    seller_fee_basis_points: u16,
+    creators: Option<()>,
+    collection: Option<()>,
+    uses: Option<()>,
+}
+
+impl DataV2 {
+    fn new(name: &str, symbol: &str, uri: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            symbol: symbol.to_string(),
+            uri: uri.to_string(),
+            seller_fee_basis_points: 0,
+            creators: None,
+            collection: None,
+            uses: None,
+        }
+    }
+}
+
+/// Metaplex `CreateMetadataAccountV3` instruction data
+#[derive(AnchorSerialize)]
+struct CreateMetadataAccountV3 {
+    discriminator: u8,
+    data: DataV2,
+    is_mutable: bool,
+    collection_details: Option<()>,
+}
+
+/// Metaplex `UpdateMetadataAccountV2` instruction data
+#[derive(AnchorSerialize)]
+struct UpdateMetadataAccountV2 {
+    discriminator: u8,
+    data: Option<DataV2>,
+    new_update_authority: Option<Pubkey>,
+    primary_sale_happened: Option<bool>,
+    is_mutable: Option<bool>,
+}
+
+/// Creates the mutable Metaplex metadata account of `mint`, with `authority` as both the
+/// mint authority and the update authority, signed with `signer_seeds`.
+#[allow(clippy::too_many_arguments)]
+pub fn create_metadata_account<'info>(
+    token_metadata_program: &AccountInfo<'info>,
+    metadata: &AccountInfo<'info>,
+    mint: &AccountInfo<'info>,
+    authority: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    name: &str,
+    symbol: &str,
+    uri: &str,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    let args = CreateMetadataAccountV3 {
+        discriminator: 33,
+        data: DataV2::new(name, symbol, uri),
+        is_mutable: true,
+        collection_details: None,
+    };
+    let ix = Instruction {
+        program_id: TOKEN_METADATA_PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new(metadata.key(), false),
+            AccountMeta::new_readonly(mint.key(), false),
+            AccountMeta::new_readonly(authority.key(), true),
+            AccountMeta::new(payer.key(), true),
+            AccountMeta::new_readonly(authority.key(), true),
+            AccountMeta::new_readonly(system_program.key(), false),
+        ],
+        data: args.try_to_vec()?,
+    };
+    invoke_signed(
+        &ix,
+        &[
+            metadata.clone(),
+            mint.clone(),
+            authority.clone(),
+            payer.clone(),
+            system_program.clone(),
+            token_metadata_program.clone(),
+        ],
+        signer_seeds,
+    )
+    .map_err(Into::into)
+}
+
+/// Points the Metaplex metadata account to `uri`, keeping its name and symbol.
+pub fn update_metadata_uri<'info>(
+    token_metadata_program: &AccountInfo<'info>,
+    metadata: &AccountInfo<'info>,
+    update_authority: &AccountInfo<'info>,
+    uri: &str,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    let (name, symbol, _) = SharesMetadata::read(&metadata.try_borrow_data()?)?;
+    let args = UpdateMetadataAccountV2 {
+        discriminator: 15,
+        data: Some(DataV2::new(&name, &symbol, uri)),
+        new_update_authority: None,
+        primary_sale_happened: None,
+        is_mutable: None,
+    };
+    let ix = Instruction {
+        program_id: TOKEN_METADATA_PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new(metadata.key(), false),
+            AccountMeta::new_readonly(update_authority.key(), true),
+        ],
+        data: args.try_to_vec()?,
+    };
+    invoke_signed(
+        &ix,
+        &[
+            metadata.clone(),
+            update_authority.clone(),
+            token_metadata_program.clone(),
+        ],
+        signer_seeds,
+    )
+    .map_err(Into::into)
+}
+
+#[cfg(test)]
+mod test_token_metadata {
+    use super::*;
+
+    #[test]
+    fn validate_enforces_lengths() {
+        assert!(SharesMetadata::validate("Vault USDC", "vUSDC", "https://x.io/v.json").is_ok());
+        assert!(SharesMetadata::validate("", "vUSDC", "").is_err());
+        for symbol in ["", "VERYLONGTICKER"] {
+            assert_eq!(
+                SharesMetadata::validate("Vault USDC", symbol, "").unwrap_err(),
+                Errors::InvalidSharesSymbol.into()
+            );
+        }
+        assert!(SharesMetadata::validate(&"n".repeat(33), "vUSDC", "").is_err());
+        assert!(SharesMetadata::validate_uri(&"u".repeat(201)).is_err());
+    }
+
+    #[test]
+    fn read_strips_metaplex_padding() {
+        let mut data = vec![4u8];
+        data.extend_from_slice(&[1; 64]);
+        for (field, len) in [
+            ("Vault USDC", 32),
+            ("vUSDC", 10),
+            ("https://x.io/v.json", 200),
+        ] {
+            let padded = format!("{field:\0<len$}");
+            padded.serialize(&mut data).unwrap();
+        }
+        data.extend_from_slice(&0u16.to_le_bytes());
+
+        let (name, symbol, uri) = SharesMetadata::read(&data).unwrap();
+        assert_eq!(name, "Vault USDC");
+        assert_eq!(symbol, "vUSDC");
+        assert_eq!(uri, "https://x.io/v.json");
+        assert!(SharesMetadata::read(&data[..70]).is_err());
+    }
+}
@@ -0,0 +1,36 @@
+use crate::util::Errors;
+use anchor_lang::prelude::*;
+use anchor_spl::token_2022;
+use anchor_spl::token_2022::spl_token_2022::extension::transfer_fee::TransferFeeConfig;
+use anchor_spl::token_interface::get_mint_extension_data;
+
+/// Transfer fee config of a Token-2022 `mint`, `None` for mints without the extension.
+fn transfer_fee_config(mint: &AccountInfo) -> Option<TransferFeeConfig> {
+    if *mint.owner != token_2022::ID {
+        return None;
+    }
+    get_mint_extension_data::<TransferFeeConfig>(mint).ok()
+}
+
+/// Fee withheld by `mint` on a transfer of `amount` in the current epoch.
+pub fn transfer_fee(mint: &AccountInfo, amount: u64) -> Result<u64> {
+    let Some(config) = transfer_fee_config(mint) else {
+        return Ok(0);
+    };
+
+    Ok(config
+        .calculate_epoch_fee(Clock::get()?.epoch, amount)
+        .ok_or(Errors::MathOverflow)?)
+}
+
+/// Amount to transfer so that `net_amount` arrives after the transfer fee of `mint`.
+pub fn gross_up_for_transfer_fee(mint: &AccountInfo, net_amount: u64) -> Result<u64> {
+    let Some(config) = transfer_fee_config(mint) else {
+        return Ok(net_amount);
+    };
+
+    let fee = config
+        .calculate_inverse_epoch_fee(Clock::get()?.epoch, net_amount)
+        .ok_or(Errors::MathOverflow)?;
+    Ok(net_amount.checked_add(fee).ok_or(Errors::MathOverflow)?)
+}
@@ -0,0 +1,74 @@
+use crate::util::Errors;
+use anchor_lang::prelude::*;
+
+/// Deposit authorisation issued off chain by the vault's compliance signer.
+/// Signed message layout (borsh, 88 bytes): vault, depositor, max_amount, expiry, nonce.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct DepositVoucher {
+    pub vault: Pubkey,     // Vault the voucher is valid for
+    pub depositor: Pubkey, // Wallet allowed to deposit
+    pub max_amount: u64,   // Max base assets for this deposit
+    pub expiry: i64,       // Unix timestamp after which the voucher is rejected
+    pub nonce: u64,        // Strictly increasing per depositor, stops replay
+}
+
+impl DepositVoucher {
+    /// Checks the voucher against the deposit being processed.
+    /// - last_nonce: last voucher nonce consumed by the depositor
+    pub fn verify(
+        &self,
+        vault: &Pubkey,
+        depositor: &Pubkey,
+        amount: u64,
+        now: i64,
+        last_nonce: u64,
+    ) -> Result<()> {
+        require_keys_eq!(self.vault, *vault, Errors::VoucherMismatch);
+        require_keys_eq!(self.depositor, *depositor, Errors::VoucherMismatch);
+        require!(now <= self.expiry, Errors::VoucherExpired);
+        require!(amount <= self.max_amount, Errors::VoucherAmountExceeded);
+        require!(self.nonce > last_nonce, Errors::VoucherNonceReused);
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test_deposit_voucher {
+    use super::*;
+
+    fn voucher() -> DepositVoucher {
+        DepositVoucher {
+            vault: Pubkey::new_from_array([1; 32]),
+            depositor: Pubkey::new_from_array([2; 32]),
+            max_amount: 1_000,
+            expiry: 100,
+            nonce: 5,
+        }
+    }
+
+    #[test]
+    fn message_layout_is_88_bytes() {
+        let message = voucher().try_to_vec().unwrap();
+        assert_eq!(message.len(), 88);
+        assert_eq!(DepositVoucher::try_from_slice(&message).unwrap(), voucher());
+    }
+
+    #[test]
+    fn accepts_matching_deposit() {
+        let v = voucher();
+        assert!(v.verify(&v.vault, &v.depositor, 1_000, 100, 4).is_ok());
+    }
+
+    #[test]
+    fn rejects_invalid_deposits() {
+        let v = voucher();
+        let other = Pubkey::new_from_array([3; 32]);
+
+        assert!(v.verify(&other, &v.depositor, 1_000, 100, 4).is_err());
+        assert!(v.verify(&v.vault, &other, 1_000, 100, 4).is_err());
+        assert!(v.verify(&v.vault, &v.depositor, 1_000, 101, 4).is_err());
+        assert!(v.verify(&v.vault, &v.depositor, 1_001, 100, 4).is_err());
+        assert!(v.verify(&v.vault, &v.depositor, 1_000, 100, 5).is_err());
+    }
+}
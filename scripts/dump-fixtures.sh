@@ -0,0 +1,8 @@
+#!/usr/bin/env bash
+# Dumps the mainnet programs the tests load (anchor test genesis, LiteSVM fixtures)
+set -euo pipefail
+
+cd "$(dirname "$0")/.."
+mkdir -p tests/fixtures
+solana program dump -u mainnet-beta \
+  metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s tests/fixtures/mpl_token_metadata.so